@@ -32,10 +32,14 @@ pub fn create_batch_get_builder(item: &ItemDefinition) -> TokenStream {
         let body: TokenStream = quote! {
             let hk_val: #hash_key_typ = hk.into();
             let sk_val: #sort_key_typ = sk.into();
-            let mut key = ::std::collections::HashMap::new();
-            key.insert(#hash_key_attr_name.to_string(), #hk_boxer);
-            key.insert(#sort_key_attr_name.to_string(), #sk_boxer);
-            self.keys.push(key);
+            let key = match (#hk_boxer, #sk_boxer) {
+                (Ok(hk), Ok(sk)) => Ok(::std::collections::HashMap::from([
+                    (#hash_key_attr_name.to_string(), hk),
+                    (#sort_key_attr_name.to_string(), sk),
+                ])),
+                (Err(e), _) => Err(e.at_attr(#hash_key_attr_name)),
+                (_, Err(e)) => Err(e.at_attr(#sort_key_attr_name)),
+            };
         };
 
         (method_name, sig, body)
@@ -49,9 +53,9 @@ pub fn create_batch_get_builder(item: &ItemDefinition) -> TokenStream {
         };
         let body: TokenStream = quote! {
             let hk_val: #hash_key_typ = hk.into();
-            let mut key = ::std::collections::HashMap::new();
-            key.insert(#hash_key_attr_name.to_string(), #hk_boxer);
-            self.keys.push(key);
+            let key = #hk_boxer
+                .map(|hk| ::std::collections::HashMap::from([(#hash_key_attr_name.to_string(), hk)]))
+                .map_err(|e| e.at_attr(#hash_key_attr_name));
         };
 
         (method_name, sig, body)
@@ -61,40 +65,94 @@ pub fn create_batch_get_builder(item: &ItemDefinition) -> TokenStream {
         pub struct #batch_get_struct<'a> {
             table: &'a #table_struct,
             keys: Vec<::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>>,
+            error: Option<::aymond::error::EncodeError>,
         }
 
         impl<'a> #batch_get_struct<'a> {
             fn new(table: &'a #table_struct) -> Self {
-                Self { table, keys: Vec::new() }
+                Self { table, keys: Vec::new(), error: None }
             }
 
             pub #key_method_sig -> #batch_get_keys_struct<'a> {
                 #key_body
+                match key {
+                    Ok(key) => self.keys.push(key),
+                    Err(err) => {
+                        self.error.get_or_insert(err);
+                    }
+                }
                 #batch_get_keys_struct {
                     table: self.table,
                     keys: self.keys,
+                    error: self.error,
+                    retry_strategy: None,
+                    projection: ::std::marker::PhantomData,
                 }
             }
         }
 
-        pub struct #batch_get_keys_struct<'a> {
+        pub struct #batch_get_keys_struct<'a, P = #item_struct> {
             table: &'a #table_struct,
             keys: Vec<::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>>,
+            /// The first key that couldn't be encoded, failing the request before it's sent.
+            error: Option<::aymond::error::EncodeError>,
+            retry_strategy: Option<::aymond::retry::RetryStrategy>,
+            projection: ::std::marker::PhantomData<P>,
         }
 
-        impl<'a> #batch_get_keys_struct<'a> {
+        impl<'a, P: ::aymond::projection::Projection<#item_struct>> #batch_get_keys_struct<'a, P> {
             pub #key_method_sig -> Self {
                 #key_body
+                match key {
+                    Ok(key) => self.keys.push(key),
+                    Err(err) => {
+                        self.error.get_or_insert(err);
+                    }
+                }
                 self
             }
 
+            pub fn project<Q: ::aymond::projection::Projection<#item_struct>>(self) -> #batch_get_keys_struct<'a, Q> {
+                #batch_get_keys_struct {
+                    table: self.table,
+                    keys: self.keys,
+                    error: self.error,
+                    retry_strategy: self.retry_strategy,
+                    projection: ::std::marker::PhantomData,
+                }
+            }
+
+            /// Overrides the client's retry strategy, for both failed requests and unprocessed keys.
+            pub fn retry_strategy(mut self, s: ::aymond::retry::RetryStrategy) -> Self {
+                self.retry_strategy = Some(s);
+                self
+            }
+
+            fn keys_and_attributes(
+                keys: Vec<::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>>,
+            ) -> #aws_sdk_dynamodb::types::KeysAndAttributes {
+                let mut builder = #aws_sdk_dynamodb::types::KeysAndAttributes::builder()
+                    .set_keys(Some(keys));
+                if let Some((expr, names)) = ::aymond::projection::projection_expression::<#item_struct, P>() {
+                    builder = builder
+                        .projection_expression(expr)
+                        .set_expression_attribute_names(Some(names));
+                }
+                builder.build().unwrap()
+            }
+
             pub async fn send(self) -> Result<
-                Vec<#item_struct>,
+                Vec<Result<P, ::aymond::error::DecodeError>>,
                 ::aymond::error::BatchError<
                     #aws_sdk_dynamodb::operation::batch_get_item::BatchGetItemError,
                 >,
             > {
-                let mut all_results: Vec<#item_struct> = Vec::new();
+                if let Some(err) = self.error {
+                    return Err(#aws_sdk_dynamodb::error::SdkError::construction_failure(err).into());
+                }
+                let strategy = self.retry_strategy.clone()
+                    .unwrap_or_else(|| self.table.aymond.retry_strategy.clone());
+                let mut all_results: Vec<Result<P, ::aymond::error::DecodeError>> = Vec::new();
 
                 for chunk in self.keys.chunks(100) {
                     let mut pending_keys: Vec<::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>>
@@ -102,21 +160,16 @@ pub fn create_batch_get_builder(item: &ItemDefinition) -> TokenStream {
                     let mut retries: u32 = 0;
 
                     loop {
-                        let request_keys = pending_keys;
-                        let keys_and_attrs = #aws_sdk_dynamodb::types::KeysAndAttributes::builder()
-                            .set_keys(Some(request_keys))
-                            .build()
-                            .unwrap();
+                        let keys_and_attrs = Self::keys_and_attributes(pending_keys);
 
-                        let res = self.table.aymond.client.batch_get_item()
-                            .request_items(&self.table.table_name, keys_and_attrs)
-                            .send()
-                            .await?;
+                        let req = self.table.aymond.client.batch_get_item()
+                            .request_items(&self.table.table_name, keys_and_attrs);
+                        let res = ::aymond::retry::retry(&strategy, || req.clone().send()).await?;
 
                         if let Some(responses) = res.responses() {
                             if let Some(items) = responses.get(&self.table.table_name) {
                                 for item in items {
-                                    all_results.push(item.into());
+                                    all_results.push(P::try_from(item));
                                 }
                             }
                         }
@@ -130,7 +183,7 @@ pub fn create_batch_get_builder(item: &ItemDefinition) -> TokenStream {
 
                         match has_unprocessed {
                             Some(unprocessed) => {
-                                match (self.table.aymond.retry_strategy)(retries) {
+                                match strategy(retries) {
                                     Some(duration) => {
                                         pending_keys = unprocessed;
                                         retries += 1;
@@ -165,15 +218,16 @@ pub fn create_batch_get_builder(item: &ItemDefinition) -> TokenStream {
                 F: FnOnce(#aws_sdk_dynamodb::operation::batch_get_item::builders::BatchGetItemFluentBuilder)
                     -> #aws_sdk_dynamodb::operation::batch_get_item::builders::BatchGetItemFluentBuilder
             {
-                let keys_and_attrs = #aws_sdk_dynamodb::types::KeysAndAttributes::builder()
-                    .set_keys(Some(self.keys))
-                    .build()
-                    .unwrap();
-
-                f(self.table.aymond.client.batch_get_item())
-                    .request_items(&self.table.table_name, keys_and_attrs)
-                    .send()
-                    .await
+                if let Some(err) = self.error {
+                    return Err(#aws_sdk_dynamodb::error::SdkError::construction_failure(err));
+                }
+                let strategy = self.retry_strategy
+                    .unwrap_or_else(|| self.table.aymond.retry_strategy.clone());
+                let keys_and_attrs = Self::keys_and_attributes(self.keys);
+
+                let req = f(self.table.aymond.client.batch_get_item())
+                    .request_items(&self.table.table_name, keys_and_attrs);
+                ::aymond::retry::retry(&strategy, || req.clone().send()).await
             }
         }
     }
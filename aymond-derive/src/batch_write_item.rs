@@ -28,7 +28,8 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
         let builders = quote! {
             pub struct #delete_hash_key_struct<'a> {
                 table: &'a #table_struct,
-                ops: Vec<#aws_sdk_dynamodb::types::WriteRequest>,
+                ops: Vec<Result<#aws_sdk_dynamodb::types::WriteRequest, ::aymond::error::EncodeError>>,
+                retry_strategy: Option<::aymond::retry::RetryStrategy>,
             }
 
             impl<'a> #delete_hash_key_struct<'a> {
@@ -36,6 +37,7 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
                     #delete_sort_key_struct {
                         table: self.table,
                         ops: self.ops,
+                        retry_strategy: self.retry_strategy,
                         hk: v.into(),
                     }
                 }
@@ -43,7 +45,8 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
 
             pub struct #delete_sort_key_struct<'a> {
                 table: &'a #table_struct,
-                ops: Vec<#aws_sdk_dynamodb::types::WriteRequest>,
+                ops: Vec<Result<#aws_sdk_dynamodb::types::WriteRequest, ::aymond::error::EncodeError>>,
+                retry_strategy: Option<::aymond::retry::RetryStrategy>,
                 hk: #hash_key_typ,
             }
 
@@ -51,10 +54,15 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
                 pub fn #sort_key_ident(mut self, v: impl Into<#sort_key_typ>) -> #batch_write_ops_struct<'a> {
                     let hk_val = self.hk;
                     let sk_val: #sort_key_typ = v.into();
-                    let mut key = ::std::collections::HashMap::new();
-                    key.insert(#hash_key_attr_name.to_string(), #hash_key_boxer);
-                    key.insert(#sort_key_attr_name.to_string(), #sort_key_boxer);
-                    self.ops.push(
+                    let key = match (#hash_key_boxer, #sort_key_boxer) {
+                        (Ok(hk), Ok(sk)) => Ok(::std::collections::HashMap::from([
+                            (#hash_key_attr_name.to_string(), hk),
+                            (#sort_key_attr_name.to_string(), sk),
+                        ])),
+                        (Err(e), _) => Err(e.at_attr(#hash_key_attr_name)),
+                        (_, Err(e)) => Err(e.at_attr(#sort_key_attr_name)),
+                    };
+                    self.ops.push(key.map(|key| {
                         #aws_sdk_dynamodb::types::WriteRequest::builder()
                             .delete_request(
                                 #aws_sdk_dynamodb::types::DeleteRequest::builder()
@@ -63,10 +71,11 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
                                     .unwrap()
                             )
                             .build()
-                    );
+                    }));
                     #batch_write_ops_struct {
                         table: self.table,
                         ops: self.ops,
+                        retry_strategy: self.retry_strategy,
                     }
                 }
             }
@@ -77,6 +86,7 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
                 #delete_hash_key_struct {
                     table: self.table,
                     ops: Vec::new(),
+                    retry_strategy: None,
                 }
             }
         };
@@ -86,6 +96,7 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
                 #delete_hash_key_struct {
                     table: self.table,
                     ops: self.ops,
+                    retry_strategy: self.retry_strategy,
                 }
             }
         };
@@ -95,15 +106,17 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
         let builders = quote! {
             pub struct #delete_hash_key_struct<'a> {
                 table: &'a #table_struct,
-                ops: Vec<#aws_sdk_dynamodb::types::WriteRequest>,
+                ops: Vec<Result<#aws_sdk_dynamodb::types::WriteRequest, ::aymond::error::EncodeError>>,
+                retry_strategy: Option<::aymond::retry::RetryStrategy>,
             }
 
             impl<'a> #delete_hash_key_struct<'a> {
                 pub fn #hash_key_ident(mut self, v: impl Into<#hash_key_typ>) -> #batch_write_ops_struct<'a> {
                     let hk_val: #hash_key_typ = v.into();
-                    let mut key = ::std::collections::HashMap::new();
-                    key.insert(#hash_key_attr_name.to_string(), #hash_key_boxer);
-                    self.ops.push(
+                    let key = #hash_key_boxer
+                        .map(|hk| ::std::collections::HashMap::from([(#hash_key_attr_name.to_string(), hk)]))
+                        .map_err(|e| e.at_attr(#hash_key_attr_name));
+                    self.ops.push(key.map(|key| {
                         #aws_sdk_dynamodb::types::WriteRequest::builder()
                             .delete_request(
                                 #aws_sdk_dynamodb::types::DeleteRequest::builder()
@@ -112,10 +125,11 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
                                     .unwrap()
                             )
                             .build()
-                    );
+                    }));
                     #batch_write_ops_struct {
                         table: self.table,
                         ops: self.ops,
+                        retry_strategy: self.retry_strategy,
                     }
                 }
             }
@@ -126,6 +140,7 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
                 #delete_hash_key_struct {
                     table: self.table,
                     ops: Vec::new(),
+                    retry_strategy: None,
                 }
             }
         };
@@ -135,6 +150,7 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
                 #delete_hash_key_struct {
                     table: self.table,
                     ops: self.ops,
+                    retry_strategy: self.retry_strategy,
                 }
             }
         };
@@ -155,17 +171,20 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
             }
 
             pub fn put(self, item: #item_struct) -> #batch_write_ops_struct<'a> {
-                let wr = #aws_sdk_dynamodb::types::WriteRequest::builder()
-                    .put_request(
-                        #aws_sdk_dynamodb::types::PutRequest::builder()
-                            .set_item(Some(item.into()))
-                            .build()
-                            .unwrap()
-                    )
-                    .build();
+                let wr = item.try_into().map(|item| {
+                    #aws_sdk_dynamodb::types::WriteRequest::builder()
+                        .put_request(
+                            #aws_sdk_dynamodb::types::PutRequest::builder()
+                                .set_item(Some(item))
+                                .build()
+                                .unwrap()
+                        )
+                        .build()
+                });
                 #batch_write_ops_struct {
                     table: self.table,
                     ops: vec![wr],
+                    retry_strategy: None,
                 }
             }
 
@@ -174,41 +193,53 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
 
         pub struct #batch_write_ops_struct<'a> {
             table: &'a #table_struct,
-            ops: Vec<#aws_sdk_dynamodb::types::WriteRequest>,
+            ops: Vec<Result<#aws_sdk_dynamodb::types::WriteRequest, ::aymond::error::EncodeError>>,
+            retry_strategy: Option<::aymond::retry::RetryStrategy>,
         }
 
         impl<'a> #batch_write_ops_struct<'a> {
             pub fn put(mut self, item: #item_struct) -> Self {
-                let wr = #aws_sdk_dynamodb::types::WriteRequest::builder()
-                    .put_request(
-                        #aws_sdk_dynamodb::types::PutRequest::builder()
-                            .set_item(Some(item.into()))
-                            .build()
-                            .unwrap()
-                    )
-                    .build();
+                let wr = item.try_into().map(|item| {
+                    #aws_sdk_dynamodb::types::WriteRequest::builder()
+                        .put_request(
+                            #aws_sdk_dynamodb::types::PutRequest::builder()
+                                .set_item(Some(item))
+                                .build()
+                                .unwrap()
+                        )
+                        .build()
+                });
                 self.ops.push(wr);
                 self
             }
 
             #ops_delete
 
+            /// Overrides the client's retry strategy, for both failed requests and unprocessed items.
+            pub fn retry_strategy(mut self, s: ::aymond::retry::RetryStrategy) -> Self {
+                self.retry_strategy = Some(s);
+                self
+            }
+
             pub async fn send(self) -> Result<
                 (),
                 ::aymond::error::BatchError<
                     #aws_sdk_dynamodb::operation::batch_write_item::BatchWriteItemError,
                 >,
             > {
-                for chunk in self.ops.chunks(25) {
+                let ops = self.ops.into_iter().collect::<Result<Vec<_>, _>>()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
+                let strategy = self.retry_strategy.clone()
+                    .unwrap_or_else(|| self.table.aymond.retry_strategy.clone());
+                for chunk in ops.chunks(25) {
                     let mut pending: Vec<#aws_sdk_dynamodb::types::WriteRequest> = chunk.to_vec();
                     let mut retries: u32 = 0;
 
                     loop {
                         let request_items = pending;
-                        let res = self.table.aymond.client.batch_write_item()
-                            .request_items(&self.table.table_name, request_items)
-                            .send()
-                            .await?;
+                        let req = self.table.aymond.client.batch_write_item()
+                            .request_items(&self.table.table_name, request_items);
+                        let res = ::aymond::retry::retry_write(&strategy, || req.clone().send()).await?;
 
                         let has_unprocessed = res.unprocessed_items()
                             .and_then(|u| u.get(&self.table.table_name))
@@ -218,7 +249,7 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
 
                         match has_unprocessed {
                             Some(unprocessed) => {
-                                match (self.table.aymond.retry_strategy)(retries) {
+                                match strategy(retries) {
                                     Some(duration) => {
                                         pending = unprocessed;
                                         retries += 1;
@@ -253,10 +284,13 @@ pub fn create_batch_write_builder(item: &ItemDefinition) -> TokenStream {
                 F: FnOnce(#aws_sdk_dynamodb::operation::batch_write_item::builders::BatchWriteItemFluentBuilder)
                     -> #aws_sdk_dynamodb::operation::batch_write_item::builders::BatchWriteItemFluentBuilder
             {
-                f(self.table.aymond.client.batch_write_item())
-                    .request_items(&self.table.table_name, self.ops)
-                    .send()
-                    .await
+                let ops = self.ops.into_iter().collect::<Result<Vec<_>, _>>()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
+                let strategy = self.retry_strategy
+                    .unwrap_or_else(|| self.table.aymond.retry_strategy.clone());
+                let req = f(self.table.aymond.client.batch_write_item())
+                    .request_items(&self.table.table_name, ops);
+                ::aymond::retry::retry_write(&strategy, || req.clone().send()).await
             }
         }
     }
@@ -14,11 +14,12 @@ pub fn create_condition_builder(item: &ItemDefinition) -> TokenStream {
             let fn_name = &attr.field;
             let ddb_name = &attr.ddb_name;
             let return_type = attr.condition_path_type();
+            let new_path = attr.new_condition_path(&quote! {
+                vec![::aymond::condition::PathSegment::Attr(#ddb_name.to_string())]
+            });
             quote! {
                 pub fn #fn_name(&self) -> #return_type {
-                    ::aymond::condition::ConditionPathRoot::with_prefix(
-                        vec![::aymond::condition::PathSegment::Attr(#ddb_name.to_string())]
-                    )
+                    #new_path
                 }
             }
         })
@@ -77,6 +78,10 @@ pub fn create_condition_builder(item: &ItemDefinition) -> TokenStream {
                     self.version_value.set(Some(v));
                 }
 
+                fn has_version_value(&self) -> bool {
+                    self.version_value.get().is_some()
+                }
+
                 fn set_expr(&mut self, expr: ::aymond::condition::CondExpr) {
                     self.expr = Some(expr);
                 }
@@ -85,11 +90,14 @@ pub fn create_condition_builder(item: &ItemDefinition) -> TokenStream {
 
                 fn build(
                     self,
-                ) -> (
-                    Option<String>,
-                    Option<::std::collections::HashMap<String, String>>,
-                    Option<::std::collections::HashMap<String, ::aymond::shim::aws_sdk_dynamodb::types::AttributeValue>>,
-                ) {
+                ) -> Result<
+                    (
+                        Option<String>,
+                        Option<::std::collections::HashMap<String, String>>,
+                        Option<::std::collections::HashMap<String, ::aymond::shim::aws_sdk_dynamodb::types::AttributeValue>>,
+                    ),
+                    ::aymond::error::EncodeError,
+                > {
                     let existence = self.existence.get();
                     let versioning = self.versioning.get();
                     let version_value = self.version_value.get();
@@ -141,14 +149,14 @@ pub fn create_condition_builder(item: &ItemDefinition) -> TokenStream {
 
                     match combined {
                         Some(expr) => {
-                            let (e, n, v) = expr.build();
-                            (
+                            let (e, n, v) = expr.build()?;
+                            Ok((
                                 Some(e),
                                 if n.is_empty() { None } else { Some(n) },
                                 if v.is_empty() { None } else { Some(v) },
-                            )
+                            ))
                         }
-                        None => (None, None, None),
+                        None => Ok((None, None, None)),
                     }
                 }
             }
@@ -198,11 +206,14 @@ pub fn create_condition_builder(item: &ItemDefinition) -> TokenStream {
 
                 fn build(
                     self,
-                ) -> (
-                    Option<String>,
-                    Option<::std::collections::HashMap<String, String>>,
-                    Option<::std::collections::HashMap<String, ::aymond::shim::aws_sdk_dynamodb::types::AttributeValue>>,
-                ) {
+                ) -> Result<
+                    (
+                        Option<String>,
+                        Option<::std::collections::HashMap<String, String>>,
+                        Option<::std::collections::HashMap<String, ::aymond::shim::aws_sdk_dynamodb::types::AttributeValue>>,
+                    ),
+                    ::aymond::error::EncodeError,
+                > {
                     let existence = self.existence.get();
 
                     let existence_expr = match existence {
@@ -228,14 +239,14 @@ pub fn create_condition_builder(item: &ItemDefinition) -> TokenStream {
 
                     match combined {
                         Some(expr) => {
-                            let (e, n, v) = expr.build();
-                            (
+                            let (e, n, v) = expr.build()?;
+                            Ok((
                                 Some(e),
                                 if n.is_empty() { None } else { Some(n) },
                                 if v.is_empty() { None } else { Some(v) },
-                            )
+                            ))
                         }
-                        None => (None, None, None),
+                        None => Ok((None, None, None)),
                     }
                 }
             }
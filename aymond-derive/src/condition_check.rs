@@ -29,8 +29,14 @@ pub fn create_condition_check_builder(item: &ItemDefinition) -> TokenStream {
             let hk = self.hk.unwrap();
             let sk = self.sk.unwrap();
             let mut key_values = ::std::collections::HashMap::new();
-            key_values.insert(#hash_key_attr_name.to_string(), #hash_key_boxer);
-            key_values.insert(#sort_key_attr_name.to_string(), #sort_key_boxer);
+            key_values.insert(
+                #hash_key_attr_name.to_string(),
+                #hash_key_boxer.map_err(|e| e.at_attr(#hash_key_attr_name))?,
+            );
+            key_values.insert(
+                #sort_key_attr_name.to_string(),
+                #sort_key_boxer.map_err(|e| e.at_attr(#sort_key_attr_name))?,
+            );
         };
 
         let builders = quote! {
@@ -75,7 +81,10 @@ pub fn create_condition_check_builder(item: &ItemDefinition) -> TokenStream {
         let build_key_map = quote! {
             let hk = self.hk.unwrap();
             let mut key_values = ::std::collections::HashMap::new();
-            key_values.insert(#hash_key_attr_name.to_string(), #hash_key_boxer);
+            key_values.insert(
+                #hash_key_attr_name.to_string(),
+                #hash_key_boxer.map_err(|e| e.at_attr(#hash_key_attr_name))?,
+            );
         };
 
         let builders = quote! {
@@ -123,9 +132,19 @@ pub fn create_condition_check_builder(item: &ItemDefinition) -> TokenStream {
             }
         }
 
-        impl<'a> Into<#aws_sdk_dynamodb::types::ConditionCheck> for #condition_check_struct<'a> {
-            fn into(self) -> #aws_sdk_dynamodb::types::ConditionCheck {
-                let (cond_expr, expr_name, expr_value) = self.cond.build();
+        impl<'a> TryFrom<#condition_check_struct<'a>> for #aws_sdk_dynamodb::types::ConditionCheck {
+            type Error = ::aymond::error::EncodeError;
+
+            fn try_from(check: #condition_check_struct<'a>) -> Result<Self, Self::Error> {
+                check.into_condition_check()
+            }
+        }
+
+        impl<'a> #condition_check_struct<'a> {
+            fn into_condition_check(
+                self,
+            ) -> Result<#aws_sdk_dynamodb::types::ConditionCheck, ::aymond::error::EncodeError> {
+                let (cond_expr, expr_name, expr_value) = self.cond.build()?;
                 let table_name = &self.table.table_name;
                 #build_key_map
                 let mut b = #aws_sdk_dynamodb::types::ConditionCheck::builder()
@@ -136,7 +155,7 @@ pub fn create_condition_check_builder(item: &ItemDefinition) -> TokenStream {
                         .set_expression_attribute_names(expr_name)
                         .set_expression_attribute_values(expr_value);
                 }
-                b.build().unwrap()
+                Ok(b.build().unwrap())
             }
         }
     }
@@ -1,9 +1,9 @@
 use proc_macro2::TokenStream;
-use quote::{ToTokens, format_ident};
+use quote::format_ident;
 use std::collections::HashMap;
 use syn::{
-    Data, DeriveInput, Expr, Fields, GenericArgument, Ident, Lit, LitStr, Meta, MetaList, Path,
-    PathArguments, Token, Type, parse_quote, punctuated::Punctuated,
+    Data, DeriveInput, Expr, ExprLit, Fields, GenericArgument, Ident, Lit, LitStr, Meta, MetaList,
+    MetaNameValue, Path, PathArguments, Token, Type, parse_quote, punctuated::Punctuated,
 };
 
 #[derive(Clone)]
@@ -13,6 +13,15 @@ pub struct ItemAttribute {
     pub ty: Type,
     pub is_option: bool,
     pub generics_hierarchy: Vec<String>,
+    pub converter: Option<Converter>,
+}
+
+/// A `#[aymond(with = "...")]` module providing `to_attribute_value` and `from_attribute_value`,
+/// and the scalar type (`S`, `N` or `B`) it produces, if declared.
+#[derive(Clone)]
+pub struct Converter {
+    pub module: Path,
+    pub scalar: Option<String>,
 }
 
 pub enum GsiRole {
@@ -41,7 +50,30 @@ pub struct ItemDefinition {
     pub version_attribute: Option<ItemAttribute>,
 }
 
-fn combine_error(errors: &mut Option<syn::Error>, err: syn::Error) {
+const INTEGER_TYPES: [&str; 10] = [
+    "i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128",
+];
+
+pub fn is_integer(t: &str) -> bool {
+    INTEGER_TYPES.contains(&t)
+}
+
+/// Rust types stored as DynamoDB numbers (N).
+pub fn is_number(t: &str) -> bool {
+    is_integer(t) || t == "f32" || t == "f64"
+}
+
+/// Rust collections stored as DynamoDB sets (SS, NS, BS).
+pub fn is_set(t: &str) -> bool {
+    t == "HashSet" || t == "BTreeSet"
+}
+
+/// Rust collections stored as DynamoDB maps (M) with string keys.
+pub fn is_map(t: &str) -> bool {
+    t == "HashMap" || t == "BTreeMap"
+}
+
+pub(crate) fn combine_error(errors: &mut Option<syn::Error>, err: syn::Error) {
     if let Some(existing) = errors {
         existing.combine(err);
     } else {
@@ -59,33 +91,67 @@ impl ItemAttribute {
             ty,
             is_option,
             generics_hierarchy,
+            converter: None,
+        }
+    }
+
+    /// The field type with any `Option` wrapper removed.
+    pub fn value_type(&self) -> Type {
+        if !self.is_option {
+            return self.ty.clone();
+        }
+        if let Type::Path(type_path) = &self.ty
+            && let PathArguments::AngleBracketed(args) =
+                &type_path.path.segments.last().unwrap().arguments
+            && let Some(GenericArgument::Type(inner_ty)) = args.args.first()
+        {
+            return inner_ty.clone();
         }
+        self.ty.clone()
     }
 
     pub fn generics_hierarchy(ty: &Type) -> Vec<String> {
-        fn collect(ty: &Type, idents: &mut Vec<String>) {
-            if let Type::Path(type_path) = ty {
-                for segment in &type_path.path.segments {
-                    idents.push(segment.ident.to_string());
-
-                    if let PathArguments::AngleBracketed(args) = &segment.arguments {
-                        let arg = &args.args.first().unwrap();
-                        if let GenericArgument::Type(inner_ty) = arg {
-                            collect(inner_ty, idents);
-                        }
+        Self::type_hierarchy(ty)
+            .into_iter()
+            .map(|(ident, _)| ident)
+            .collect()
+    }
+
+    /// Walks the type and its first generic argument (the value type, for maps) recursively,
+    /// returning each level's type name alongside the type itself. Module paths are ignored, so
+    /// `chrono::DateTime<Utc>` is named `DateTime`.
+    pub fn type_hierarchy(ty: &Type) -> Vec<(String, Type)> {
+        fn collect(ty: &Type, levels: &mut Vec<(String, Type)>) {
+            if let Type::Path(type_path) = ty
+                && let Some(segment) = type_path.path.segments.last()
+            {
+                levels.push((segment.ident.to_string(), ty.clone()));
+
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    // Maps are keyed by strings, so only the value type is interesting
+                    let arg = if is_map(&segment.ident.to_string()) {
+                        args.args.last().unwrap()
+                    } else {
+                        args.args.first().unwrap()
+                    };
+                    if let GenericArgument::Type(inner_ty) = arg {
+                        collect(inner_ty, levels);
                     }
                 }
             }
         }
-        let mut idents = vec![];
-        collect(ty, &mut idents);
-        idents
+        let mut levels = vec![];
+        collect(ty, &mut levels);
+        levels
     }
 
+    /// Returns a statement encoding `ident` into `map`, propagating any `EncodeError` with `?`.
     pub fn insert_into_map(&self, ident: &TokenStream, map: &TokenStream) -> TokenStream {
         let attr_name = &self.ddb_name;
         let boxer = self.to_attribute_value(ident);
-        let insert: TokenStream = parse_quote!(#map.insert(#attr_name.to_string(), #boxer););
+        let insert: TokenStream = parse_quote! {
+            #map.insert(#attr_name.to_string(), #boxer.map_err(|e| e.at_attr(#attr_name))?);
+        };
         if self.is_option {
             return parse_quote! {
                 if #ident.is_some() {
@@ -96,6 +162,8 @@ impl ItemAttribute {
         insert
     }
 
+    /// Returns an expression encoding `ident` into an `AttributeValue`, evaluating to
+    /// `Result<AttributeValue, ::aymond::error::EncodeError>`.
     pub fn to_attribute_value(&self, ident: &TokenStream) -> Expr {
         self.to_attribute_value_inner(ident, 0)
     }
@@ -103,92 +171,177 @@ impl ItemAttribute {
     fn to_attribute_value_inner(&self, ident: &TokenStream, hier: usize) -> Expr {
         let attr_val: TokenStream =
             parse_quote!(::aymond::shim::aws_sdk_dynamodb::types::AttributeValue);
+        let ok: TokenStream =
+            parse_quote!(::core::result::Result::<#attr_val, ::aymond::error::EncodeError>::Ok);
+        if let Some(converter) = &self.converter
+            && self.generics_hierarchy[hier] != "Option"
+        {
+            let module = &converter.module;
+            return parse_quote! {
+                ::aymond::marshal::IntoEncodeResult::into_encode_result(
+                    #module::to_attribute_value(&#ident),
+                )
+            };
+        }
         match &self.generics_hierarchy[hier..] {
-            [t, ..]
-                if matches!(
-                    t.as_str(),
-                    "i8" | "i16" | "i32" | "i64" | "i128" | "u8" | "u16" | "u32" | "u64" | "u128"
-                ) =>
-            {
-                parse_quote! (#attr_val::N(#ident.to_string()))
+            [t, ..] if t == "f32" || t == "f64" => {
+                parse_quote!(::aymond::marshal::encode_float(#ident))
             }
-            [t, ..] if t == "String" => parse_quote!(#attr_val::S(#ident.to_string())),
-            [h, s, ..] if h == "HashSet" && s == "String" => {
-                parse_quote!(#attr_val::Ss(#ident.iter().cloned().collect()))
+            [t, ..] if is_number(t) => {
+                parse_quote! (#ok(#attr_val::N(#ident.to_string())))
             }
-            [h, v, u, ..] if h == "HashSet" && v == "Vec" && u == "u8" => {
+            [t, ..] if t == "bool" => parse_quote!(#ok(#attr_val::Bool(#ident))),
+            [t, ..] if t == "String" => parse_quote!(#ok(#attr_val::S(#ident.to_string()))),
+            [h, s, ..] if is_set(h) && s == "String" => {
+                parse_quote!(#ok(#attr_val::Ss(#ident.iter().cloned().collect())))
+            }
+            [h, n, ..] if is_set(h) && is_number(n) => {
+                parse_quote!(#ok(#attr_val::Ns(#ident.iter().map(|e| e.to_string()).collect())))
+            }
+            [h, v, u, ..] if is_set(h) && v == "Vec" && u == "u8" => {
                 let blob: TokenStream =
                     parse_quote!(::aymond::shim::aws_sdk_dynamodb::primitives::Blob);
-                parse_quote!(#attr_val::Bs(#ident.iter().map(|e| #blob::new(e.clone())).collect()))
+                parse_quote!(#ok(#attr_val::Bs(#ident.iter().map(|e| #blob::new(e.clone())).collect())))
             }
             [v, u, ..] if v == "Vec" && u == "u8" => {
                 let blob: TokenStream =
                     parse_quote!(::aymond::shim::aws_sdk_dynamodb::primitives::Blob);
-                parse_quote!(#attr_val::B(#blob::new(#ident)))
+                parse_quote!(#ok(#attr_val::B(#blob::new(#ident))))
             }
             [v, ..] if v == "Vec" => {
                 let rec = self.to_attribute_value_inner(&parse_quote!(e), hier + 1);
-                parse_quote!(#attr_val::L(#ident.iter().map(|e| #rec).collect()))
+                parse_quote! {
+                    #ident
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, e)| #rec.map_err(|err| err.at_index(i)))
+                        .collect::<Result<_, ::aymond::error::EncodeError>>()
+                        .map(#attr_val::L)
+                }
+            }
+            [m, ..] if is_map(m) => {
+                let rec = self.to_attribute_value_inner(&parse_quote!(e), hier + 1);
+                parse_quote! {
+                    #ident
+                        .into_iter()
+                        .map(|(k, e)| match #rec {
+                            Ok(v) => Ok((k, v)),
+                            Err(err) => Err(err.at_attr(&k)),
+                        })
+                        .collect::<Result<_, ::aymond::error::EncodeError>>()
+                        .map(#attr_val::M)
+                }
             }
             [t, ..] if t == "Option" => {
                 self.to_attribute_value_inner(&parse_quote!(#ident.unwrap()), hier + 1)
             }
-            // We assume this is a struct if it's otherwise not recognized
-            _ => parse_quote!(#attr_val::M(#ident.into())),
+            // Anything else (nested items, enums, ...) knows how to convert itself
+            _ => parse_quote!(::aymond::marshal::IntoAttributeValue::into_attribute_value(#ident)),
         }
     }
 
-    pub fn unwrap_attribute_value(&self, ident: &Expr) -> Expr {
-        self.unwrap_attribute_value_inner(ident, if self.is_option { 1 } else { 0 })
+    /// Returns an expression decoding the `AttributeValue` reference `v` into this attribute's
+    /// type, evaluating to `Result<T, ::aymond::error::DecodeError>`.
+    pub fn decode_attribute_value(&self, v: &Expr) -> Expr {
+        self.decode_attribute_value_inner(v, if self.is_option { 1 } else { 0 })
     }
 
-    fn unwrap_attribute_value_inner(&self, ident: &Expr, hier: usize) -> Expr {
-        let (as_, get_value): (TokenStream, TokenStream) = match &self.generics_hierarchy[hier..] {
-            [t, ..]
-                if matches!(
-                    t.as_str(),
-                    "i8" | "i16" | "i32" | "i64" | "i128" | "u8" | "u16" | "u32" | "u64" | "u128"
-                ) =>
-            {
-                (parse_quote!(.as_n()), parse_quote!(.parse().unwrap()))
-            }
-            [t, ..] if t == "String" => (parse_quote!(.as_s()), parse_quote!(.to_string())),
-            [h, s, ..] if h == "HashSet" && s == "String" => (
-                parse_quote!(.as_ss()),
-                parse_quote!(.iter().cloned().collect()),
-            ),
-            [h, v, u, ..] if h == "HashSet" && v == "Vec" && u == "u8" => (
-                parse_quote!(.as_bs()),
-                parse_quote!(.iter().map(|b| b.clone().into_inner()).collect()),
-            ),
-            [v, u, ..] if v == "Vec" && u == "u8" => {
-                (parse_quote!(.as_b()), parse_quote!(.clone().into_inner()))
-            }
+    fn decode_attribute_value_inner(&self, v: &Expr, hier: usize) -> Expr {
+        let marshal: TokenStream = parse_quote!(::aymond::marshal);
+        if let Some(converter) = &self.converter {
+            let module = &converter.module;
+            let expected = self.expected_type_name();
+            return parse_quote! {
+                #module::from_attribute_value(#v)
+                    .map_err(|e| ::aymond::marshal::converter_error(#expected, e))
+            };
+        }
+        match &self.generics_hierarchy[hier..] {
+            [t, ..] if is_number(t) => {
+                parse_quote!(#marshal::decode_n(#v))
+            }
+            [t, ..] if t == "bool" => parse_quote!(#marshal::decode_bool(#v)),
+            [t, ..] if t == "String" => parse_quote!(#marshal::decode_s(#v)),
+            [h, s, ..] if is_set(h) && s == "String" => parse_quote!(#marshal::decode_ss(#v)),
+            [h, n, ..] if is_set(h) && is_number(n) => parse_quote!(#marshal::decode_ns(#v)),
+            [h, v_, u, ..] if is_set(h) && v_ == "Vec" && u == "u8" => {
+                parse_quote!(#marshal::decode_bs(#v))
+            }
+            [v_, u, ..] if v_ == "Vec" && u == "u8" => parse_quote!(#marshal::decode_b(#v)),
+            [v_, ..] if v_ == "Vec" => {
+                let rec = self.decode_attribute_value_inner(&parse_quote!(e), hier + 1);
+                parse_quote!(#marshal::decode_l(#v, |e| #rec))
+            }
+            [m, ..] if is_map(m) => {
+                let rec = self.decode_attribute_value_inner(&parse_quote!(e), hier + 1);
+                parse_quote!(#marshal::decode_map(#v, |e| #rec))
+            }
+            // Anything else (nested items, enums, ...) knows how to convert itself
+            _ => parse_quote!(#marshal::FromAttributeValue::from_attribute_value(#v)),
+        }
+    }
+
+    /// The field's type in `{Name}Partial`, without the `Option`. Nested items decode into their
+    /// own partial types, since a response may only carry the nested attributes an update touched.
+    pub fn partial_type(&self) -> TokenStream {
+        if self.converter.is_some() {
+            let ty = self.value_type();
+            return parse_quote!(#ty);
+        }
+        self.partial_type_inner(if self.is_option { 1 } else { 0 })
+    }
+
+    fn partial_type_inner(&self, hier: usize) -> TokenStream {
+        let ty = &Self::type_hierarchy(&self.ty)[hier].1;
+        match &self.generics_hierarchy[hier..] {
+            [t, ..] if is_number(t) || t == "bool" || t == "String" => parse_quote!(#ty),
+            [h, ..] if is_set(h) => parse_quote!(#ty),
+            [v, u, ..] if v == "Vec" && u == "u8" => parse_quote!(#ty),
             [v, ..] if v == "Vec" => {
-                let rec = self.unwrap_attribute_value_inner(&parse_quote!(e), hier + 1);
-                (
-                    parse_quote!(.as_l()),
-                    parse_quote!(.iter().map(|e| #rec).collect()),
-                )
+                let inner = self.partial_type_inner(hier + 1);
+                parse_quote!(::std::vec::Vec<#inner>)
             }
-            // We assume this is a struct if it's otherwise not recognized
-            _ => (parse_quote!(.as_m()), parse_quote!(.into())),
-        };
+            [m, ..] if is_map(m) => {
+                let map = format_ident!("{}", m);
+                let inner = self.partial_type_inner(hier + 1);
+                parse_quote!(::std::collections::#map<String, #inner>)
+            }
+            _ => parse_quote!(<#ty as ::aymond::marshal::PartialValue>::Partial),
+        }
+    }
 
-        if hier == 1 && self.is_option {
-            parse_quote!(#ident #as_ .ok().map(|e| e #get_value))
-        } else {
-            parse_quote!(#ident #as_ .unwrap() #get_value)
+    /// The DynamoDB type descriptor a required attribute is stored as, used in decode errors.
+    pub fn expected_type_name(&self) -> Expr {
+        let ty = &self.ty;
+        if let Some(converter) = &self.converter {
+            let scalar = converter.scalar.as_deref().unwrap_or("custom");
+            return parse_quote!(#scalar);
+        }
+        match self.generics_hierarchy.as_slice() {
+            [t, ..] if is_number(t) => parse_quote!("N"),
+            [t, ..] if t == "bool" => parse_quote!("BOOL"),
+            [t, ..] if t == "String" => parse_quote!("S"),
+            [h, s, ..] if is_set(h) && s == "String" => parse_quote!("SS"),
+            [h, n, ..] if is_set(h) && is_number(n) => parse_quote!("NS"),
+            [h, v, u, ..] if is_set(h) && v == "Vec" && u == "u8" => parse_quote!("BS"),
+            [v, u, ..] if v == "Vec" && u == "u8" => parse_quote!("B"),
+            [v, ..] if v == "Vec" => parse_quote!("L"),
+            [m, ..] if is_map(m) => parse_quote!("M"),
+            _ => parse_quote!(<#ty as ::aymond::marshal::FromAttributeValue>::TYPE_NAME),
         }
     }
 
     pub fn scalar_type(&self) -> Expr {
+        if let Some(Converter {
+            scalar: Some(scalar),
+            ..
+        }) = &self.converter
+        {
+            let variant = format_ident!("{}", scalar);
+            return parse_quote! {::aymond::shim::aws_sdk_dynamodb::types::ScalarAttributeType::#variant};
+        }
         match self.generics_hierarchy.as_slice() {
-            [t] if matches!(
-                t.as_str(),
-                "i8" | "i16" | "i32" | "i64" | "i128" | "u8" | "u16" | "u32" | "u64" | "u128"
-            ) =>
-            {
+            [t] if is_number(t) => {
                 parse_quote! {::aymond::shim::aws_sdk_dynamodb::types::ScalarAttributeType::N}
             }
             [t] if t == "String" => {
@@ -197,7 +350,19 @@ impl ItemAttribute {
             [v, u] if v == "Vec" && u == "u8" => {
                 parse_quote! {::aymond::shim::aws_sdk_dynamodb::types::ScalarAttributeType::B}
             }
-            _ => panic!("Unknown variable type: {}", self.ty.to_token_stream()),
+            // Anything else must name its own key type; the assertion rejects types not stored
+            // as S, N or B.
+            _ => {
+                let ty = &self.ty;
+                parse_quote! {
+                    ::aymond::shim::aws_sdk_dynamodb::types::ScalarAttributeType::from({
+                        const TYPE_NAME: &str = ::aymond::marshal::key_type_name(
+                            <#ty as ::aymond::marshal::FromAttributeValue>::TYPE_NAME,
+                        );
+                        TYPE_NAME
+                    })
+                }
+            }
         }
     }
 }
@@ -206,30 +371,35 @@ impl ItemAttribute {
     /// Returns the TokenStream for the condition path return type based on generics_hierarchy.
     /// `hier` is the starting index into generics_hierarchy (used to skip Option wrapper).
     pub fn condition_path_type(&self) -> TokenStream {
-        Self::condition_path_type_from_hierarchy(&self.generics_hierarchy, 0)
+        if self.converter.is_some() {
+            let ty = self.value_type();
+            return parse_quote!(::aymond::condition::ConvertedConditionPath<#ty>);
+        }
+        self.condition_path_type_inner(0)
+    }
+
+    /// Returns an expression constructing this attribute's condition path from `path`.
+    pub fn new_condition_path(&self, path: &TokenStream) -> TokenStream {
+        match &self.converter {
+            Some(converter) => {
+                let module = &converter.module;
+                parse_quote! {
+                    ::aymond::condition::ConvertedConditionPath::new(#path, |v| {
+                        ::aymond::marshal::IntoEncodeResult::into_encode_result(
+                            #module::to_attribute_value(v),
+                        )
+                    })
+                }
+            }
+            None => parse_quote!(::aymond::condition::ConditionPathRoot::with_prefix(#path)),
+        }
     }
 
-    fn condition_path_type_from_hierarchy(hierarchy: &[String], hier: usize) -> TokenStream {
+    fn condition_path_type_inner(&self, hier: usize) -> TokenStream {
         let cond: TokenStream = parse_quote!(::aymond::condition);
-        match &hierarchy[hier..] {
-            [t, ..] if t == "Option" => {
-                Self::condition_path_type_from_hierarchy(hierarchy, hier + 1)
-            }
-            [t, ..]
-                if matches!(
-                    t.as_str(),
-                    "i8" | "i16"
-                        | "i32"
-                        | "i64"
-                        | "i128"
-                        | "u8"
-                        | "u16"
-                        | "u32"
-                        | "u64"
-                        | "u128"
-                        | "bool"
-                ) =>
-            {
+        match &self.generics_hierarchy[hier..] {
+            [t, ..] if t == "Option" => self.condition_path_type_inner(hier + 1),
+            [t, ..] if is_number(t) || t == "bool" => {
                 let ty: TokenStream = t.parse().unwrap();
                 parse_quote!(#cond::ScalarConditionPath<#ty>)
             }
@@ -240,67 +410,104 @@ impl ItemAttribute {
                 parse_quote!(#cond::ScalarConditionPath<Vec<u8>>)
             }
             [v, ..] if v == "Vec" => {
-                let inner = Self::condition_path_type_from_hierarchy(hierarchy, hier + 1);
+                let inner = self.condition_path_type_inner(hier + 1);
                 parse_quote!(#cond::ListConditionPath<#inner>)
             }
-            [h, s, ..] if h == "HashSet" && s == "String" => {
+            [m, ..] if is_map(m) => {
+                let inner = self.condition_path_type_inner(hier + 1);
+                parse_quote!(#cond::MapConditionPath<#inner>)
+            }
+            [h, s, ..] if is_set(h) && s == "String" => {
                 parse_quote!(#cond::StringSetConditionPath)
             }
-            [h, v, u, ..] if h == "HashSet" && v == "Vec" && u == "u8" => {
+            [h, n, ..] if is_set(h) && is_number(n) => {
+                let ty: TokenStream = n.parse().unwrap();
+                parse_quote!(#cond::NumberSetConditionPath<#ty>)
+            }
+            [h, v, u, ..] if is_set(h) && v == "Vec" && u == "u8" => {
                 parse_quote!(#cond::BinarySetConditionPath)
             }
-            // Assume nested struct
-            [name, ..] => {
-                let path_ident = format_ident!("{}ConditionPath", name);
-                parse_quote!(#path_ident)
+            // Nested items, enums and feature types name their own path
+            [_, ..] => {
+                let ty = &Self::type_hierarchy(&self.ty)[hier].1;
+                parse_quote!(<#ty as #cond::HasConditionPath>::Path)
             }
             [] => panic!("Empty generics_hierarchy"),
         }
     }
 
     pub fn update_path_type(&self) -> TokenStream {
-        Self::update_path_type_from_hierarchy(&self.generics_hierarchy, 0)
+        if self.converter.is_some() {
+            let ty = self.value_type();
+            if self.is_option {
+                return parse_quote!(
+                    ::aymond::update::ConvertedUpdatePath<#ty, ::aymond::update::Optional>
+                );
+            }
+            return parse_quote!(::aymond::update::ConvertedUpdatePath<#ty>);
+        }
+        self.update_path_type_inner(0)
     }
 
-    fn update_path_type_from_hierarchy(hierarchy: &[String], hier: usize) -> TokenStream {
+    /// Returns an expression constructing this attribute's update path from `path`.
+    pub fn new_update_path(&self, path: &TokenStream) -> TokenStream {
+        match &self.converter {
+            Some(converter) => {
+                let module = &converter.module;
+                parse_quote! {
+                    ::aymond::update::ConvertedUpdatePath::new(#path, |v| {
+                        ::aymond::marshal::IntoEncodeResult::into_encode_result(
+                            #module::to_attribute_value(v),
+                        )
+                    })
+                }
+            }
+            None => parse_quote!(::aymond::update::UpdatePathRoot::with_prefix(#path)),
+        }
+    }
+
+    fn update_path_type_inner(&self, hier: usize) -> TokenStream {
         let upd: TokenStream = parse_quote!(::aymond::update);
-        match &hierarchy[hier..] {
-            [t, ..] if t == "Option" => Self::update_path_type_from_hierarchy(hierarchy, hier + 1),
-            [t, ..]
-                if matches!(
-                    t.as_str(),
-                    "i8" | "i16"
-                        | "i32"
-                        | "i64"
-                        | "i128"
-                        | "u8"
-                        | "u16"
-                        | "u32"
-                        | "u64"
-                        | "u128"
-                        | "bool"
-                ) =>
-            {
+        // Optional fields get paths whose `set` also takes `None`, which removes the attribute.
+        let optional = match hier {
+            1 if self.is_option => parse_quote!(, #upd::Optional),
+            _ => TokenStream::new(),
+        };
+        match &self.generics_hierarchy[hier..] {
+            [t, ..] if t == "Option" => self.update_path_type_inner(hier + 1),
+            [t, ..] if is_number(t) || t == "bool" => {
                 let ty: TokenStream = t.parse().unwrap();
-                parse_quote!(#upd::ScalarUpdatePath<#ty>)
+                parse_quote!(#upd::ScalarUpdatePath<#ty #optional>)
             }
-            [t, ..] if t == "String" => parse_quote!(#upd::ScalarUpdatePath<String>),
+            [t, ..] if t == "String" => parse_quote!(#upd::ScalarUpdatePath<String #optional>),
             [v, u, ..] if v == "Vec" && u == "u8" => {
-                parse_quote!(#upd::ScalarUpdatePath<Vec<u8>>)
+                parse_quote!(#upd::ScalarUpdatePath<Vec<u8> #optional>)
             }
             [v, ..] if v == "Vec" => {
-                let inner = Self::update_path_type_from_hierarchy(hierarchy, hier + 1);
+                let inner = self.update_path_type_inner(hier + 1);
                 parse_quote!(#upd::ListUpdatePath<#inner>)
             }
-            [h, s, ..] if h == "HashSet" && s == "String" => {
-                parse_quote!(#upd::SetUpdatePath<String>)
+            [m, ..] if is_map(m) => {
+                let inner = self.update_path_type_inner(hier + 1);
+                parse_quote!(#upd::MapUpdatePath<#inner>)
             }
-            [h, v, u, ..] if h == "HashSet" && v == "Vec" && u == "u8" => {
-                parse_quote!(#upd::SetUpdatePath<Vec<u8>>)
+            [h, s, ..] if is_set(h) && s == "String" => {
+                parse_quote!(#upd::SetUpdatePath<String #optional>)
             }
-            [name, ..] => {
-                let path_ident = format_ident!("{}UpdatePath", name);
-                parse_quote!(#path_ident)
+            [h, n, ..] if is_set(h) && is_number(n) => {
+                let ty: TokenStream = n.parse().unwrap();
+                parse_quote!(#upd::SetUpdatePath<#ty #optional>)
+            }
+            [h, v, u, ..] if is_set(h) && v == "Vec" && u == "u8" => {
+                parse_quote!(#upd::SetUpdatePath<Vec<u8> #optional>)
+            }
+            [_, ..] => {
+                let ty = &Self::type_hierarchy(&self.ty)[hier].1;
+                let marker: TokenStream = match hier {
+                    1 if self.is_option => parse_quote!(#upd::Optional),
+                    _ => parse_quote!(#upd::Required),
+                };
+                parse_quote!(<#ty as #upd::HasUpdatePath>::Path<#marker>)
             }
             [] => panic!("Empty generics_hierarchy"),
         }
@@ -350,86 +557,135 @@ impl ItemDefinition {
             let mut is_version = false;
             let mut gsi_entries: Vec<(String, GsiRole)> = vec![];
             let mut lsi_entries: Vec<String> = vec![];
+            let mut with: Option<Path> = None;
+            let mut scalar: Option<String> = None;
             let mut field_has_error = false;
 
             for attr in &aymond_attrs {
-                let inner: Meta = match attr.parse_args() {
-                    Ok(meta) => meta,
-                    Err(err) => {
-                        combine_error(
-                            &mut errors,
-                            syn::Error::new_spanned(
-                                attr,
-                                format!("invalid #[aymond(...)] field annotation: {err}"),
-                            ),
-                        );
-                        field_has_error = true;
-                        continue;
-                    }
-                };
-                match &inner {
-                    Meta::Path(p) if p.is_ident("hash_key") => is_hash = true,
-                    Meta::Path(p) if p.is_ident("sort_key") => is_sort = true,
-                    Meta::List(list) if list.path.is_ident("hash_key") => {
-                        is_hash = true;
-                        match Self::extract_attribute_name(list) {
-                            Ok(name) => custom_name = name,
-                            Err(err) => {
-                                combine_error(&mut errors, err);
-                                field_has_error = true;
+                let metas =
+                    match attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+                        Ok(metas) => metas,
+                        Err(err) => {
+                            combine_error(
+                                &mut errors,
+                                syn::Error::new_spanned(
+                                    attr,
+                                    format!("invalid #[aymond(...)] field annotation: {err}"),
+                                ),
+                            );
+                            field_has_error = true;
+                            continue;
+                        }
+                    };
+                for inner in metas {
+                    match &inner {
+                        Meta::Path(p) if p.is_ident("hash_key") => is_hash = true,
+                        Meta::Path(p) if p.is_ident("sort_key") => is_sort = true,
+                        Meta::List(list) if list.path.is_ident("hash_key") => {
+                            is_hash = true;
+                            match Self::extract_attribute_name(list) {
+                                Ok(name) => custom_name = name,
+                                Err(err) => {
+                                    combine_error(&mut errors, err);
+                                    field_has_error = true;
+                                }
                             }
                         }
-                    }
-                    Meta::List(list) if list.path.is_ident("sort_key") => {
-                        is_sort = true;
-                        match Self::extract_attribute_name(list) {
-                            Ok(name) => custom_name = name,
-                            Err(err) => {
-                                combine_error(&mut errors, err);
-                                field_has_error = true;
+                        Meta::List(list) if list.path.is_ident("sort_key") => {
+                            is_sort = true;
+                            match Self::extract_attribute_name(list) {
+                                Ok(name) => custom_name = name,
+                                Err(err) => {
+                                    combine_error(&mut errors, err);
+                                    field_has_error = true;
+                                }
                             }
                         }
-                    }
-                    Meta::List(list) if list.path.is_ident("attribute") => {
-                        match Self::extract_attribute_args(list) {
-                            Ok((name, version)) => {
-                                custom_name = name;
-                                is_version = version;
+                        Meta::List(list) if list.path.is_ident("attribute") => {
+                            match Self::extract_attribute_args(list) {
+                                Ok((name, version)) => {
+                                    custom_name = name;
+                                    is_version = version;
+                                }
+                                Err(err) => {
+                                    combine_error(&mut errors, err);
+                                    field_has_error = true;
+                                }
                             }
-                            Err(err) => {
-                                combine_error(&mut errors, err);
-                                field_has_error = true;
+                        }
+                        Meta::List(list) if list.path.is_ident("gsi") => {
+                            match Self::parse_gsi_args(list) {
+                                Ok(args) => gsi_entries.push(args),
+                                Err(err) => {
+                                    combine_error(&mut errors, err);
+                                    field_has_error = true;
+                                }
                             }
                         }
-                    }
-                    Meta::List(list) if list.path.is_ident("gsi") => {
-                        match Self::parse_gsi_args(list) {
-                            Ok(args) => gsi_entries.push(args),
-                            Err(err) => {
-                                combine_error(&mut errors, err);
-                                field_has_error = true;
+                        Meta::List(list) if list.path.is_ident("lsi") => {
+                            match Self::parse_lsi_args(list) {
+                                Ok(name) => lsi_entries.push(name),
+                                Err(err) => {
+                                    combine_error(&mut errors, err);
+                                    field_has_error = true;
+                                }
                             }
                         }
-                    }
-                    Meta::List(list) if list.path.is_ident("lsi") => {
-                        match Self::parse_lsi_args(list) {
-                            Ok(name) => lsi_entries.push(name),
-                            Err(err) => {
-                                combine_error(&mut errors, err);
-                                field_has_error = true;
+                        Meta::Path(p) if p.is_ident("serde") => {
+                            with = Some(parse_quote!(::aymond::serde::document));
+                        }
+                        Meta::Path(p) if p.is_ident("json") => {
+                            with = Some(parse_quote!(::aymond::serde::json));
+                            scalar = Some("S".to_string());
+                        }
+                        Meta::NameValue(nv) if nv.path.is_ident("with") => {
+                            match Self::parse_str_value(nv).and_then(|s| s.parse::<Path>()) {
+                                Ok(module) => with = Some(module),
+                                Err(err) => {
+                                    combine_error(
+                                        &mut errors,
+                                        syn::Error::new_spanned(
+                                            nv,
+                                            format!(
+                                                "invalid with annotation; expected \
+                                                 with = \"path::to::module\": {err}"
+                                            ),
+                                        ),
+                                    );
+                                    field_has_error = true;
+                                }
                             }
                         }
-                    }
-                    _ => {
-                        combine_error(
-                            &mut errors,
-                            syn::Error::new_spanned(
-                                &inner,
-                                "unknown #[aymond(...)] field annotation; expected one of: \
-                                 hash_key, sort_key, attribute(...), gsi(...), lsi(...)",
-                            ),
-                        );
-                        field_has_error = true;
+                        Meta::NameValue(nv) if nv.path.is_ident("scalar") => {
+                            match Self::parse_str_value(nv) {
+                                Ok(lit) if ["S", "N", "B"].contains(&lit.value().as_str()) => {
+                                    scalar = Some(lit.value())
+                                }
+                                _ => {
+                                    combine_error(
+                                        &mut errors,
+                                        syn::Error::new_spanned(
+                                            nv,
+                                            "invalid scalar annotation; expected scalar = \"S\", \
+                                             \"N\" or \"B\"",
+                                        ),
+                                    );
+                                    field_has_error = true;
+                                }
+                            }
+                        }
+                        _ => {
+                            combine_error(
+                                &mut errors,
+                                syn::Error::new_spanned(
+                                    &inner,
+                                    "unknown #[aymond(...)] field annotation; expected one of: \
+                                     hash_key, sort_key, attribute(...), gsi(...), lsi(...), \
+                                     with = \"...\", scalar = \"...\", serde, json",
+                                ),
+                            );
+                            field_has_error = true;
+                        }
                     }
                 }
             }
@@ -469,8 +725,38 @@ impl ItemDefinition {
             let field_name = field_ident.to_string();
             let attr_name = custom_name.unwrap_or_else(|| field_name.clone());
 
+            let is_key = is_hash || is_sort || !gsi_entries.is_empty() || !lsi_entries.is_empty();
+            if with.is_none() && scalar.is_some() {
+                combine_error(
+                    &mut errors,
+                    syn::Error::new_spanned(
+                        &field_ident,
+                        "scalar = \"...\" requires #[aymond(with = \"...\")]",
+                    ),
+                );
+            } else if with.is_some() && scalar.is_none() && is_key {
+                combine_error(
+                    &mut errors,
+                    syn::Error::new_spanned(
+                        &field_ident,
+                        "key attributes using #[aymond(with = \"...\")] must declare \
+                         scalar = \"S\", \"N\" or \"B\"",
+                    ),
+                );
+            } else if with.is_some() && is_version {
+                combine_error(
+                    &mut errors,
+                    syn::Error::new_spanned(
+                        &field_ident,
+                        "#[aymond(attribute(version))] cannot be combined with \
+                         #[aymond(with = \"...\")]",
+                    ),
+                );
+            }
+
             let ty = field.ty.clone();
-            let item_attribute = ItemAttribute::new(field_ident, attr_name, ty);
+            let mut item_attribute = ItemAttribute::new(field_ident, attr_name, ty);
+            item_attribute.converter = with.map(|module| Converter { module, scalar });
 
             for (idx_name, role) in gsi_entries {
                 let def = gsis
@@ -496,10 +782,7 @@ impl ItemDefinition {
             }
 
             if is_version {
-                let numeric_types = [
-                    "i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128",
-                ];
-                if !numeric_types.contains(&item_attribute.generics_hierarchy[0].as_str()) {
+                if !is_integer(&item_attribute.generics_hierarchy[0]) {
                     combine_error(
                         &mut errors,
                         syn::Error::new_spanned(
@@ -639,7 +922,7 @@ impl ItemDefinition {
 
     /// Parses `attribute(...)` args, returning `(custom_name, is_version)`.
     /// Supports: `attribute(name = "x")`, `attribute(version)`, `attribute(name = "x", version)`.
-    fn extract_attribute_args(list: &MetaList) -> syn::Result<(Option<String>, bool)> {
+    pub(crate) fn extract_attribute_args(list: &MetaList) -> syn::Result<(Option<String>, bool)> {
         let mut custom_name = None;
         let mut is_version = false;
 
@@ -684,6 +967,15 @@ impl ItemDefinition {
         Ok((custom_name, is_version))
     }
 
+    fn parse_str_value(nv: &MetaNameValue) -> syn::Result<LitStr> {
+        match &nv.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(s), ..
+            }) => Ok(s.clone()),
+            value => Err(syn::Error::new_spanned(value, "expected a string literal")),
+        }
+    }
+
     fn extract_attribute_name(list: &MetaList) -> syn::Result<Option<String>> {
         Ok(Self::extract_attribute_args(list)?.0)
     }
@@ -35,12 +35,17 @@ pub fn create_delete_builder(item: &ItemDefinition) -> TokenStream {
         let sort_key_boxer = &sort_key.to_attribute_value(&parse_quote!(sk));
 
         let build_key_map = quote! {
-            let hk = self.hk.unwrap();
+            let hk = self.hk.take().unwrap();
             let mut key_values = ::std::collections::HashMap::new();
-            key_values.insert(#hash_key_attr_name.to_string(), #hash_key_boxer);
-            if self.sk.is_some() {
-                let sk = self.sk.unwrap();
-                key_values.insert(#sort_key_attr_name.to_string(), #sort_key_boxer);
+            key_values.insert(
+                #hash_key_attr_name.to_string(),
+                #hash_key_boxer.map_err(|e| e.at_attr(#hash_key_attr_name))?,
+            );
+            if let Some(sk) = self.sk.take() {
+                key_values.insert(
+                    #sort_key_attr_name.to_string(),
+                    #sort_key_boxer.map_err(|e| e.at_attr(#sort_key_attr_name))?,
+                );
             }
         };
 
@@ -54,11 +59,18 @@ pub fn create_delete_builder(item: &ItemDefinition) -> TokenStream {
                 hk: Option<#hash_key_typ>,
                 sk: Option<#sort_key_typ>,
                 cond: #condition_builder_struct,
+                retry_strategy: Option<::aymond::retry::RetryStrategy>,
             }
 
             impl<'a> #delete_item_struct<'a> {
                 fn new(table: &'a #table_struct) -> #hash_key_struct<'a> {
-                    let q = #delete_item_struct { table, hk: None, sk: None, cond: #condition_builder_struct::new() };
+                    let q = #delete_item_struct {
+                        table,
+                        hk: None,
+                        sk: None,
+                        cond: #condition_builder_struct::new(),
+                        retry_strategy: None,
+                    };
                     #hash_key_struct { q }
                 }
             }
@@ -92,9 +104,12 @@ pub fn create_delete_builder(item: &ItemDefinition) -> TokenStream {
         (builders, build_key_map)
     } else {
         let build_key_map = quote! {
-            let hk = self.hk.unwrap();
+            let hk = self.hk.take().unwrap();
             let mut key_values = ::std::collections::HashMap::new();
-            key_values.insert(#hash_key_attr_name.to_string(), #hash_key_boxer);
+            key_values.insert(
+                #hash_key_attr_name.to_string(),
+                #hash_key_boxer.map_err(|e| e.at_attr(#hash_key_attr_name))?,
+            );
         };
 
         let builders = quote! {
@@ -106,11 +121,17 @@ pub fn create_delete_builder(item: &ItemDefinition) -> TokenStream {
                 table: &'a #table_struct,
                 hk: Option<#hash_key_typ>,
                 cond: #condition_builder_struct,
+                retry_strategy: Option<::aymond::retry::RetryStrategy>,
             }
 
             impl<'a> #delete_item_struct<'a> {
                 fn new(table: &'a #table_struct) -> #hash_key_struct<'a> {
-                    let q = #delete_item_struct { table, hk: None, cond: #condition_builder_struct::new() };
+                    let q = #delete_item_struct {
+                        table,
+                        hk: None,
+                        cond: #condition_builder_struct::new(),
+                        retry_strategy: None,
+                    };
                     #hash_key_struct { q }
                 }
             }
@@ -136,6 +157,16 @@ pub fn create_delete_builder(item: &ItemDefinition) -> TokenStream {
         #builders
 
         impl<'a> #delete_item_struct<'a> {
+            fn key_values(
+                &mut self,
+            ) -> Result<
+                ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>,
+                ::aymond::error::EncodeError,
+            > {
+                #build_key_map
+                Ok(key_values)
+            }
+
             pub fn condition<F, R>(mut self, f: F) -> #delete_item_struct<'a>
             where
                 F: FnOnce(&#condition_builder_struct) -> R,
@@ -148,6 +179,11 @@ pub fn create_delete_builder(item: &ItemDefinition) -> TokenStream {
                 self
             }
 
+            pub fn retry_strategy(mut self, s: ::aymond::retry::RetryStrategy) -> #delete_item_struct<'a> {
+                self.retry_strategy = Some(s);
+                self
+            }
+
             pub async fn send(self) -> Result<
                 (),
                 #aws_sdk_dynamodb::error::SdkError<
@@ -159,8 +195,20 @@ pub fn create_delete_builder(item: &ItemDefinition) -> TokenStream {
                 Ok(())
             }
 
+            pub async fn send_returning_old(self) -> Result<
+                Option<#item_struct>,
+                ::aymond::error::RequestError<
+                    #aws_sdk_dynamodb::operation::delete_item::DeleteItemError,
+                >,
+            > {
+                let res = self
+                    .raw(|r| r.return_values(#aws_sdk_dynamodb::types::ReturnValue::AllOld))
+                    .await?;
+                Ok(res.attributes().map(#item_struct::try_from).transpose()?)
+            }
+
             pub async fn raw<F>(
-                self,
+                mut self,
                 f: F,
             ) -> Result<
                 ::aymond::shim::aws_sdk_dynamodb::operation::delete_item::DeleteItemOutput,
@@ -173,10 +221,14 @@ pub fn create_delete_builder(item: &ItemDefinition) -> TokenStream {
                 F: FnOnce(#aws_sdk_dynamodb::operation::delete_item::builders::DeleteItemFluentBuilder)
                 -> #aws_sdk_dynamodb::operation::delete_item::builders::DeleteItemFluentBuilder
             {
-                let (cond_expr, expr_name, expr_value) = self.cond.build();
+                let key_values = self.key_values()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
+                let (cond_expr, expr_name, expr_value) = self.cond.build()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
                 let table_name = &self.table.table_name;
                 let client = &self.table.aymond.client;
-                #build_key_map
+                let strategy = self.retry_strategy.clone()
+                    .unwrap_or_else(|| self.table.aymond.retry_strategy.clone());
                 let mut req = f(client.delete_item())
                     .table_name(table_name)
                     .set_key(Some(key_values));
@@ -185,17 +237,23 @@ pub fn create_delete_builder(item: &ItemDefinition) -> TokenStream {
                         .set_expression_attribute_names(expr_name)
                         .set_expression_attribute_values(expr_value);
                 }
-                req
-                    .send()
-                    .await
+                ::aymond::retry::retry_write(&strategy, || req.clone().send()).await
+            }
+        }
+
+        impl<'a> TryFrom<#delete_item_struct<'a>> for #aws_sdk_dynamodb::types::Delete {
+            type Error = ::aymond::error::EncodeError;
+
+            fn try_from(delete: #delete_item_struct<'a>) -> Result<Self, Self::Error> {
+                delete.into_delete()
             }
         }
 
-        impl<'a> Into<#aws_sdk_dynamodb::types::Delete> for #delete_item_struct<'a> {
-            fn into(self) -> #aws_sdk_dynamodb::types::Delete {
-                let (cond_expr, expr_name, expr_value) = self.cond.build();
+        impl<'a> #delete_item_struct<'a> {
+            fn into_delete(mut self) -> Result<#aws_sdk_dynamodb::types::Delete, ::aymond::error::EncodeError> {
+                let key_values = self.key_values()?;
+                let (cond_expr, expr_name, expr_value) = self.cond.build()?;
                 let table_name = &self.table.table_name;
-                #build_key_map
                 let mut b = #aws_sdk_dynamodb::types::Delete::builder()
                     .table_name(table_name)
                     .set_key(Some(key_values));
@@ -204,7 +262,7 @@ pub fn create_delete_builder(item: &ItemDefinition) -> TokenStream {
                         .set_expression_attribute_names(expr_name)
                         .set_expression_attribute_values(expr_value);
                 }
-                b.build().unwrap()
+                Ok(b.build().unwrap())
             }
         }
     }
@@ -0,0 +1,405 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident, Meta, parse_quote};
+
+use crate::{
+    definition::{ItemAttribute, ItemDefinition, combine_error},
+    marshal::field_decoder,
+    nested_item::{
+        condition_attribute_functions, create_condition_path_struct, create_update_path_struct,
+    },
+    util::to_snake_case,
+};
+
+pub const DEFAULT_TAG: &str = "type";
+
+pub struct EnumVariant {
+    pub ident: Ident,
+    pub name: String,
+    /// `None` for unit variants.
+    pub fields: Option<Vec<ItemAttribute>>,
+}
+
+pub struct EnumDefinition {
+    pub name: String,
+    pub tag: String,
+    pub variants: Vec<EnumVariant>,
+}
+
+impl EnumDefinition {
+    pub fn new(ast: &mut DeriveInput, tag: Option<String>) -> syn::Result<Self> {
+        let name = ast.ident.to_string();
+        let tag = tag.unwrap_or_else(|| DEFAULT_TAG.to_string());
+        let data_enum = match &mut ast.data {
+            Data::Enum(data_enum) => data_enum,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &ast.ident,
+                    "#[aymond(enum)] supports only enums",
+                ));
+            }
+        };
+
+        let mut variants = vec![];
+        let mut errors = None;
+
+        for variant in &mut data_enum.variants {
+            let fields = match &mut variant.fields {
+                Fields::Unit => None,
+                Fields::Named(fields_named) => {
+                    let mut attributes = vec![];
+                    for field in &mut fields_named.named {
+                        let mut custom_name = None;
+                        for attr in field.attrs.iter().filter(|a| a.path().is_ident("aymond")) {
+                            match attr.parse_args::<Meta>() {
+                                Ok(Meta::List(list)) if list.path.is_ident("attribute") => {
+                                    match ItemDefinition::extract_attribute_args(&list) {
+                                        Ok((name, false)) => custom_name = name,
+                                        Ok((_, true)) => combine_error(
+                                            &mut errors,
+                                            syn::Error::new_spanned(
+                                                &list,
+                                                "#[aymond(attribute(version))] is not supported \
+                                                 on enum variant fields",
+                                            ),
+                                        ),
+                                        Err(err) => combine_error(&mut errors, err),
+                                    }
+                                }
+                                Ok(other) => combine_error(
+                                    &mut errors,
+                                    syn::Error::new_spanned(
+                                        other,
+                                        "unknown #[aymond(...)] enum field annotation; \
+                                         expected attribute(name = \"...\")",
+                                    ),
+                                ),
+                                Err(err) => combine_error(
+                                    &mut errors,
+                                    syn::Error::new_spanned(
+                                        attr,
+                                        format!("invalid #[aymond(...)] field annotation: {err}"),
+                                    ),
+                                ),
+                            }
+                        }
+                        field.attrs.retain(|attr| !attr.path().is_ident("aymond"));
+
+                        let field_ident = field.ident.clone().unwrap();
+                        let ddb_name = custom_name.unwrap_or_else(|| field_ident.to_string());
+                        if ddb_name == tag {
+                            combine_error(
+                                &mut errors,
+                                syn::Error::new_spanned(
+                                    &field_ident,
+                                    format!(
+                                        "attribute `{ddb_name}` conflicts with the enum tag; \
+                                         rename it or use #[aymond(enum, tag = \"...\")]"
+                                    ),
+                                ),
+                            );
+                        }
+                        attributes.push(ItemAttribute::new(
+                            field_ident,
+                            ddb_name,
+                            field.ty.clone(),
+                        ));
+                    }
+                    Some(attributes)
+                }
+                Fields::Unnamed(_) => {
+                    combine_error(
+                        &mut errors,
+                        syn::Error::new_spanned(
+                            &variant.ident,
+                            "#[aymond(enum)] does not support tuple variants; use named fields",
+                        ),
+                    );
+                    continue;
+                }
+            };
+            variants.push(EnumVariant {
+                ident: variant.ident.clone(),
+                name: variant.ident.to_string(),
+                fields,
+            });
+        }
+
+        if let Some(err) = errors {
+            return Err(err);
+        }
+
+        Ok(EnumDefinition {
+            name,
+            tag,
+            variants,
+        })
+    }
+
+    pub fn is_unit_only(&self) -> bool {
+        self.variants.iter().all(|v| v.fields.is_none())
+    }
+}
+
+pub fn create_enum(input: &mut DeriveInput, tag: Option<String>) -> syn::Result<TokenStream> {
+    let def = EnumDefinition::new(input, tag)?;
+    let body = if def.is_unit_only() {
+        create_unit_enum(&def)
+    } else {
+        create_tagged_enum(&def)
+    };
+    Ok(quote! {
+        #[derive(Debug, PartialEq)]
+        #input
+        #body
+    })
+}
+
+/// Unit-only enums are stored as a string holding the variant name.
+fn create_unit_enum(def: &EnumDefinition) -> TokenStream {
+    let attr_val: TokenStream = quote!(::aymond::shim::aws_sdk_dynamodb::types::AttributeValue);
+    let name = format_ident!("{}", def.name);
+    let condition_path = format_ident!("{}ConditionPath", def.name);
+    let update_path = format_ident!("{}UpdatePath", def.name);
+
+    let variant_idents: Vec<&Ident> = def.variants.iter().map(|v| &v.ident).collect();
+    let variant_names: Vec<&String> = def.variants.iter().map(|v| &v.name).collect();
+
+    quote! {
+        impl ::aymond::marshal::IntoAttributeValue for #name {
+            fn into_attribute_value(self) -> Result<#attr_val, ::aymond::error::EncodeError> {
+                let name = match self {
+                    #( #name::#variant_idents => #variant_names, )*
+                };
+                Ok(#attr_val::S(name.to_string()))
+            }
+        }
+
+        impl ::aymond::marshal::FromAttributeValue for #name {
+            const TYPE_NAME: &'static str = "S";
+
+            fn from_attribute_value(v: &#attr_val) -> Result<Self, ::aymond::error::DecodeError> {
+                match ::aymond::marshal::decode_s(v)?.as_str() {
+                    #( #variant_names => Ok(#name::#variant_idents), )*
+                    other => Err(::aymond::error::DecodeError::invalid(
+                        "S",
+                        format!("unknown variant `{}`", other),
+                    )),
+                }
+            }
+        }
+
+        impl ::aymond::condition::IntoConditionValue for #name {
+            fn into_condition_value(self) -> Result<#attr_val, ::aymond::error::EncodeError> {
+                ::aymond::marshal::IntoAttributeValue::into_attribute_value(self)
+            }
+        }
+
+        impl ::aymond::update::IntoUpdateValue for #name {
+            fn into_update_value(self) -> Result<#attr_val, ::aymond::error::EncodeError> {
+                ::aymond::marshal::IntoAttributeValue::into_attribute_value(self)
+            }
+        }
+
+        pub type #condition_path = ::aymond::condition::ScalarConditionPath<#name>;
+        pub type #update_path<O = ::aymond::update::Required> =
+            ::aymond::update::ScalarUpdatePath<#name, O>;
+
+        impl ::aymond::marshal::PartialValue for #name {
+            type Partial = #name;
+        }
+
+        impl ::aymond::condition::HasConditionPath for #name {
+            type Path = #condition_path;
+        }
+
+        impl ::aymond::update::HasUpdatePath for #name {
+            type Path<O> = #update_path<O>;
+        }
+    }
+}
+
+/// Enums with fields are stored as a map, with the variant name held in the tag attribute
+/// alongside the variant's fields.
+fn create_tagged_enum(def: &EnumDefinition) -> TokenStream {
+    let attr_val: TokenStream = quote!(::aymond::shim::aws_sdk_dynamodb::types::AttributeValue);
+    let name = format_ident!("{}", def.name);
+    let tag = &def.tag;
+    let condition_path = format_ident!("{}ConditionPath", def.name);
+    let update_path = format_ident!("{}UpdatePath", def.name);
+    let attribute_functions = condition_attribute_functions();
+
+    let mut encode_arms = vec![];
+    let mut decode_arms = vec![];
+    let mut condition_accessors = vec![];
+    let mut update_accessors = vec![];
+    let mut variant_paths = vec![];
+
+    for variant in &def.variants {
+        let variant_ident = &variant.ident;
+        let variant_name = &variant.name;
+        let Some(fields) = &variant.fields else {
+            encode_arms.push(quote! {
+                #name::#variant_ident => {
+                    map.insert(#tag.to_string(), #attr_val::S(#variant_name.to_string()));
+                }
+            });
+            decode_arms.push(quote! {
+                #variant_name => Ok(#name::#variant_ident),
+            });
+            continue;
+        };
+
+        let field_idents: Vec<&Ident> = fields.iter().map(|f| &f.field).collect();
+        let inserts: Vec<TokenStream> = fields
+            .iter()
+            .map(|f| {
+                let ident = &f.field;
+                f.insert_into_map(&quote!(#ident), &quote!(map))
+            })
+            .collect();
+        let decoders = fields.iter().map(field_decoder);
+        encode_arms.push(quote! {
+            #name::#variant_ident { #( #field_idents ),* } => {
+                map.insert(#tag.to_string(), #attr_val::S(#variant_name.to_string()));
+                #( #inserts )*
+            }
+        });
+        decode_arms.push(quote! {
+            #variant_name => Ok(#name::#variant_ident { #( #field_idents: #decoders ),* }),
+        });
+
+        let accessor = format_ident!("{}", to_snake_case(variant_name));
+        let variant_condition_path = format_ident!("{}{}ConditionPath", def.name, variant_name);
+        let variant_update_path = format_ident!("{}{}UpdatePath", def.name, variant_name);
+        condition_accessors.push(quote! {
+            pub fn #accessor(&self) -> #variant_condition_path {
+                ::aymond::condition::ConditionPathRoot::with_prefix(self.path_prefix.clone())
+            }
+        });
+        update_accessors.push(quote! {
+            pub fn #accessor(&self) -> #variant_update_path {
+                ::aymond::update::UpdatePathRoot::with_prefix(self.path_prefix.clone())
+            }
+        });
+        variant_paths.push(create_condition_path_struct(
+            &variant_condition_path,
+            fields.iter(),
+        ));
+        variant_paths.push(create_update_path_struct(
+            &parse_quote!(pub),
+            &variant_update_path,
+            fields.iter(),
+        ));
+    }
+
+    quote! {
+        impl ::aymond::marshal::IntoAttributeValue for #name {
+            fn into_attribute_value(self) -> Result<#attr_val, ::aymond::error::EncodeError> {
+                let mut map = ::std::collections::HashMap::new();
+                match self {
+                    #( #encode_arms )*
+                }
+                Ok(#attr_val::M(map))
+            }
+        }
+
+        impl ::aymond::marshal::FromAttributeValue for #name {
+            const TYPE_NAME: &'static str = "M";
+
+            fn from_attribute_value(v: &#attr_val) -> Result<Self, ::aymond::error::DecodeError> {
+                let map = ::aymond::marshal::as_m(v)?;
+                let tag = ::aymond::marshal::decode_s(::aymond::marshal::required(map, #tag, "S")?)
+                    .map_err(|e| e.at_attr(#tag))?;
+                match tag.as_str() {
+                    #( #decode_arms )*
+                    other => Err(::aymond::error::DecodeError::invalid(
+                        "S",
+                        format!("unknown variant `{}`", other),
+                    )
+                    .at_attr(#tag)),
+                }
+            }
+        }
+
+        pub struct #condition_path {
+            path_prefix: Vec<::aymond::condition::PathSegment>,
+        }
+
+        impl ::aymond::condition::ConditionPathRoot for #condition_path {
+            fn with_prefix(path: Vec<::aymond::condition::PathSegment>) -> Self {
+                Self { path_prefix: path }
+            }
+        }
+
+        impl #condition_path {
+            pub fn tag(&self) -> ::aymond::condition::ScalarConditionPath<String> {
+                let mut path = self.path_prefix.clone();
+                path.push(::aymond::condition::PathSegment::Attr(#tag.to_string()));
+                ::aymond::condition::ConditionPathRoot::with_prefix(path)
+            }
+
+            #attribute_functions
+
+            #( #condition_accessors )*
+        }
+
+        pub struct #update_path<O = ::aymond::update::Required> {
+            path_prefix: Vec<::aymond::update::PathSegment>,
+            _phantom: ::std::marker::PhantomData<O>,
+        }
+
+        impl ::aymond::marshal::PartialValue for #name {
+            type Partial = #name;
+        }
+
+        impl ::aymond::condition::HasConditionPath for #name {
+            type Path = #condition_path;
+        }
+
+        impl ::aymond::update::HasUpdatePath for #name {
+            type Path<O> = #update_path<O>;
+        }
+
+        impl<O> ::aymond::update::UpdatePathRoot for #update_path<O> {
+            fn with_prefix(path: Vec<::aymond::update::PathSegment>) -> Self {
+                Self {
+                    path_prefix: path,
+                    _phantom: ::std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #update_path {
+            pub fn set(self, v: #name) -> ::aymond::update::UpdateExpr {
+                match ::aymond::marshal::IntoAttributeValue::into_attribute_value(v) {
+                    Ok(v) => ::aymond::update::UpdateExpr::set(self.path_prefix, v),
+                    Err(err) => ::aymond::update::UpdateExpr::invalid(err),
+                }
+            }
+        }
+
+        impl #update_path<::aymond::update::Optional> {
+            /// Sets the attribute, or removes it if `v` is `None`.
+            pub fn set<M>(
+                self,
+                v: impl ::aymond::update::IntoOptionalValue<#name, M>,
+            ) -> ::aymond::update::UpdateExpr {
+                let value = v
+                    .into_optional_value()
+                    .map(::aymond::marshal::IntoAttributeValue::into_attribute_value);
+                ::aymond::update::UpdateExpr::set_or_remove_encoded(self.path_prefix, value)
+            }
+        }
+
+        impl<O> #update_path<O> {
+            pub fn remove(self) -> ::aymond::update::UpdateExpr {
+                ::aymond::update::UpdateExpr::remove(self.path_prefix)
+            }
+
+            #( #update_accessors )*
+        }
+
+        #( #variant_paths )*
+    }
+}
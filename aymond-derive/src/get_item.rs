@@ -15,26 +15,29 @@ pub fn create_get_builder(item: &ItemDefinition) -> TokenStream {
     let hash_key_attr_name = &hash_key.ddb_name;
     let hash_key_ident = &hash_key.field;
     let hash_key_typ = &hash_key.ty;
-    let hash_key_boxer = &hash_key.to_attribute_value(&parse_quote!(self.hk.unwrap()));
+    let hash_key_boxer = &hash_key.to_attribute_value(&parse_quote!(get.hk.unwrap()));
 
-    let builders = if item.sort_key.is_some() {
+    let (builders, fields) = if item.sort_key.is_some() {
         let sort_key = item.sort_key.as_ref().unwrap();
         let sort_key_struct = format_ident!("{}GetItemSortKey", &item.name);
         let sort_key_ident = &sort_key.field;
         let sort_key_attr_name = &sort_key.ddb_name;
         let sort_key_typ = &sort_key.ty;
-        let sort_key_boxer = &sort_key.to_attribute_value(&parse_quote!(self.sk.unwrap()));
+        let sort_key_boxer = &sort_key.to_attribute_value(&parse_quote!(get.sk.unwrap()));
 
-        quote! {
+        let builders = quote! {
             pub struct #hash_key_struct<'a> {
                 q: #get_item_struct<'a>,
             }
 
-            pub struct #get_item_struct<'a> {
+            pub struct #get_item_struct<'a, P = #item_struct> {
                 table: &'a #table_struct,
                 hk: Option<#hash_key_typ>,
                 sk: Option<#sort_key_typ>,
                 consistent_read: Option<bool>,
+                retry_strategy: Option<::aymond::retry::RetryStrategy>,
+
+                projection: ::std::marker::PhantomData<P>,
             }
 
             impl<'a> #get_item_struct<'a> {
@@ -44,6 +47,8 @@ pub fn create_get_builder(item: &ItemDefinition) -> TokenStream {
                         hk: None,
                         sk: None,
                         consistent_read: None,
+                        retry_strategy: None,
+                        projection: ::std::marker::PhantomData,
                     };
                     #hash_key_struct { q }
                 }
@@ -67,27 +72,42 @@ pub fn create_get_builder(item: &ItemDefinition) -> TokenStream {
                 }
             }
 
-            impl<'a> Into<::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>> for #get_item_struct<'a> {
-                fn into(self) -> ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue> {
+            impl<'a, P> TryFrom<#get_item_struct<'a, P>> for ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue> {
+                type Error = ::aymond::error::EncodeError;
+
+                fn try_from(get: #get_item_struct<'a, P>) -> Result<Self, Self::Error> {
                     let mut key_values = ::std::collections::HashMap::new();
-                    key_values.insert(#hash_key_attr_name.to_string(), #hash_key_boxer);
-                    if self.sk.is_some() {
-                        key_values.insert(#sort_key_attr_name.to_string(), #sort_key_boxer);
+                    key_values.insert(
+                        #hash_key_attr_name.to_string(),
+                        #hash_key_boxer.map_err(|e| e.at_attr(#hash_key_attr_name))?,
+                    );
+                    if get.sk.is_some() {
+                        key_values.insert(
+                            #sort_key_attr_name.to_string(),
+                            #sort_key_boxer.map_err(|e| e.at_attr(#sort_key_attr_name))?,
+                        );
                     }
-                    key_values
+                    Ok(key_values)
                 }
             }
-        }
+        };
+        (
+            builders,
+            quote! { table, hk, sk, consistent_read, retry_strategy },
+        )
     } else {
-        quote! {
+        let builders = quote! {
             pub struct #hash_key_struct<'a> {
                 q: #get_item_struct<'a>,
             }
 
-            pub struct #get_item_struct<'a> {
+            pub struct #get_item_struct<'a, P = #item_struct> {
                 table: &'a #table_struct,
                 hk: Option<#hash_key_typ>,
                 consistent_read: Option<bool>,
+                retry_strategy: Option<::aymond::retry::RetryStrategy>,
+
+                projection: ::std::marker::PhantomData<P>,
             }
 
             impl<'a> #get_item_struct<'a> {
@@ -96,6 +116,8 @@ pub fn create_get_builder(item: &ItemDefinition) -> TokenStream {
                         table,
                         hk: None,
                         consistent_read: None,
+                        retry_strategy: None,
+                        projection: ::std::marker::PhantomData,
                     };
                     #hash_key_struct { q }
                 }
@@ -108,34 +130,55 @@ pub fn create_get_builder(item: &ItemDefinition) -> TokenStream {
                 }
             }
 
-            impl<'a> Into<::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>> for #get_item_struct<'a> {
-                fn into(self) -> ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue> {
+            impl<'a, P> TryFrom<#get_item_struct<'a, P>> for ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue> {
+                type Error = ::aymond::error::EncodeError;
+
+                fn try_from(get: #get_item_struct<'a, P>) -> Result<Self, Self::Error> {
                     let mut key_values = ::std::collections::HashMap::new();
-                    key_values.insert(#hash_key_attr_name.to_string(), #hash_key_boxer);
-                    key_values
+                    key_values.insert(
+                        #hash_key_attr_name.to_string(),
+                        #hash_key_boxer.map_err(|e| e.at_attr(#hash_key_attr_name))?,
+                    );
+                    Ok(key_values)
                 }
             }
-        }
+        };
+        (
+            builders,
+            quote! { table, hk, consistent_read, retry_strategy },
+        )
     };
 
     quote! {
         #builders
 
-        impl<'a> #get_item_struct<'a> {
+        impl<'a, P: ::aymond::projection::Projection<#item_struct>> #get_item_struct<'a, P> {
             pub fn consistent_read(mut self, v: bool) -> Self {
                 self.consistent_read = Some(v);
                 self
             }
 
+            pub fn retry_strategy(mut self, s: ::aymond::retry::RetryStrategy) -> Self {
+                self.retry_strategy = Some(s);
+                self
+            }
+
+            pub fn project<Q: ::aymond::projection::Projection<#item_struct>>(self) -> #get_item_struct<'a, Q> {
+                let #get_item_struct { #fields, .. } = self;
+                #get_item_struct {
+                    #fields,
+                    projection: ::std::marker::PhantomData,
+                }
+            }
+
             pub async fn send(self) -> Result<
-                Option<#item_struct>,
-                ::aymond::shim::aws_sdk_dynamodb::error::SdkError<
+                Option<P>,
+                ::aymond::error::RequestError<
                     ::aymond::shim::aws_sdk_dynamodb::operation::get_item::GetItemError,
-                    ::aymond::shim::aws_sdk_dynamodb::config::http::HttpResponse,
                 >,
             > {
                 let res = self.raw(|r| r).await?;
-                Ok(res.item().map(|e| e.into()))
+                Ok(res.item().map(P::try_from).transpose()?)
             }
 
             pub async fn raw<F>(
@@ -153,12 +196,44 @@ pub fn create_get_builder(item: &ItemDefinition) -> TokenStream {
                 -> #aws_sdk_dynamodb::operation::get_item::builders::GetItemFluentBuilder
             {
                 let consistent_read = self.consistent_read;
-                f(self.table.aymond.client.get_item().set_consistent_read(consistent_read))
-                    .table_name(&self.table.table_name)
-                    .set_key(Some(self.into()))
-                    .send()
-                    .await
+                let strategy = self.retry_strategy.clone()
+                    .unwrap_or_else(|| self.table.aymond.retry_strategy.clone());
+                let projection = ::aymond::projection::projection_expression::<#item_struct, P>();
+                let table = self.table;
+                let key = self.try_into()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
+                let mut req = f(table.aymond.client.get_item().set_consistent_read(consistent_read))
+                    .table_name(&table.table_name)
+                    .set_key(Some(key));
+                if let Some((expr, names)) = projection {
+                    req = req.projection_expression(expr)
+                        .set_expression_attribute_names(Some(names));
+                }
+                ::aymond::retry::retry(&strategy, || req.clone().send()).await
+            }
+        }
+
+        impl<'a, P: ::aymond::projection::Projection<#item_struct>> TryFrom<#get_item_struct<'a, P>> for #aws_sdk_dynamodb::types::TransactGetItem {
+            type Error = ::aymond::error::EncodeError;
+
+            fn try_from(get: #get_item_struct<'a, P>) -> Result<Self, Self::Error> {
+                let projection = ::aymond::projection::projection_expression::<#item_struct, P>();
+                let table = get.table;
+                let mut b = #aws_sdk_dynamodb::types::Get::builder()
+                    .table_name(&table.table_name)
+                    .set_key(Some(get.try_into()?));
+                if let Some((expr, names)) = projection {
+                    b = b.projection_expression(expr)
+                        .set_expression_attribute_names(Some(names));
+                }
+                Ok(#aws_sdk_dynamodb::types::TransactGetItem::builder()
+                    .get(b.build().unwrap())
+                    .build())
             }
         }
+
+        impl<'a, P: ::aymond::projection::Projection<#item_struct>> ::aymond::traits::TxGetRequest for #get_item_struct<'a, P> {
+            type Output = P;
+        }
     }
 }
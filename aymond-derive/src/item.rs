@@ -2,7 +2,7 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use syn::{DeriveInput, Expr, parse_quote};
 
-use crate::{ItemDefinition, marshal};
+use crate::{ItemDefinition, marshal, projection};
 
 pub fn create_item(input: &mut DeriveInput) -> syn::Result<(TokenStream, ItemDefinition)> {
     let aws_sdk_dynamodb: Expr = parse_quote!(::aymond::shim::aws_sdk_dynamodb);
@@ -24,12 +24,19 @@ pub fn create_item(input: &mut DeriveInput) -> syn::Result<(TokenStream, ItemDef
     };
 
     let from_into = marshal::from_into_item_structure(&def);
+    let partial = marshal::partial_item_structure(&def);
+    let partial_name = format_ident!("{}Partial", def.name);
+    let projected_attributes = projection::create_projected_attributes(&def);
     let item = quote! {
         #[derive(Debug, PartialEq)]
         #input
         #from_into
+        #partial
+        #projected_attributes
 
         impl Item for #name {
+            type Partial = #partial_name;
+
             fn key_schemas() -> Vec<#aws_sdk_dynamodb::types::KeySchemaElement> {
                 vec![
                     #(
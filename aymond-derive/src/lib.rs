@@ -1,13 +1,18 @@
+#![allow(clippy::unnecessary_unwrap)]
+
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{DeriveInput, meta::parser, parse_macro_input};
 
 use crate::{
     definition::{ItemAttribute, ItemDefinition},
+    enum_item::create_enum,
     item::create_item,
     nested_item::create_nested_item,
+    projection::create_projection,
     scan::create_scan_builder,
     table::create_table,
+    transparent::create_transparent,
 };
 
 mod batch_get_item;
@@ -17,14 +22,17 @@ mod condition_check;
 mod create_table;
 mod definition;
 mod delete_item;
+mod enum_item;
 mod get_item;
 mod item;
 mod marshal;
 mod nested_item;
+mod projection;
 mod put_item;
 mod query;
 mod scan;
 mod table;
+mod transparent;
 mod update_item;
 mod util;
 
@@ -35,6 +43,10 @@ pub fn aymond(args: TokenStream, input: TokenStream) -> TokenStream {
     let mut item: bool = false;
     let mut nested_item: bool = false;
     let mut table: bool = false;
+    let mut enum_item: bool = false;
+    let mut transparent: bool = false;
+    let mut tag: Option<String> = None;
+    let mut projection_of: Option<syn::Path> = None;
     let arg_parser = parser(|meta| {
         if meta.path.is_ident("item") {
             item = true;
@@ -45,16 +57,79 @@ pub fn aymond(args: TokenStream, input: TokenStream) -> TokenStream {
         } else if meta.path.is_ident("table") {
             table = true;
             Ok(())
+        } else if meta.path.is_ident("enum") {
+            enum_item = true;
+            Ok(())
+        } else if meta.path.is_ident("transparent") {
+            transparent = true;
+            Ok(())
+        } else if meta.path.is_ident("projection_of") {
+            projection_of = Some(meta.value()?.parse::<syn::Path>()?);
+            Ok(())
+        } else if meta.path.is_ident("tag") {
+            tag = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            Ok(())
         } else {
             Err(meta.error("Unsupported attribute"))
         }
     });
     parse_macro_input!(args with arg_parser);
 
+    if projection_of.is_some() && (item || nested_item || table || enum_item || transparent) {
+        return syn::Error::new_spanned(
+            &input.ident,
+            "#[aymond(projection_of = ...)] cannot be combined with other modes",
+        )
+        .to_compile_error()
+        .into();
+    }
+    if enum_item {
+        let chunk = if item || nested_item || table || transparent {
+            Err(syn::Error::new_spanned(
+                &input.ident,
+                "#[aymond(enum)] cannot be combined with item, nested_item, table or transparent",
+            ))
+        } else {
+            create_enum(&mut input, tag)
+        };
+        return match chunk {
+            Ok(chunk) => chunk.into(),
+            Err(err) => err.to_compile_error().into(),
+        };
+    }
+    if transparent {
+        let chunk = if item || nested_item || table {
+            Err(syn::Error::new_spanned(
+                &input.ident,
+                "#[aymond(transparent)] cannot be combined with item, nested_item or table",
+            ))
+        } else {
+            create_transparent(&mut input)
+        };
+        return match chunk {
+            Ok(chunk) => chunk.into(),
+            Err(err) => err.to_compile_error().into(),
+        };
+    }
+    if let Some(of) = projection_of {
+        return match create_projection(&mut input, &of) {
+            Ok(chunk) => chunk.into(),
+            Err(err) => err.to_compile_error().into(),
+        };
+    }
+    if tag.is_some() {
+        return syn::Error::new_spanned(
+            &input.ident,
+            "#[aymond(tag = ...)] requires #[aymond(enum)]",
+        )
+        .to_compile_error()
+        .into();
+    }
+
     let chunks: syn::Result<Vec<proc_macro2::TokenStream>> = match (item, nested_item, table) {
         (false, false, false) => Err(syn::Error::new_spanned(
             &input.ident,
-            "missing mode in #[aymond(...)]; expected one of: item or nested_item",
+            "missing mode in #[aymond(...)]; expected one of: item, nested_item, enum, transparent or projection_of",
         )),
         (true, true, _) => Err(syn::Error::new_spanned(
             &input.ident,
@@ -4,6 +4,69 @@ use syn::{Expr, Ident, parse_quote};
 
 use crate::{ItemAttribute, ItemDefinition};
 
+/// Returns an expression that reads the attribute from `map` and decodes it, propagating any
+/// `DecodeError` with `?`.
+pub fn field_decoder(i: &ItemAttribute) -> Expr {
+    let name = &i.ddb_name;
+    let decode = i.decode_attribute_value(&parse_quote!(v));
+    if !i.is_option {
+        let expected = i.expected_type_name();
+        parse_quote!({
+            let v = ::aymond::marshal::required(map, #name, #expected)?;
+            #decode.map_err(|e| e.at_attr(#name))?
+        })
+    } else {
+        optional_field_decoder(i)
+    }
+}
+
+/// Like [`field_decoder`], but yields `None` when the attribute is missing regardless of whether
+/// the field itself is optional.
+fn optional_field_decoder(i: &ItemAttribute) -> Expr {
+    let name = &i.ddb_name;
+    let decode = i.decode_attribute_value(&parse_quote!(v));
+    parse_quote! {
+        ::aymond::marshal::optional(map, #name)
+            .map(|v| #decode)
+            .transpose()
+            .map_err(|e| e.at_attr(#name))?
+    }
+}
+
+/// Generates `{Name}Partial`, a copy of the item with every field optional and nested items
+/// partial too, used to decode responses that only carry some of the item's attributes.
+pub fn partial_item_structure(item: &ItemDefinition) -> TokenStream {
+    let aws_sdk_dynamodb: Expr = parse_quote!(::aymond::shim::aws_sdk_dynamodb);
+    let name = format_ident!("{}Partial", item.name);
+
+    let fields: Vec<&Ident> = item.all_attributes().map(|i| &i.field).collect();
+    let types: Vec<TokenStream> = item
+        .all_attributes()
+        .map(|i| {
+            let ty = i.partial_type();
+            quote! { Option<#ty> }
+        })
+        .collect();
+    let unboxers: Vec<Expr> = item.all_attributes().map(optional_field_decoder).collect();
+
+    quote! {
+        #[derive(Debug, Default, PartialEq)]
+        pub struct #name {
+            #( pub #fields: #types ),*
+        }
+
+        impl TryFrom<&::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>> for #name {
+            type Error = ::aymond::error::DecodeError;
+
+            fn try_from(map: &::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>) -> Result<Self, Self::Error> {
+                Ok(#name {
+                    #( #fields: #unboxers ),*
+                })
+            }
+        }
+    }
+}
+
 pub fn from_into_item_structure(item: &ItemDefinition) -> TokenStream {
     let aws_sdk_dynamodb: Expr = parse_quote!(::aymond::shim::aws_sdk_dynamodb);
     let name = format_ident!("{}", item.name);
@@ -14,39 +77,35 @@ pub fn from_into_item_structure(item: &ItemDefinition) -> TokenStream {
 
     let mut append = |i: &ItemAttribute| {
         let field = i.field.clone();
-        let insert_map = i.insert_into_map(&parse_quote!(self.#field), &parse_quote!(map));
+        let insert_map = i.insert_into_map(&parse_quote!(item.#field), &parse_quote!(map));
         fields.push(field);
         insert_maps.push(insert_map);
 
-        let name = i.ddb_name.clone();
-        let unboxer = if !i.is_option {
-            i.unwrap_attribute_value(&parse_quote!(map.get(#name).unwrap()))
-        } else {
-            let exp = i.unwrap_attribute_value(&parse_quote!(e));
-            parse_quote!(map.get(#name).and_then(|e| #exp))
-        };
-
-        unboxers.push(unboxer);
+        unboxers.push(field_decoder(i));
     };
 
     item.all_attributes().for_each(&mut append);
 
     quote! {
-        impl From<&::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>> for #name {
-            fn from(map: &::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>) -> Self {
-                #name {
+        impl TryFrom<&::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>> for #name {
+            type Error = ::aymond::error::DecodeError;
+
+            fn try_from(map: &::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>) -> Result<Self, Self::Error> {
+                Ok(#name {
                     #( #fields: #unboxers ),*
-                }
+                })
             }
         }
 
-        impl Into<::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>> for #name {
-            fn into(self) -> ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue> {
+        impl TryFrom<#name> for ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue> {
+            type Error = ::aymond::error::EncodeError;
+
+            fn try_from(item: #name) -> Result<Self, Self::Error> {
                 let mut map = ::std::collections::HashMap::new();
                 #(
                     #insert_maps
                 )*
-                map
+                Ok(map)
             }
         }
     }
@@ -1,37 +1,100 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::DeriveInput;
+use syn::{DeriveInput, Ident, Visibility};
 
-use crate::{ItemDefinition, marshal};
+use crate::{ItemAttribute, ItemDefinition, marshal};
 
 pub fn create_nested_item(input: &mut DeriveInput) -> syn::Result<TokenStream> {
     let def = ItemDefinition::new(input, true)?;
+    let name = format_ident!("{}", def.name);
     let from_into = marshal::from_into_item_structure(&def);
+    let partial = marshal::partial_item_structure(&def);
+    let partial_name = format_ident!("{}Partial", def.name);
     let condition_path = create_condition_path(&def);
-    let update_path = create_update_path(&def);
+    let update_path = create_update_path(&def, &input.vis);
     Ok(quote! {
         #[derive(Debug, PartialEq)]
         #input
         #from_into
+        #partial
         #condition_path
         #update_path
+
+        impl ::aymond::marshal::IntoAttributeValue for #name {
+            fn into_attribute_value(
+                self,
+            ) -> Result<::aymond::shim::aws_sdk_dynamodb::types::AttributeValue, ::aymond::error::EncodeError> {
+                self.try_into().map(::aymond::shim::aws_sdk_dynamodb::types::AttributeValue::M)
+            }
+        }
+
+        impl ::aymond::marshal::FromAttributeValue for #name {
+            const TYPE_NAME: &'static str = "M";
+
+            fn from_attribute_value(
+                v: &::aymond::shim::aws_sdk_dynamodb::types::AttributeValue,
+            ) -> Result<Self, ::aymond::error::DecodeError> {
+                ::aymond::marshal::decode_m(v)
+            }
+        }
+
+        impl ::aymond::marshal::FromAttributeValue for #partial_name {
+            const TYPE_NAME: &'static str = "M";
+
+            fn from_attribute_value(
+                v: &::aymond::shim::aws_sdk_dynamodb::types::AttributeValue,
+            ) -> Result<Self, ::aymond::error::DecodeError> {
+                ::aymond::marshal::decode_m(v)
+            }
+        }
+
+        impl ::aymond::marshal::PartialValue for #name {
+            type Partial = #partial_name;
+        }
     })
 }
 
 pub fn create_condition_path(def: &ItemDefinition) -> TokenStream {
+    let name = format_ident!("{}", def.name);
     let path_struct = format_ident!("{}ConditionPath", &def.name);
+    let path = create_condition_path_struct(&path_struct, def.all_attributes());
+    let attribute_functions = condition_attribute_functions();
+    quote! {
+        #path
+
+        impl #path_struct {
+            #attribute_functions
+        }
+
+        impl ::aymond::condition::HasConditionPath for #name {
+            type Path = #path_struct;
+        }
+    }
+}
 
-    let accessors: Vec<TokenStream> = def
-        .all_attributes()
+/// The condition functions that apply to a whole attribute of any type, for path structs
+/// holding a `path_prefix`.
+pub fn condition_attribute_functions() -> TokenStream {
+    quote!(::aymond::impl_attribute_functions!(path_prefix);)
+}
+
+/// Generates a condition path struct exposing an accessor per attribute, rooted at the
+/// struct's path prefix.
+pub fn create_condition_path_struct<'a>(
+    path_struct: &Ident,
+    attributes: impl Iterator<Item = &'a ItemAttribute>,
+) -> TokenStream {
+    let accessors: Vec<TokenStream> = attributes
         .map(|attr| {
             let fn_name = &attr.field;
             let ddb_name = &attr.ddb_name;
             let return_type = attr.condition_path_type();
+            let new_path = attr.new_condition_path(&quote!(path));
             quote! {
                 pub fn #fn_name(&self) -> #return_type {
                     let mut path = self.path_prefix.clone();
                     path.push(::aymond::condition::PathSegment::Attr(#ddb_name.to_string()));
-                    ::aymond::condition::ConditionPathRoot::with_prefix(path)
+                    #new_path
                 }
             }
         })
@@ -54,37 +117,95 @@ pub fn create_condition_path(def: &ItemDefinition) -> TokenStream {
     }
 }
 
-pub fn create_update_path(def: &ItemDefinition) -> TokenStream {
+/// Generates the update path for a nested item, which can also set or remove the whole item.
+///
+/// The path struct shares the item's visibility so that it can name the item as its
+/// `UpdateValuePath::Value`, which is what lets lists of the item be appended to.
+pub fn create_update_path(def: &ItemDefinition, vis: &Visibility) -> TokenStream {
+    let name = format_ident!("{}", def.name);
     let path_struct = format_ident!("{}UpdatePath", &def.name);
+    let attr_val: TokenStream = quote!(::aymond::shim::aws_sdk_dynamodb::types::AttributeValue);
+    let upd: TokenStream = quote!(::aymond::update);
+    let path = create_update_path_struct(vis, &path_struct, def.all_attributes());
+    quote! {
+        #path
 
-    let accessors: Vec<TokenStream> = def
-        .all_attributes()
+        impl<O> #path_struct<O> {
+            pub fn remove(self) -> #upd::UpdateExpr {
+                #upd::UpdateExpr::remove(self.path_prefix)
+            }
+        }
+
+        impl #path_struct {
+            pub fn set(self, v: #name) -> #upd::UpdateExpr {
+                match v.try_into() {
+                    Ok(v) => #upd::UpdateExpr::set(self.path_prefix, #attr_val::M(v)),
+                    Err(err) => #upd::UpdateExpr::invalid(err),
+                }
+            }
+        }
+
+        impl #path_struct<#upd::Optional> {
+            /// Sets the attribute, or removes it if `v` is `None`.
+            pub fn set<M>(self, v: impl #upd::IntoOptionalValue<#name, M>) -> #upd::UpdateExpr {
+                let value = v.into_optional_value().map(|v| v.try_into().map(#attr_val::M));
+                #upd::UpdateExpr::set_or_remove_encoded(self.path_prefix, value)
+            }
+        }
+
+        impl #upd::HasUpdatePath for #name {
+            type Path<O> = #path_struct<O>;
+        }
+
+        impl #upd::UpdateValuePath for #path_struct {
+            type Value = #name;
+
+            fn to_attribute_value(v: #name) -> Result<#attr_val, ::aymond::error::EncodeError> {
+                v.try_into().map(#attr_val::M)
+            }
+        }
+    }
+}
+
+/// Generates an update path struct exposing an accessor per attribute, rooted at the struct's
+/// path prefix. `O` marks whether the path leads to an `Option` field.
+pub fn create_update_path_struct<'a>(
+    vis: &Visibility,
+    path_struct: &Ident,
+    attributes: impl Iterator<Item = &'a ItemAttribute>,
+) -> TokenStream {
+    let accessors: Vec<TokenStream> = attributes
         .map(|attr| {
             let fn_name = &attr.field;
             let ddb_name = &attr.ddb_name;
             let return_type = attr.update_path_type();
+            let new_path = attr.new_update_path(&quote!(path));
             quote! {
                 pub fn #fn_name(&self) -> #return_type {
                     let mut path = self.path_prefix.clone();
                     path.push(::aymond::update::PathSegment::Attr(#ddb_name.to_string()));
-                    ::aymond::update::UpdatePathRoot::with_prefix(path)
+                    #new_path
                 }
             }
         })
         .collect();
 
     quote! {
-        pub struct #path_struct {
+        #vis struct #path_struct<O = ::aymond::update::Required> {
             path_prefix: Vec<::aymond::update::PathSegment>,
+            _phantom: ::std::marker::PhantomData<O>,
         }
 
-        impl ::aymond::update::UpdatePathRoot for #path_struct {
+        impl<O> ::aymond::update::UpdatePathRoot for #path_struct<O> {
             fn with_prefix(path: Vec<::aymond::update::PathSegment>) -> Self {
-                Self { path_prefix: path }
+                Self {
+                    path_prefix: path,
+                    _phantom: ::std::marker::PhantomData,
+                }
             }
         }
 
-        impl #path_struct {
+        impl<O> #path_struct<O> {
             #( #accessors )*
         }
     }
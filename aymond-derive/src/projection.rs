@@ -0,0 +1,115 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{Data, DeriveInput, Fields, Ident, Path, spanned::Spanned};
+
+use crate::{ItemDefinition, marshal};
+
+fn projected_attribute_fn(field: &Ident) -> Ident {
+    format_ident!("__aymond_project_{}", field)
+}
+
+/// Generates a hidden accessor per attribute of an item describing how projections read it.
+pub fn create_projected_attributes(def: &ItemDefinition) -> TokenStream {
+    let name = format_ident!("{}", def.name);
+    let accessors: Vec<TokenStream> = def
+        .all_attributes()
+        .map(|attr| {
+            let fn_name = projected_attribute_fn(&attr.field);
+            let ddb_name = &attr.ddb_name;
+            let ty = &attr.ty;
+            let decoder = marshal::field_decoder(attr);
+            quote! {
+                #[doc(hidden)]
+                pub fn #fn_name() -> ::aymond::projection::ProjectedAttribute<#ty> {
+                    ::aymond::projection::ProjectedAttribute {
+                        name: #ddb_name,
+                        decode: |map| {
+                            let v = #decoder;
+                            Ok(v)
+                        },
+                    }
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        impl #name {
+            #( #accessors )*
+        }
+    }
+}
+
+/// Generates a struct holding a subset of another item's fields, fetched with a projection
+/// expression.
+pub fn create_projection(input: &mut DeriveInput, of: &Path) -> syn::Result<TokenStream> {
+    let name = input.ident.clone();
+    let fields = match &input.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Named(fields) => fields.named.iter().collect::<Vec<_>>(),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &name,
+                    "#[aymond(projection_of = ...)] requires a struct with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &name,
+                "#[aymond(projection_of = ...)] supports only structs",
+            ));
+        }
+    };
+
+    if let Some(attr) = fields
+        .iter()
+        .flat_map(|f| f.attrs.iter())
+        .find(|a| a.path().is_ident("aymond"))
+    {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "projection fields take their attributes from the projected item",
+        ));
+    }
+
+    let mut field_names: Vec<&Ident> = vec![];
+    let mut decoders: Vec<TokenStream> = vec![];
+    let mut attribute_names: Vec<TokenStream> = vec![];
+    for field in &fields {
+        let field_name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let accessor = projected_attribute_fn(field_name);
+        field_names.push(field_name);
+        // Spanned on the field so a missing attribute or a type mismatch points at it.
+        decoders.push(quote_spanned! {ty.span()=>
+            {
+                let attr: ::aymond::projection::ProjectedAttribute<#ty> = #of::#accessor();
+                (attr.decode)(map)?
+            }
+        });
+        attribute_names.push(quote! { #of::#accessor().name });
+    }
+
+    let attr_val: TokenStream = quote!(::aymond::shim::aws_sdk_dynamodb::types::AttributeValue);
+    Ok(quote! {
+        #[derive(Debug, PartialEq)]
+        #input
+
+        impl TryFrom<&::std::collections::HashMap<String, #attr_val>> for #name {
+            type Error = ::aymond::error::DecodeError;
+
+            fn try_from(map: &::std::collections::HashMap<String, #attr_val>) -> Result<Self, Self::Error> {
+                Ok(#name {
+                    #( #field_names: #decoders ),*
+                })
+            }
+        }
+
+        impl ::aymond::projection::Projection<#of> for #name {
+            fn attribute_names() -> Option<Vec<&'static str>> {
+                Some(vec![ #( #attribute_names ),* ])
+            }
+        }
+    })
+}
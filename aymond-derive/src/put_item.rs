@@ -34,11 +34,17 @@ pub fn create_put_item_builder(item: &ItemDefinition) -> TokenStream {
             table: &'a #table_struct,
             i: Option<#item_struct>,
             cond: #condition_builder_struct,
+            retry_strategy: Option<::aymond::retry::RetryStrategy>,
         }
 
         impl<'a> #put_item_struct<'a> {
             fn new(table: &'a #table_struct) -> #put_item_struct<'a> {
-                #put_item_struct { table, i: None, cond: #condition_builder_struct::new() }
+                #put_item_struct {
+                    table,
+                    i: None,
+                    cond: #condition_builder_struct::new(),
+                    retry_strategy: None,
+                }
             }
         }
 
@@ -61,6 +67,11 @@ pub fn create_put_item_builder(item: &ItemDefinition) -> TokenStream {
                 self
             }
 
+            pub fn retry_strategy(mut self, s: ::aymond::retry::RetryStrategy) -> #put_item_struct<'a> {
+                self.retry_strategy = Some(s);
+                self
+            }
+
             pub async fn raw<F>(
                 self,
                 f: F,
@@ -75,20 +86,23 @@ pub fn create_put_item_builder(item: &ItemDefinition) -> TokenStream {
                 F: FnOnce(#aws_sdk_dynamodb::operation::put_item::builders::PutItemFluentBuilder)
                 -> #aws_sdk_dynamodb::operation::put_item::builders::PutItemFluentBuilder
             {
+                let strategy = self.retry_strategy
+                    .unwrap_or_else(|| self.table.aymond.retry_strategy.clone());
                 let mut item = self.i.expect("item not set");
                 #increment_version
-                let (cond_expr, expr_name, expr_value) = self.cond.build();
+                let (cond_expr, expr_name, expr_value) = self.cond.build()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
+                let item = item.try_into()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
                 let mut req = f(self.table.aymond.client.put_item())
                     .table_name(&self.table.table_name)
-                    .set_item(Some(item.into()));
+                    .set_item(Some(item));
                 if cond_expr.is_some() {
                     req = req.set_condition_expression(cond_expr)
                         .set_expression_attribute_names(expr_name)
                         .set_expression_attribute_values(expr_value);
                 }
-                req
-                    .send()
-                    .await
+                ::aymond::retry::retry_write(&strategy, || req.clone().send()).await
             }
 
             pub async fn send(self) -> Result<
@@ -101,22 +115,48 @@ pub fn create_put_item_builder(item: &ItemDefinition) -> TokenStream {
                 self.raw(|r| r).await?;
                 Ok(())
             }
+
+            pub async fn send_returning_old(self) -> Result<
+                Option<#item_struct>,
+                ::aymond::error::RequestError<
+                    #aws_sdk_dynamodb::operation::put_item::PutItemError,
+                >,
+            > {
+                let res = self
+                    .raw(|r| r.return_values(#aws_sdk_dynamodb::types::ReturnValue::AllOld))
+                    .await?;
+                Ok(res.attributes().map(#item_struct::try_from).transpose()?)
+            }
         }
 
-        impl<'a> Into<#aws_sdk_dynamodb::types::Put> for #put_item_struct<'a> {
-            fn into(self) -> #aws_sdk_dynamodb::types::Put {
+        impl<'a> TryFrom<#put_item_struct<'a>> for #aws_sdk_dynamodb::types::Put {
+            type Error = ::aymond::error::EncodeError;
+
+            fn try_from(put: #put_item_struct<'a>) -> Result<Self, Self::Error> {
+                put.into_put()
+            }
+        }
+
+        impl<'a> #put_item_struct<'a> {
+            fn into_put(self) -> Result<#aws_sdk_dynamodb::types::Put, ::aymond::error::EncodeError> {
                 let mut item = self.i.expect("item not set");
                 #increment_version
-                let (cond_expr, expr_name, expr_value) = self.cond.build();
+                let (cond_expr, expr_name, expr_value) = self.cond.build()?;
                 let mut b = #aws_sdk_dynamodb::types::Put::builder()
                     .table_name(&self.table.table_name)
-                    .set_item(Some(item.into()));
+                    .set_item(Some(item.try_into()?));
                 if cond_expr.is_some() {
                     b = b.set_condition_expression(cond_expr)
                         .set_expression_attribute_names(expr_name)
                         .set_expression_attribute_values(expr_value);
                 }
-                b.build().unwrap()
+                Ok(b.build().unwrap())
+            }
+        }
+
+        impl<'a> ::aymond::traits::TxPut for #put_item_struct<'a> {
+            fn key_schemas() -> Vec<#aws_sdk_dynamodb::types::KeySchemaElement> {
+                <#item_struct as ::aymond::traits::Item>::key_schemas()
             }
         }
     }
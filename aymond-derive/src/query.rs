@@ -9,7 +9,28 @@ use syn::{Expr, parse_quote};
 pub fn create_main_query_builder(item: &ItemDefinition) -> TokenStream {
     let hash_key = item.hash_key.as_ref().unwrap();
     let sort_key = item.sort_key.as_ref();
-    create_query_builder(&item.name, &item.name, hash_key, sort_key, None)
+    let key_schema = dedup_key_schema([Some(hash_key), sort_key]);
+    create_query_builder(
+        &item.name,
+        &item.name,
+        hash_key,
+        sort_key,
+        None,
+        &key_schema,
+    )
+}
+
+/// The attributes of an index's `LastEvaluatedKey`: its own keys followed by the table's.
+fn dedup_key_schema<'a>(
+    attrs: impl IntoIterator<Item = Option<&'a ItemAttribute>>,
+) -> Vec<&'a ItemAttribute> {
+    let mut key_schema: Vec<&ItemAttribute> = vec![];
+    for attr in attrs.into_iter().flatten() {
+        if !key_schema.iter().any(|a| a.ddb_name == attr.ddb_name) {
+            key_schema.push(attr);
+        }
+    }
+    key_schema
 }
 
 pub fn create_index_query_builders(item: &ItemDefinition) -> TokenStream {
@@ -28,12 +49,19 @@ pub fn create_index_query_builders(item: &ItemDefinition) -> TokenStream {
         let prefix = format!("{}Index{}", item.name, pascal);
         let index_hk_struct = format_ident!("{}QueryHashKey", prefix);
 
+        let key_schema = dedup_key_schema([
+            Some(gsi_hash_key),
+            gsi.sort_key.as_ref(),
+            item.hash_key.as_ref(),
+            item.sort_key.as_ref(),
+        ]);
         let builder = create_query_builder(
             &item.name,
             &prefix,
             gsi_hash_key,
             gsi.sort_key.as_ref(),
             Some(&gsi.name),
+            &key_schema,
         );
         chunks.push(builder);
 
@@ -60,12 +88,18 @@ pub fn create_index_query_builders(item: &ItemDefinition) -> TokenStream {
         let index_hk_struct = format_ident!("{}QueryHashKey", prefix);
         let index_query_struct = format_ident!("{}Query", prefix);
 
+        let key_schema = dedup_key_schema([
+            Some(table_hash_key),
+            Some(&lsi.sort_key),
+            item.sort_key.as_ref(),
+        ]);
         let builder = create_query_builder(
             &item.name,
             &prefix,
             table_hash_key,
             Some(&lsi.sort_key),
             Some(&lsi.name),
+            &key_schema,
         );
         chunks.push(builder);
 
@@ -88,6 +122,7 @@ pub fn create_query_builder(
     hash_key: &ItemAttribute,
     sort_key: Option<&ItemAttribute>,
     index_name: Option<&str>,
+    key_schema: &[&ItemAttribute],
 ) -> TokenStream {
     let aws_sdk_dynamodb: Expr = parse_quote!(::aymond::shim::aws_sdk_dynamodb);
 
@@ -95,11 +130,15 @@ pub fn create_query_builder(
     let table_struct = format_ident!("{}Table", item_name);
     let query_struct = format_ident!("{}Query", prefix);
     let hash_key_struct = format_ident!("{}QueryHashKey", prefix);
+    let condition_builder_struct = format_ident!("{}Condition", item_name);
 
     let hash_key_attr_name = &hash_key.ddb_name;
     let hash_key_ident = &hash_key.field;
     let hash_key_typ = &hash_key.ty;
-    let hash_key_boxer = hash_key.to_attribute_value(&parse_quote!(self.hk.unwrap()));
+    let hash_key_boxer = hash_key.to_attribute_value(&parse_quote!(query.hk.unwrap()));
+
+    let key_schema_names: Vec<&String> = key_schema.iter().map(|a| &a.ddb_name).collect();
+    let key_schema_types: Vec<Expr> = key_schema.iter().map(|a| a.scalar_type()).collect();
 
     let index_name_init = match index_name {
         Some(name) => quote! { Some(#name.to_string()) },
@@ -108,21 +147,27 @@ pub fn create_query_builder(
 
     let mut chunks: Vec<TokenStream> = vec![];
 
+    let fields = if sort_key.is_some() {
+        quote! { table, index_name, hk, qs, b, c, scan_index_forward, page_size, max_items, consistent_read, filter, retry_strategy }
+    } else {
+        quote! { table, index_name, hk, qs, scan_index_forward, page_size, max_items, consistent_read, filter, retry_strategy }
+    };
+
     if let Some(sort_key_attr) = sort_key {
         let sort_key_struct = format_ident!("{}QuerySortKey", prefix);
         let sort_key_ident = &sort_key_attr.field;
         let sort_key_attr_name = &sort_key_attr.ddb_name;
         let sort_key_typ = &sort_key_attr.ty;
 
-        let sort_key_b_boxer = sort_key_attr.to_attribute_value(&parse_quote!(self.b.unwrap()));
-        let sort_key_c_boxer = sort_key_attr.to_attribute_value(&parse_quote!(self.c.unwrap()));
+        let sort_key_b_boxer = sort_key_attr.to_attribute_value(&parse_quote!(query.b.unwrap()));
+        let sort_key_c_boxer = sort_key_attr.to_attribute_value(&parse_quote!(query.c.unwrap()));
 
         chunks.push(quote! {
             pub struct #hash_key_struct<'a> {
                 q: #query_struct<'a>,
             }
 
-            pub struct #query_struct<'a> {
+            pub struct #query_struct<'a, P = #item_struct> {
                 table: &'a #table_struct,
                 index_name: Option<String>,
                 hk: Option<#hash_key_typ>,
@@ -130,8 +175,12 @@ pub fn create_query_builder(
                 b: Option<#sort_key_typ>,
                 c: Option<#sort_key_typ>,
                 scan_index_forward: Option<bool>,
-                limit: Option<i32>,
+                page_size: Option<i32>,
+                max_items: Option<usize>,
                 consistent_read: Option<bool>,
+                filter: Option<::aymond::condition::CondExpr>,
+                retry_strategy: Option<::aymond::retry::RetryStrategy>,
+                projection: ::std::marker::PhantomData<P>,
             }
 
             impl<'a> #query_struct<'a> {
@@ -144,8 +193,12 @@ pub fn create_query_builder(
                         b: None,
                         c: None,
                         scan_index_forward: None,
-                        limit: None,
+                        page_size: None,
+                        max_items: None,
                         consistent_read: None,
+                        filter: None,
+                        retry_strategy: None,
+                        projection: ::std::marker::PhantomData,
                     };
                     #hash_key_struct { q }
                 }
@@ -162,32 +215,39 @@ pub fn create_query_builder(
                 q: #query_struct<'a>,
             }
 
-            impl<'a> Into<(
+            impl<'a, P> TryFrom<#query_struct<'a, P>> for (
                 String,
                 ::std::collections::HashMap<String, String>,
                 ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>,
-            )> for #query_struct<'a> {
-                fn into(self) -> (
-                    String,
-                    ::std::collections::HashMap<String, String>,
-                    ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>,
-                ) {
-                    let exp = self.qs.unwrap();
+            ) {
+                type Error = ::aymond::error::EncodeError;
+
+                fn try_from(query: #query_struct<'a, P>) -> Result<Self, Self::Error> {
+                    let exp = query.qs.unwrap();
 
                     let mut key_names = ::std::collections::HashMap::new();
                     key_names.insert("#hk".to_string(), #hash_key_attr_name.to_string());
                     key_names.insert("#sk".to_string(), #sort_key_attr_name.to_string());
 
                     let mut key_values = ::std::collections::HashMap::new();
-                    key_values.insert(":hk".to_string(), #hash_key_boxer);
-                    if self.b.is_some() {
-                        key_values.insert(":b".to_string(), #sort_key_b_boxer);
+                    key_values.insert(
+                        ":hk".to_string(),
+                        #hash_key_boxer.map_err(|e| e.at_attr(#hash_key_attr_name))?,
+                    );
+                    if query.b.is_some() {
+                        key_values.insert(
+                            ":b".to_string(),
+                            #sort_key_b_boxer.map_err(|e| e.at_attr(#sort_key_attr_name))?,
+                        );
                     }
-                    if self.c.is_some() {
-                        key_values.insert(":c".to_string(), #sort_key_c_boxer);
+                    if query.c.is_some() {
+                        key_values.insert(
+                            ":c".to_string(),
+                            #sort_key_c_boxer.map_err(|e| e.at_attr(#sort_key_attr_name))?,
+                        );
                     }
 
-                    (exp, key_names, key_values)
+                    Ok((exp, key_names, key_values))
                 }
             }
         });
@@ -236,14 +296,18 @@ pub fn create_query_builder(
                 q: #query_struct<'a>,
             }
 
-            pub struct #query_struct<'a> {
+            pub struct #query_struct<'a, P = #item_struct> {
                 table: &'a #table_struct,
                 index_name: Option<String>,
                 hk: Option<#hash_key_typ>,
                 qs: Option<String>,
                 scan_index_forward: Option<bool>,
-                limit: Option<i32>,
+                page_size: Option<i32>,
+                max_items: Option<usize>,
                 consistent_read: Option<bool>,
+                filter: Option<::aymond::condition::CondExpr>,
+                retry_strategy: Option<::aymond::retry::RetryStrategy>,
+                projection: ::std::marker::PhantomData<P>,
             }
 
             impl<'a> #query_struct<'a> {
@@ -254,8 +318,12 @@ pub fn create_query_builder(
                         hk: None,
                         qs: None,
                         scan_index_forward: None,
-                        limit: None,
+                        page_size: None,
+                        max_items: None,
                         consistent_read: None,
+                        filter: None,
+                        retry_strategy: None,
+                        projection: ::std::marker::PhantomData,
                     };
                     #hash_key_struct { q }
                 }
@@ -269,25 +337,26 @@ pub fn create_query_builder(
                 }
             }
 
-            impl<'a> Into<(
+            impl<'a, P> TryFrom<#query_struct<'a, P>> for (
                 String,
                 ::std::collections::HashMap<String, String>,
                 ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>,
-            )> for #query_struct<'a> {
-                fn into(self) -> (
-                    String,
-                    ::std::collections::HashMap<String, String>,
-                    ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>,
-                ) {
-                    let exp = self.qs.unwrap();
+            ) {
+                type Error = ::aymond::error::EncodeError;
+
+                fn try_from(query: #query_struct<'a, P>) -> Result<Self, Self::Error> {
+                    let exp = query.qs.unwrap();
 
                     let mut key_names = ::std::collections::HashMap::new();
                     key_names.insert("#hk".to_string(), #hash_key_attr_name.to_string());
 
                     let mut key_values = ::std::collections::HashMap::new();
-                    key_values.insert(":hk".to_string(), #hash_key_boxer);
+                    key_values.insert(
+                        ":hk".to_string(),
+                        #hash_key_boxer.map_err(|e| e.at_attr(#hash_key_attr_name))?,
+                    );
 
-                    (exp, key_names, key_values)
+                    Ok((exp, key_names, key_values))
                 }
             }
         });
@@ -296,14 +365,24 @@ pub fn create_query_builder(
     quote! {
         #( #chunks )*
 
-        impl<'a> #query_struct<'a> {
+        impl<'a, P: ::aymond::projection::Projection<#item_struct> + 'a> #query_struct<'a, P> {
             pub fn scan_index_forward(mut self, v: bool) -> Self {
                 self.scan_index_forward = Some(v);
                 self
             }
 
-            pub fn limit(mut self, v: i32) -> Self {
-                self.limit = Some(v);
+            #[deprecated(note = "limit() sets the page size; use page_size() or max_items()")]
+            pub fn limit(self, v: i32) -> Self {
+                self.page_size(v)
+            }
+
+            pub fn page_size(mut self, v: i32) -> Self {
+                self.page_size = Some(v);
+                self
+            }
+
+            pub fn max_items(mut self, v: usize) -> Self {
+                self.max_items = Some(v);
                 self
             }
 
@@ -312,33 +391,207 @@ pub fn create_query_builder(
                 self
             }
 
-            pub async fn send(self) -> impl ::aymond::shim::futures::Stream<Item = Result<#item_struct, #aws_sdk_dynamodb::error::SdkError<
+            pub fn retry_strategy(mut self, s: ::aymond::retry::RetryStrategy) -> Self {
+                self.retry_strategy = Some(s);
+                self
+            }
+
+            fn strategy(&self) -> ::aymond::retry::RetryStrategy {
+                self.retry_strategy.clone()
+                    .unwrap_or_else(|| self.table.aymond.retry_strategy.clone())
+            }
+
+            pub fn filter<F, R>(mut self, f: F) -> Self
+            where
+                F: FnOnce(&#condition_builder_struct) -> R,
+                R: ::aymond::condition::IntoOptionalCondExpr,
+            {
+                self.filter = f(&#condition_builder_struct::new()).into_optional_cond_expr();
+                self
+            }
+
+            /// Builds the key condition and filter expressions, along with the attribute names and
+            /// values they refer to.
+            fn build_expressions(
+                mut self,
+            ) -> Result<
+                (
+                    String,
+                    Option<String>,
+                    ::std::collections::HashMap<String, String>,
+                    ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>,
+                ),
+                ::aymond::error::EncodeError,
+            > {
+                let filter = self.filter.take();
+                let (key_expr, mut attr_names, mut attr_values) = self.try_into()?;
+                let filter_expr = match filter {
+                    Some(filter) => {
+                        let (expr, names, values) = filter.build()?;
+                        attr_names.extend(names);
+                        attr_values.extend(values);
+                        Some(expr)
+                    }
+                    None => None,
+                };
+                Ok((key_expr, filter_expr, attr_names, attr_values))
+            }
+
+            pub fn project<Q: ::aymond::projection::Projection<#item_struct>>(self) -> #query_struct<'a, Q> {
+                let #query_struct { #fields, .. } = self;
+                #query_struct {
+                    #fields,
+                    projection: ::std::marker::PhantomData,
+                }
+            }
+
+            pub async fn send(self) -> impl ::aymond::shim::futures::Stream<Item = Result<P, ::aymond::error::RequestError<
                 #aws_sdk_dynamodb::operation::query::QueryError,
-                #aws_sdk_dynamodb::config::http::HttpResponse
             >>> + 'a
             {
                 let index_name = self.index_name.clone();
                 let scan_index_forward = self.scan_index_forward;
-                let limit = self.limit;
+                let max_items = self.max_items;
+                // Without an explicit page size or a filter, don't read more than we'll return. A
+                // filter can discard items DynamoDB already counted against the limit, which would
+                // cost a request per page of filtered-out items.
+                let page_size = match self.filter {
+                    None => self
+                        .page_size
+                        .or(max_items.map(|n| i32::try_from(n).unwrap_or(i32::MAX))),
+                    Some(_) => self.page_size,
+                };
                 let consistent_read = self.consistent_read;
+                let strategy = self.strategy();
                 let query = self.table.aymond.client.query();
                 let table_name = &self.table.table_name;
-                let (key_expr, attr_names, attr_values) = self.into();
-                let pagination = query
+                // An expression that fails to encode fails the first page instead
+                let req = self.build_expressions().map(|(key_expr, filter_expr, mut attr_names, attr_values)| {
+                    let projection_expr = ::aymond::projection::projection_expression::<#item_struct, P>()
+                        .map(|(expr, names)| {
+                            attr_names.extend(names);
+                            expr
+                        });
+                    query
+                        .table_name(table_name)
+                        .set_index_name(index_name.clone())
+                        .set_scan_index_forward(scan_index_forward)
+                        .set_limit(page_size)
+                        .set_consistent_read(consistent_read)
+                        .set_key_condition_expression(Some(key_expr))
+                        .set_filter_expression(filter_expr)
+                        .set_projection_expression(projection_expr)
+                        .set_expression_attribute_names(Some(attr_names))
+                        .set_expression_attribute_values(Some(attr_values))
+                });
+                let pagination = ::aymond::retry::paginate_items(
+                    strategy,
+                    move |start_key| {
+                        let req = req.clone();
+                        async move {
+                            match req {
+                                Ok(req) => req.set_exclusive_start_key(start_key).send().await,
+                                Err(err) => Err(#aws_sdk_dynamodb::error::SdkError::construction_failure(err)),
+                            }
+                        }
+                    },
+                    |page: &#aws_sdk_dynamodb::operation::query::QueryOutput| page.last_evaluated_key.clone(),
+                    |page: #aws_sdk_dynamodb::operation::query::QueryOutput| page.items.unwrap_or_default(),
+                );
+                let items = ::aymond::shim::futures::StreamExt::map(pagination, |item| {
+                    item.map_err(::aymond::error::RequestError::from)
+                        .and_then(|i| Ok(P::try_from(&i)?))
+                });
+                ::aymond::shim::futures::StreamExt::take(items, max_items.unwrap_or(usize::MAX))
+            }
+
+            pub async fn first(self) -> Result<
+                Option<P>,
+                ::aymond::error::RequestError<#aws_sdk_dynamodb::operation::query::QueryError>,
+            > {
+                let items = self.max_items(1).send().await;
+                let mut items = ::std::pin::pin!(items);
+                ::aymond::shim::futures::StreamExt::next(&mut items).await.transpose()
+            }
+
+            pub async fn one(self) -> Result<
+                Option<P>,
+                ::aymond::error::RequestError<#aws_sdk_dynamodb::operation::query::QueryError>,
+            > {
+                let items = self.max_items(2).send().await;
+                let items: Vec<P> = ::aymond::shim::futures::TryStreamExt::try_collect(items).await?;
+                if items.len() > 1 {
+                    return Err(::aymond::error::RequestError::NotUnique);
+                }
+                Ok(items.into_iter().next())
+            }
+
+            pub async fn count(self) -> Result<
+                ::aymond::count::Count,
+                #aws_sdk_dynamodb::error::SdkError<
+                    #aws_sdk_dynamodb::operation::query::QueryError,
+                    #aws_sdk_dynamodb::config::http::HttpResponse,
+                >,
+            > {
+                let index_name = self.index_name.clone();
+                let consistent_read = self.consistent_read;
+                let strategy = self.strategy();
+                let query = self.table.aymond.client.query();
+                let table_name = &self.table.table_name;
+                let (key_expr, filter_expr, attr_names, attr_values) = self
+                    .build_expressions()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
+                let req = query
                     .table_name(table_name)
-                    .set_index_name(index_name.clone())
-                    .set_scan_index_forward(scan_index_forward)
-                    .set_limit(limit)
+                    .set_index_name(index_name)
                     .set_consistent_read(consistent_read)
+                    .select(#aws_sdk_dynamodb::types::Select::Count)
                     .set_key_condition_expression(Some(key_expr))
+                    .set_filter_expression(filter_expr)
                     .set_expression_attribute_names(Some(attr_names))
-                    .set_expression_attribute_values(Some(attr_values))
-                    .into_paginator()
+                    .set_expression_attribute_values(Some(attr_values));
+                let pages = ::aymond::retry::paginate(
+                    strategy,
+                    move |start_key| req.clone().set_exclusive_start_key(start_key).send(),
+                    |page: &#aws_sdk_dynamodb::operation::query::QueryOutput| page.last_evaluated_key.clone(),
+                );
+                let mut pages = ::std::pin::pin!(pages);
+                let mut count = ::aymond::count::Count::default();
+                while let Some(page) = ::aymond::shim::futures::StreamExt::next(&mut pages).await {
+                    let page = page?;
+                    count.count += page.count() as u64;
+                    count.scanned_count += page.scanned_count() as u64;
+                }
+                Ok(count)
+            }
+
+            pub async fn send_page(
+                self,
+                page_size: i32,
+                cursor: Option<::aymond::cursor::Cursor>,
+            ) -> Result<
+                ::aymond::cursor::Page<P>,
+                ::aymond::error::RequestError<#aws_sdk_dynamodb::operation::query::QueryError>,
+            > {
+                let signing_key = self.table.aymond.cursor_signing_key.clone();
+                let start_key = cursor
+                    .map(|c| c.decode(
+                        &[ #( (#key_schema_names, #key_schema_types) ),* ],
+                        signing_key.as_deref(),
+                    ))
+                    .transpose()?;
+                let res = self
+                    .raw(|r| r.limit(page_size).set_exclusive_start_key(start_key))
+                    .await?;
+                let items = res
                     .items()
-                    .send();
-                ::aymond::shim::futures::stream::unfold(pagination, |mut p| async move {
-                    p.next().await.map(|item| (item.map(|i| (&i).into()), p))
-                })
+                    .iter()
+                    .map(P::try_from)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let next = res
+                    .last_evaluated_key()
+                    .map(|k| ::aymond::cursor::Cursor::encode(k, signing_key.as_deref()));
+                Ok(::aymond::cursor::Page { items, next })
             }
 
             pub async fn raw<F>(
@@ -357,27 +610,36 @@ pub fn create_query_builder(
             {
                 let index_name = self.index_name.clone();
                 let scan_index_forward = self.scan_index_forward;
-                let limit = self.limit;
+                let page_size = self.page_size;
                 let consistent_read = self.consistent_read;
+                let strategy = self.strategy();
                 let query = f(
                     self.table
                         .aymond
                         .client
                         .query()
                         .set_scan_index_forward(scan_index_forward)
-                        .set_limit(limit)
+                        .set_limit(page_size)
                         .set_consistent_read(consistent_read),
                 );
                 let table_name = &self.table.table_name;
-                let (key_expr, attr_names, attr_values) = self.into();
-                query
+                let (key_expr, filter_expr, mut attr_names, attr_values) = self
+                    .build_expressions()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
+                let projection_expr = ::aymond::projection::projection_expression::<#item_struct, P>()
+                    .map(|(expr, names)| {
+                        attr_names.extend(names);
+                        expr
+                    });
+                let req = query
                     .table_name(table_name)
                     .set_index_name(index_name.clone())
                     .set_key_condition_expression(Some(key_expr))
+                    .set_filter_expression(filter_expr)
+                    .set_projection_expression(projection_expr)
                     .set_expression_attribute_names(Some(attr_names))
-                    .set_expression_attribute_values(Some(attr_values))
-                    .send()
-                    .await
+                    .set_expression_attribute_values(Some(attr_values));
+                ::aymond::retry::retry(&strategy, || req.clone().send()).await
             }
         }
     }
@@ -9,33 +9,254 @@ pub fn create_scan_builder(item: &ItemDefinition) -> TokenStream {
     let item_struct = format_ident!("{}", &item.name);
     let table_struct = format_ident!("{}Table", &item.name);
     let scan_struct = format_ident!("{}Scan", &item.name);
+    let parallel_scan_struct = format_ident!("{}ParallelScan", &item.name);
+
+    let key_schema = item.hash_key.iter().chain(item.sort_key.iter());
+    let key_schema_names: Vec<&String> = key_schema.clone().map(|a| &a.ddb_name).collect();
+    let key_schema_types: Vec<Expr> = key_schema.map(|a| a.scalar_type()).collect();
+    let condition_builder_struct = format_ident!("{}Condition", &item.name);
 
     quote! {
-        pub struct #scan_struct<'a> {
+        pub struct #scan_struct<'a, P = #item_struct> {
             table: &'a #table_struct,
+            filter: Option<::aymond::condition::CondExpr>,
+            page_size: Option<i32>,
+            max_items: Option<usize>,
+            retry_strategy: Option<::aymond::retry::RetryStrategy>,
+            projection: ::std::marker::PhantomData<P>,
         }
 
         impl<'a> #scan_struct<'a> {
             fn new(table: &'a #table_struct) -> Self {
-                Self { table }
+                Self {
+                    table,
+                    filter: None,
+                    page_size: None,
+                    max_items: None,
+                    retry_strategy: None,
+                    projection: ::std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl<'a, P: ::aymond::projection::Projection<#item_struct> + 'a> #scan_struct<'a, P> {
+            pub fn project<Q: ::aymond::projection::Projection<#item_struct>>(self) -> #scan_struct<'a, Q> {
+                #scan_struct {
+                    table: self.table,
+                    filter: self.filter,
+                    page_size: self.page_size,
+                    max_items: self.max_items,
+                    retry_strategy: self.retry_strategy,
+                    projection: ::std::marker::PhantomData,
+                }
+            }
+
+            pub fn page_size(mut self, v: i32) -> Self {
+                self.page_size = Some(v);
+                self
+            }
+
+            pub fn max_items(mut self, v: usize) -> Self {
+                self.max_items = Some(v);
+                self
+            }
+
+            pub fn retry_strategy(mut self, s: ::aymond::retry::RetryStrategy) -> Self {
+                self.retry_strategy = Some(s);
+                self
+            }
+
+            fn strategy(&self) -> ::aymond::retry::RetryStrategy {
+                self.retry_strategy.clone()
+                    .unwrap_or_else(|| self.table.aymond.retry_strategy.clone())
             }
 
-            pub async fn send(self) -> impl ::aymond::shim::futures::Stream<Item = Result<#item_struct, #aws_sdk_dynamodb::error::SdkError<
+            pub fn filter<F, R>(mut self, f: F) -> Self
+            where
+                F: FnOnce(&#condition_builder_struct) -> R,
+                R: ::aymond::condition::IntoOptionalCondExpr,
+            {
+                self.filter = f(&#condition_builder_struct::new()).into_optional_cond_expr();
+                self
+            }
+
+            /// Splits the scan into `total_segments` segments, reading up to `max_concurrency` of
+            /// them at a time.
+            ///
+            /// DynamoDB takes 1 to 1,000,000 segments. Outside that range, the scan yields a single
+            /// `SegmentError` for segment 0 instead of sending any requests.
+            pub fn parallel(self, total_segments: i32, max_concurrency: usize) -> #parallel_scan_struct<'a, P> {
+                #parallel_scan_struct {
+                    scan: self,
+                    total_segments,
+                    max_concurrency,
+                }
+            }
+
+            fn build_expressions(
+                &mut self,
+            ) -> Result<
+                (
+                    Option<String>,
+                    Option<String>,
+                    Option<::std::collections::HashMap<String, String>>,
+                    Option<::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>>,
+                ),
+                ::aymond::error::EncodeError,
+            > {
+                let mut names = ::std::collections::HashMap::new();
+                let mut values = ::std::collections::HashMap::new();
+                let filter_expr = match self.filter.take() {
+                    Some(filter) => {
+                        let (e, n, v) = filter.build()?;
+                        names.extend(n);
+                        values.extend(v);
+                        Some(e)
+                    }
+                    None => None,
+                };
+                let projection_expr = ::aymond::projection::projection_expression::<#item_struct, P>()
+                    .map(|(e, n)| {
+                        names.extend(n);
+                        e
+                    });
+                Ok((
+                    filter_expr,
+                    projection_expr,
+                    if names.is_empty() { None } else { Some(names) },
+                    if values.is_empty() { None } else { Some(values) },
+                ))
+            }
+
+            pub async fn send(mut self) -> impl ::aymond::shim::futures::Stream<Item = Result<P, ::aymond::error::RequestError<
                 #aws_sdk_dynamodb::operation::scan::ScanError,
-                #aws_sdk_dynamodb::config::http::HttpResponse
             >>> + 'a {
-                let pagination = self.table.aymond.client.scan()
-                    .table_name(&self.table.table_name)
-                    .into_paginator()
+                let max_items = self.max_items;
+                // Without an explicit page size or a filter, don't read more than we'll return. A
+                // filter can discard items DynamoDB already counted against the limit, which would
+                // cost a request per page of filtered-out items.
+                let page_size = match self.filter {
+                    None => self
+                        .page_size
+                        .or(max_items.map(|n| i32::try_from(n).unwrap_or(i32::MAX))),
+                    Some(_) => self.page_size,
+                };
+                // An expression that fails to encode fails the first page instead
+                let req = self.build_expressions().map(|(filter_expr, projection_expr, names, values)| {
+                    self.table.aymond.client.scan()
+                        .table_name(&self.table.table_name)
+                        .set_limit(page_size)
+                        .set_filter_expression(filter_expr)
+                        .set_projection_expression(projection_expr)
+                        .set_expression_attribute_names(names)
+                        .set_expression_attribute_values(values)
+                });
+                let pagination = ::aymond::retry::paginate_items(
+                    self.strategy(),
+                    move |start_key| {
+                        let req = req.clone();
+                        async move {
+                            match req {
+                                Ok(req) => req.set_exclusive_start_key(start_key).send().await,
+                                Err(err) => Err(#aws_sdk_dynamodb::error::SdkError::construction_failure(err)),
+                            }
+                        }
+                    },
+                    |page: &#aws_sdk_dynamodb::operation::scan::ScanOutput| page.last_evaluated_key.clone(),
+                    |page: #aws_sdk_dynamodb::operation::scan::ScanOutput| page.items.unwrap_or_default(),
+                );
+                let items = ::aymond::shim::futures::StreamExt::map(pagination, |item| {
+                    item.map_err(::aymond::error::RequestError::from)
+                        .and_then(|i| Ok(P::try_from(&i)?))
+                });
+                ::aymond::shim::futures::StreamExt::take(items, max_items.unwrap_or(usize::MAX))
+            }
+
+            pub async fn first(self) -> Result<
+                Option<P>,
+                ::aymond::error::RequestError<#aws_sdk_dynamodb::operation::scan::ScanError>,
+            > {
+                let items = self.max_items(1).send().await;
+                let mut items = ::std::pin::pin!(items);
+                ::aymond::shim::futures::StreamExt::next(&mut items).await.transpose()
+            }
+
+            pub async fn one(self) -> Result<
+                Option<P>,
+                ::aymond::error::RequestError<#aws_sdk_dynamodb::operation::scan::ScanError>,
+            > {
+                let items = self.max_items(2).send().await;
+                let items: Vec<P> = ::aymond::shim::futures::TryStreamExt::try_collect(items).await?;
+                if items.len() > 1 {
+                    return Err(::aymond::error::RequestError::NotUnique);
+                }
+                Ok(items.into_iter().next())
+            }
+
+            pub async fn count(self) -> Result<
+                ::aymond::count::Count,
+                #aws_sdk_dynamodb::error::SdkError<
+                    #aws_sdk_dynamodb::operation::scan::ScanError,
+                    #aws_sdk_dynamodb::config::http::HttpResponse,
+                >,
+            > {
+                // Counting doesn't read attributes, so drop any projection
+                let mut scan = self.project::<#item_struct>();
+                let (filter_expr, _, names, values) = scan
+                    .build_expressions()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
+                let req = scan.table.aymond.client.scan()
+                    .table_name(&scan.table.table_name)
+                    .select(#aws_sdk_dynamodb::types::Select::Count)
+                    .set_filter_expression(filter_expr)
+                    .set_expression_attribute_names(names)
+                    .set_expression_attribute_values(values);
+                let pages = ::aymond::retry::paginate(
+                    scan.strategy(),
+                    move |start_key| req.clone().set_exclusive_start_key(start_key).send(),
+                    |page: &#aws_sdk_dynamodb::operation::scan::ScanOutput| page.last_evaluated_key.clone(),
+                );
+                let mut pages = ::std::pin::pin!(pages);
+                let mut count = ::aymond::count::Count::default();
+                while let Some(page) = ::aymond::shim::futures::StreamExt::next(&mut pages).await {
+                    let page = page?;
+                    count.count += page.count() as u64;
+                    count.scanned_count += page.scanned_count() as u64;
+                }
+                Ok(count)
+            }
+
+            pub async fn send_page(
+                self,
+                page_size: i32,
+                cursor: Option<::aymond::cursor::Cursor>,
+            ) -> Result<
+                ::aymond::cursor::Page<P>,
+                ::aymond::error::RequestError<#aws_sdk_dynamodb::operation::scan::ScanError>,
+            > {
+                let signing_key = self.table.aymond.cursor_signing_key.clone();
+                let start_key = cursor
+                    .map(|c| c.decode(
+                        &[ #( (#key_schema_names, #key_schema_types) ),* ],
+                        signing_key.as_deref(),
+                    ))
+                    .transpose()?;
+                let res = self
+                    .raw(|r| r.limit(page_size).set_exclusive_start_key(start_key))
+                    .await?;
+                let items = res
                     .items()
-                    .send();
-                ::aymond::shim::futures::stream::unfold(pagination, |mut p| async move {
-                    p.next().await.map(|item| (item.map(|i| (&i).into()), p))
-                })
+                    .iter()
+                    .map(P::try_from)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let next = res
+                    .last_evaluated_key()
+                    .map(|k| ::aymond::cursor::Cursor::encode(k, signing_key.as_deref()));
+                Ok(::aymond::cursor::Page { items, next })
             }
 
             pub async fn raw<F>(
-                self,
+                mut self,
                 f: F,
             ) -> Result<
                 #aws_sdk_dynamodb::operation::scan::ScanOutput,
@@ -48,10 +269,92 @@ pub fn create_scan_builder(item: &ItemDefinition) -> TokenStream {
                 F: FnOnce(#aws_sdk_dynamodb::operation::scan::builders::ScanFluentBuilder)
                     -> #aws_sdk_dynamodb::operation::scan::builders::ScanFluentBuilder,
             {
-                f(self.table.aymond.client.scan())
-                    .table_name(&self.table.table_name)
-                    .send()
-                    .await
+                let (filter_expr, projection_expr, names, values) = self
+                    .build_expressions()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
+                let strategy = self.strategy();
+                let mut req = f(self.table.aymond.client.scan())
+                    .table_name(&self.table.table_name);
+                if filter_expr.is_some() || projection_expr.is_some() {
+                    req = req.set_filter_expression(filter_expr)
+                        .set_projection_expression(projection_expr)
+                        .set_expression_attribute_names(names)
+                        .set_expression_attribute_values(values);
+                }
+                ::aymond::retry::retry(&strategy, || req.clone().send()).await
+            }
+        }
+        pub struct #parallel_scan_struct<'a, P = #item_struct> {
+            scan: #scan_struct<'a, P>,
+            total_segments: i32,
+            max_concurrency: usize,
+        }
+
+        impl<'a, P: ::aymond::projection::Projection<#item_struct> + 'a> #parallel_scan_struct<'a, P> {
+            pub fn send_segments(mut self) -> Vec<impl ::aymond::shim::futures::Stream<Item = Result<P, ::aymond::error::SegmentError<
+                #aws_sdk_dynamodb::operation::scan::ScanError,
+            >>> + 'a> {
+                let total_segments = self.total_segments;
+                // An invalid segment count fails a single segment, since there are none to fail
+                let (expressions, segments) = if (1..=1_000_000).contains(&total_segments) {
+                    (self.scan.build_expressions(), 0..total_segments)
+                } else {
+                    let err = ::aymond::error::EncodeError::new(format!(
+                        "total_segments must be between 1 and 1000000, got {}",
+                        total_segments,
+                    ));
+                    (Err(err), 0..1)
+                };
+                let table = self.scan.table;
+                let page_size = self.scan.page_size;
+                let strategy = self.scan.strategy();
+                segments
+                    .map(|segment| {
+                        // An expression that fails to encode fails the first page of every segment
+                        let req = expressions.clone().map(|(filter_expr, projection_expr, names, values)| {
+                            table.aymond.client.scan()
+                                .table_name(&table.table_name)
+                                .segment(segment)
+                                .total_segments(total_segments)
+                                .set_limit(page_size)
+                                .set_filter_expression(filter_expr)
+                                .set_projection_expression(projection_expr)
+                                .set_expression_attribute_names(names)
+                                .set_expression_attribute_values(values)
+                        });
+                        let pagination = ::aymond::retry::paginate_items(
+                            strategy.clone(),
+                            move |start_key| {
+                                let req = req.clone();
+                                async move {
+                                    match req {
+                                        Ok(req) => req.set_exclusive_start_key(start_key).send().await,
+                                        Err(err) => Err(#aws_sdk_dynamodb::error::SdkError::construction_failure(err)),
+                                    }
+                                }
+                            },
+                            |page: &#aws_sdk_dynamodb::operation::scan::ScanOutput| page.last_evaluated_key.clone(),
+                            |page: #aws_sdk_dynamodb::operation::scan::ScanOutput| page.items.unwrap_or_default(),
+                        );
+                        ::aymond::shim::futures::StreamExt::map(pagination, move |item| {
+                            item.map_err(::aymond::error::RequestError::from)
+                                .and_then(|i| Ok(P::try_from(&i)?))
+                                .map_err(|error| ::aymond::error::SegmentError { segment, error })
+                        })
+                    })
+                    .collect()
+            }
+
+            pub async fn send(self) -> impl ::aymond::shim::futures::Stream<Item = Result<P, ::aymond::error::SegmentError<
+                #aws_sdk_dynamodb::operation::scan::ScanError,
+            >>> + 'a {
+                use ::aymond::shim::futures::StreamExt;
+                let max_concurrency = self.max_concurrency.max(1);
+                let max_items = self.scan.max_items;
+                let segments = self.send_segments();
+                ::aymond::shim::futures::stream::iter(segments.into_iter().map(Box::pin))
+                    .flatten_unordered(max_concurrency)
+                    .take(max_items.unwrap_or(usize::MAX))
             }
         }
     }
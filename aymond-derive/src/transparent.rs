@@ -0,0 +1,91 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, parse_quote};
+
+use crate::ItemAttribute;
+
+/// Generates a single-field tuple struct that is stored exactly like the type it wraps.
+pub fn create_transparent(input: &mut DeriveInput) -> syn::Result<TokenStream> {
+    let name = input.ident.clone();
+    let inner_ty = match &input.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                fields.unnamed.first().unwrap().ty.clone()
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &name,
+                    "#[aymond(transparent)] requires a tuple struct with exactly one field",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &name,
+                "#[aymond(transparent)] supports only structs",
+            ));
+        }
+    };
+
+    let inner = ItemAttribute::new(format_ident!("value"), String::new(), inner_ty);
+    if inner.is_option {
+        return Err(syn::Error::new_spanned(
+            &inner.ty,
+            "#[aymond(transparent)] cannot wrap an Option; make the field optional instead",
+        ));
+    }
+
+    let attr_val: TokenStream = quote!(::aymond::shim::aws_sdk_dynamodb::types::AttributeValue);
+    let condition_path = format_ident!("{}ConditionPath", name);
+    let update_path = format_ident!("{}UpdatePath", name);
+    let encode = inner.to_attribute_value(&quote!(self.0));
+    let decode = inner.decode_attribute_value(&parse_quote!(v));
+    let expected = inner.expected_type_name();
+
+    Ok(quote! {
+        #[derive(Debug, PartialEq)]
+        #input
+
+        impl ::aymond::marshal::IntoAttributeValue for #name {
+            fn into_attribute_value(self) -> Result<#attr_val, ::aymond::error::EncodeError> {
+                #encode
+            }
+        }
+
+        impl ::aymond::marshal::FromAttributeValue for #name {
+            const TYPE_NAME: &'static str = #expected;
+
+            fn from_attribute_value(v: &#attr_val) -> Result<Self, ::aymond::error::DecodeError> {
+                #decode.map(#name)
+            }
+        }
+
+        impl ::aymond::condition::IntoConditionValue for #name {
+            fn into_condition_value(self) -> Result<#attr_val, ::aymond::error::EncodeError> {
+                ::aymond::marshal::IntoAttributeValue::into_attribute_value(self)
+            }
+        }
+
+        impl ::aymond::update::IntoUpdateValue for #name {
+            fn into_update_value(self) -> Result<#attr_val, ::aymond::error::EncodeError> {
+                ::aymond::marshal::IntoAttributeValue::into_attribute_value(self)
+            }
+        }
+
+        pub type #condition_path = ::aymond::condition::ScalarConditionPath<#name>;
+        pub type #update_path<O = ::aymond::update::Required> =
+            ::aymond::update::ScalarUpdatePath<#name, O>;
+
+        impl ::aymond::marshal::PartialValue for #name {
+            type Partial = #name;
+        }
+
+        impl ::aymond::condition::HasConditionPath for #name {
+            type Path = #condition_path;
+        }
+
+        impl ::aymond::update::HasUpdatePath for #name {
+            type Path<O> = #update_path<O>;
+        }
+    })
+}
@@ -6,6 +6,7 @@ use syn::{Expr, parse_quote};
 pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
     let aws_sdk_dynamodb: Expr = parse_quote!(::aymond::shim::aws_sdk_dynamodb);
 
+    let item_struct = format_ident!("{}", &item.name);
     let table_struct = format_ident!("{}Table", &item.name);
     let update_item_struct = format_ident!("{}UpdateItem", &item.name);
     let hash_key_struct = format_ident!("{}UpdateItemHashKey", &item.name);
@@ -26,11 +27,12 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
             let fn_name = &attr.field;
             let ddb_name = &attr.ddb_name;
             let return_type = attr.update_path_type();
+            let new_path = attr.new_update_path(&quote! {
+                vec![::aymond::update::PathSegment::Attr(#ddb_name.to_string())]
+            });
             quote! {
                 pub fn #fn_name(&self) -> #return_type {
-                    ::aymond::update::UpdatePathRoot::with_prefix(
-                        vec![::aymond::update::PathSegment::Attr(#ddb_name.to_string())]
-                    )
+                    #new_path
                 }
             }
         })
@@ -51,6 +53,47 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
         })
         .collect();
 
+    let (expected_version_fn, bump_version) = if let Some(ver_attr) = &item.version_attribute {
+        let ver_ty = &ver_attr.ty;
+        let ver_ddb_name = &ver_attr.ddb_name;
+        let expected_version_fn = quote! {
+            pub fn expected_version(self, v: impl Into<#ver_ty>) -> #update_item_struct<'a> {
+                self.cond.set_version_value(v.into());
+                self
+            }
+        };
+        // ADD treats a missing version as zero, so this also covers items that don't exist yet.
+        // Without an expected version the bump wouldn't be checked against anything, and
+        // updating the version alongside the bump would touch the same path twice, which
+        // DynamoDB rejects, so both fail before sending instead.
+        let bump_version = quote! {
+            let expr = if !self.cond.is_versioning_enabled() {
+                expr
+            } else if !self.cond.has_version_value() {
+                ::aymond::update::UpdateExpr::invalid(
+                    ::aymond::error::EncodeError::new(
+                        "an expected version is required; call expected_version() or disable versioning",
+                    )
+                    .at_attr(#ver_ddb_name),
+                )
+            } else if expr.updates_attribute(#ver_ddb_name) {
+                ::aymond::update::UpdateExpr::invalid(
+                    ::aymond::error::EncodeError::new(
+                        "the version is incremented automatically; disable versioning to update it",
+                    )
+                    .at_attr(#ver_ddb_name),
+                )
+            } else {
+                let path = vec![::aymond::update::PathSegment::Attr(#ver_ddb_name.to_string())];
+                let one = #aws_sdk_dynamodb::types::AttributeValue::N("1".to_string());
+                expr.and(::aymond::update::UpdateExpr::add(path, one))
+            };
+        };
+        (expected_version_fn, bump_version)
+    } else {
+        (quote! {}, quote! {})
+    };
+
     let (builders, build_key_map) = if item.sort_key.is_some() {
         let sort_key_struct = format_ident!("{}UpdateItemSortKey", &item.name);
         let sort_key = item.sort_key.as_ref().unwrap();
@@ -60,11 +103,17 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
         let sort_key_boxer = &sort_key.to_attribute_value(&parse_quote!(sk));
 
         let build_key_map = quote! {
-            let hk = self.hk.unwrap();
-            let sk = self.sk.unwrap();
+            let hk = self.hk.take().unwrap();
+            let sk = self.sk.take().unwrap();
             let mut key_values = ::std::collections::HashMap::new();
-            key_values.insert(#hash_key_attr_name.to_string(), #hash_key_boxer);
-            key_values.insert(#sort_key_attr_name.to_string(), #sort_key_boxer);
+            key_values.insert(
+                #hash_key_attr_name.to_string(),
+                #hash_key_boxer.map_err(|e| e.at_attr(#hash_key_attr_name))?,
+            );
+            key_values.insert(
+                #sort_key_attr_name.to_string(),
+                #sort_key_boxer.map_err(|e| e.at_attr(#sort_key_attr_name))?,
+            );
         };
 
         let builders = quote! {
@@ -78,6 +127,7 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
                 sk: Option<#sort_key_typ>,
                 cond: #condition_builder_struct,
                 expr: Option<::aymond::update::UpdateExpr>,
+                retry_strategy: Option<::aymond::retry::RetryStrategy>,
             }
 
             impl<'a> #update_item_struct<'a> {
@@ -88,6 +138,7 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
                         sk: None,
                         cond: #condition_builder_struct::new(),
                         expr: None,
+                        retry_strategy: None,
                     };
                     #hash_key_struct { q }
                 }
@@ -114,9 +165,12 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
         (builders, build_key_map)
     } else {
         let build_key_map = quote! {
-            let hk = self.hk.unwrap();
+            let hk = self.hk.take().unwrap();
             let mut key_values = ::std::collections::HashMap::new();
-            key_values.insert(#hash_key_attr_name.to_string(), #hash_key_boxer);
+            key_values.insert(
+                #hash_key_attr_name.to_string(),
+                #hash_key_boxer.map_err(|e| e.at_attr(#hash_key_attr_name))?,
+            );
         };
 
         let builders = quote! {
@@ -129,6 +183,7 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
                 hk: Option<#hash_key_typ>,
                 cond: #condition_builder_struct,
                 expr: Option<::aymond::update::UpdateExpr>,
+                retry_strategy: Option<::aymond::retry::RetryStrategy>,
             }
 
             impl<'a> #update_item_struct<'a> {
@@ -138,6 +193,7 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
                         hk: None,
                         cond: #condition_builder_struct::new(),
                         expr: None,
+                        retry_strategy: None,
                     };
                     #hash_key_struct { q }
                 }
@@ -176,6 +232,16 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
         #builders
 
         impl<'a> #update_item_struct<'a> {
+            fn key_values(
+                &mut self,
+            ) -> Result<
+                ::std::collections::HashMap<String, #aws_sdk_dynamodb::types::AttributeValue>,
+                ::aymond::error::EncodeError,
+            > {
+                #build_key_map
+                Ok(key_values)
+            }
+
             pub fn expression<F, R>(mut self, f: F) -> #update_item_struct<'a>
             where
                 F: FnOnce(&#expression_builder_struct) -> R,
@@ -200,6 +266,13 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
                 self
             }
 
+            #expected_version_fn
+
+            pub fn retry_strategy(mut self, s: ::aymond::retry::RetryStrategy) -> #update_item_struct<'a> {
+                self.retry_strategy = Some(s);
+                self
+            }
+
             pub async fn send(self) -> Result<
                 (),
                 #aws_sdk_dynamodb::error::SdkError<
@@ -211,8 +284,21 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
                 Ok(())
             }
 
+            pub async fn send_returning<R>(self, returning: R) -> Result<
+                Option<R::Output>,
+                ::aymond::error::RequestError<
+                    #aws_sdk_dynamodb::operation::update_item::UpdateItemError,
+                >,
+            >
+            where
+                R: ::aymond::update::ReturnSelector<#item_struct>,
+            {
+                let res = self.raw(|r| r.return_values(returning.return_value())).await?;
+                Ok(res.attributes().map(R::decode).transpose()?)
+            }
+
             pub async fn raw<F>(
-                self,
+                mut self,
                 f: F,
             ) -> Result<
                 #aws_sdk_dynamodb::operation::update_item::UpdateItemOutput,
@@ -225,9 +311,14 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
                 F: FnOnce(#aws_sdk_dynamodb::operation::update_item::builders::UpdateItemFluentBuilder)
                 -> #aws_sdk_dynamodb::operation::update_item::builders::UpdateItemFluentBuilder
             {
-                let (cond_expr, cond_names, cond_values) = self.cond.build();
-                let (update_expr, update_names, update_values) =
-                    self.expr.expect("update expression not set").build();
+                let key_values = self.key_values()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
+                let expr = self.expr.take().expect("update expression not set");
+                #bump_version
+                let (cond_expr, cond_names, cond_values) = self.cond.build()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
+                let (update_expr, update_names, update_values) = expr.build()
+                    .map_err(#aws_sdk_dynamodb::error::SdkError::construction_failure)?;
 
                 let mut names = ::std::collections::HashMap::new();
                 if let Some(cond_names) = cond_names {
@@ -245,25 +336,35 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
 
                 let table_name = &self.table.table_name;
                 let client = &self.table.aymond.client;
-                #build_key_map
+                let strategy = self.retry_strategy.clone()
+                    .unwrap_or_else(|| self.table.aymond.retry_strategy.clone());
 
-                f(client.update_item())
+                let req = f(client.update_item())
                     .table_name(table_name)
                     .set_key(Some(key_values))
                     .set_update_expression(Some(update_expr))
                     .set_condition_expression(cond_expr)
                     .set_expression_attribute_names(names)
-                    .set_expression_attribute_values(values)
-                    .send()
-                    .await
+                    .set_expression_attribute_values(values);
+                ::aymond::retry::retry_write(&strategy, || req.clone().send()).await
+            }
+        }
+
+        impl<'a> TryFrom<#update_item_struct<'a>> for #aws_sdk_dynamodb::types::Update {
+            type Error = ::aymond::error::EncodeError;
+
+            fn try_from(update: #update_item_struct<'a>) -> Result<Self, Self::Error> {
+                update.into_update()
             }
         }
 
-        impl<'a> Into<#aws_sdk_dynamodb::types::Update> for #update_item_struct<'a> {
-            fn into(self) -> #aws_sdk_dynamodb::types::Update {
-                let (cond_expr, cond_names, cond_values) = self.cond.build();
-                let (update_expr, update_names, update_values) =
-                    self.expr.expect("update expression not set").build();
+        impl<'a> #update_item_struct<'a> {
+            fn into_update(mut self) -> Result<#aws_sdk_dynamodb::types::Update, ::aymond::error::EncodeError> {
+                let key_values = self.key_values()?;
+                let expr = self.expr.take().expect("update expression not set");
+                #bump_version
+                let (cond_expr, cond_names, cond_values) = self.cond.build()?;
+                let (update_expr, update_names, update_values) = expr.build()?;
 
                 let mut names = ::std::collections::HashMap::new();
                 if let Some(cond_names) = cond_names {
@@ -280,9 +381,8 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
                 let values = if values.is_empty() { None } else { Some(values) };
 
                 let table_name = &self.table.table_name;
-                #build_key_map
 
-                #aws_sdk_dynamodb::types::Update::builder()
+                Ok(#aws_sdk_dynamodb::types::Update::builder()
                     .table_name(table_name)
                     .set_key(Some(key_values))
                     .set_update_expression(Some(update_expr))
@@ -290,7 +390,7 @@ pub fn create_update_builder(item: &ItemDefinition) -> TokenStream {
                     .set_expression_attribute_names(names)
                     .set_expression_attribute_values(values)
                     .build()
-                    .unwrap()
+                    .unwrap())
             }
         }
     }
@@ -43,7 +43,10 @@ async fn test_batch_get_with_sort_key() {
         .make_and_model("BMW", "M3")
         .send()
         .await
-        .unwrap();
+        .unwrap()
+        .into_iter()
+        .map(|r| r.unwrap())
+        .collect::<Vec<_>>();
     results.sort_by(|a, b| a.make.cmp(&b.make));
 
     assert_eq!(results.len(), 2);
@@ -68,7 +71,43 @@ async fn test_batch_get_with_sort_key() {
         .await
         .unwrap();
     assert_eq!(results.len(), 1);
-    assert_eq!(results[0], ferrari());
+    assert_eq!(results[0].as_ref().unwrap(), &ferrari());
+
+    // An item that fails to decode doesn't take down the rest of the batch
+    #[aymond(item, table)]
+    struct TextCar {
+        #[aymond(hash_key)]
+        make: String,
+        #[aymond(sort_key)]
+        model: String,
+        hp: String,
+    }
+
+    let text_table = TextCarTable::new(&aymond, "batch_get_sort");
+    text_table
+        .put()
+        .item(TextCar {
+            make: "Lada".into(),
+            model: "Niva".into(),
+            hp: "plenty".into(),
+        })
+        .send()
+        .await
+        .unwrap();
+
+    let results = table
+        .batch_get()
+        .make_and_model("Ferrari", "F40")
+        .make_and_model("Lada", "Niva")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    let (ok, err): (Vec<_>, Vec<_>) = results.into_iter().partition(|r| r.is_ok());
+    assert_eq!(ok[0].as_ref().unwrap(), &ferrari());
+    let err = err[0].as_ref().unwrap_err();
+    assert_eq!(err.path, "hp");
+    assert_eq!(err.actual, Some("S"));
 }
 
 #[tokio::test]
@@ -105,7 +144,10 @@ async fn test_batch_get_no_sort_key() {
         .name("Ferrari")
         .send()
         .await
-        .unwrap();
+        .unwrap()
+        .into_iter()
+        .map(|r| r.unwrap())
+        .collect::<Vec<_>>();
     results.sort_by(|a, b| a.name.cmp(&b.name));
 
     assert_eq!(results.len(), 2);
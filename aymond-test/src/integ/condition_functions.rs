@@ -0,0 +1,103 @@
+#[tokio::test]
+async fn test_condition_functions() {
+    use aymond::{Aymond, condition::AttributeType, prelude::*, shim::futures::StreamExt};
+    use std::collections::HashSet;
+
+    #[aymond(nested_item)]
+    struct Owner {
+        name: String,
+    }
+
+    #[aymond(item, table)]
+    struct Task {
+        #[aymond(hash_key)]
+        id: String,
+        status: String,
+        start: i64,
+        end: i64,
+        tags: Vec<String>,
+        watchers: HashSet<String>,
+        owner: Option<Owner>,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = TaskTable::new(&aymond, "condition_functions");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    for (id, status, start, end, tags, owner) in [
+        (
+            "t-1",
+            "open",
+            10,
+            20,
+            vec!["a", "b", "c", "d"],
+            Some("alice"),
+        ),
+        ("t-2", "closed", 30, 25, vec!["a"], None),
+        ("t-3", "blocked", 40, 50, vec!["b", "c"], Some("bob")),
+    ] {
+        table
+            .put()
+            .item(Task {
+                id: id.to_string(),
+                status: status.to_string(),
+                start,
+                end,
+                tags: tags.into_iter().map(String::from).collect(),
+                watchers: HashSet::from(["carol".to_string()]),
+                owner: owner.map(|name| Owner {
+                    name: name.to_string(),
+                }),
+            })
+            .send()
+            .await
+            .expect("Failed to write");
+    }
+
+    let table = &table;
+    let scan = |filter: fn(&TaskCondition) -> aymond::condition::CondExpr| async move {
+        let mut ids: Vec<String> = table
+            .scan()
+            .filter(filter)
+            .send()
+            .await
+            .map(|e| e.unwrap().id)
+            .collect()
+            .await;
+        ids.sort();
+        ids
+    };
+
+    assert_eq!(scan(|c| c.tags().size().gt(3)).await, vec!["t-1"]);
+    assert_eq!(
+        scan(|c| c.status().is_in(["open", "blocked"])).await,
+        vec!["t-1", "t-3"]
+    );
+    assert_eq!(
+        scan(|c| c.end().gt_attr(c.start())).await,
+        vec!["t-1", "t-3"]
+    );
+    assert_eq!(scan(|c| c.tags().contains("c")).await, vec!["t-1", "t-3"]);
+    assert_eq!(
+        scan(|c| c.status().contains("lo")).await,
+        vec!["t-2", "t-3"]
+    );
+    assert_eq!(
+        scan(|c| c.owner().attribute_not_exists()).await,
+        vec!["t-2"]
+    );
+    assert_eq!(
+        scan(|c| c.owner().attribute_type(AttributeType::M)).await,
+        vec!["t-1", "t-3"]
+    );
+    assert_eq!(
+        scan(|c| c
+            .watchers()
+            .size()
+            .eq(1)
+            .and(c.status().size().between(4, 6)))
+        .await,
+        vec!["t-1", "t-2"]
+    );
+}
@@ -0,0 +1,88 @@
+#[tokio::test]
+async fn test() {
+    use aymond::{Aymond, count::Count, prelude::*};
+
+    #[aymond(item, table)]
+    struct Comment {
+        #[aymond(hash_key)]
+        post: String,
+        #[aymond(sort_key)]
+        id: i32,
+        #[aymond(gsi("by_author", hash_key))]
+        author: String,
+        likes: i32,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CommentTable::new(&aymond, "count");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    for id in 0..10 {
+        table
+            .put()
+            .item(Comment {
+                post: if id < 7 { "p1" } else { "p2" }.to_string(),
+                id,
+                author: if id % 2 == 0 { "alice" } else { "bob" }.to_string(),
+                likes: id,
+            })
+            .send()
+            .await
+            .expect("Failed to write");
+    }
+
+    let count = table
+        .query()
+        .post("p1")
+        .id_ge(0)
+        .count()
+        .await
+        .expect("count should succeed");
+    assert_eq!(
+        count,
+        Count {
+            count: 7,
+            scanned_count: 7
+        }
+    );
+
+    // Filters reduce the count but not the scanned count
+    let count = table
+        .query()
+        .post("p1")
+        .id_ge(0)
+        .filter(|c| c.likes().ge(5))
+        .count()
+        .await
+        .expect("count should succeed");
+    assert_eq!(
+        count,
+        Count {
+            count: 2,
+            scanned_count: 7
+        }
+    );
+
+    let count = table
+        .query_by_author()
+        .author("alice")
+        .count()
+        .await
+        .expect("count should succeed");
+    assert_eq!(count.count, 5);
+
+    let count = table
+        .scan()
+        .filter(|c| c.author().eq("bob"))
+        .count()
+        .await
+        .expect("count should succeed");
+    assert_eq!(
+        count,
+        Count {
+            count: 5,
+            scanned_count: 10
+        }
+    );
+}
@@ -0,0 +1,104 @@
+#[tokio::test]
+async fn test() {
+    use aymond::{Aymond, prelude::*, shim::aws_sdk_dynamodb::types::AttributeValue};
+    use std::{collections::HashMap, net::IpAddr, time::Duration};
+
+    mod ip_string {
+        use aymond::shim::aws_sdk_dynamodb::types::AttributeValue;
+        use std::net::IpAddr;
+
+        pub fn to_attribute_value(v: &IpAddr) -> AttributeValue {
+            AttributeValue::S(v.to_string())
+        }
+
+        pub fn from_attribute_value(v: &AttributeValue) -> Result<IpAddr, String> {
+            let s = v.as_s().map_err(|_| "expected a string".to_string())?;
+            s.parse().map_err(|e| format!("{e}"))
+        }
+    }
+
+    mod duration_millis {
+        use aymond::shim::aws_sdk_dynamodb::types::AttributeValue;
+        use std::time::Duration;
+
+        pub fn to_attribute_value(v: &Duration) -> AttributeValue {
+            AttributeValue::N(v.as_millis().to_string())
+        }
+
+        pub fn from_attribute_value(v: &AttributeValue) -> Result<Duration, String> {
+            let n = v.as_n().map_err(|_| "expected a number".to_string())?;
+            n.parse()
+                .map(Duration::from_millis)
+                .map_err(|e| format!("{e}"))
+        }
+    }
+
+    #[aymond(item, table)]
+    struct Probe {
+        #[aymond(hash_key)]
+        region: String,
+        #[aymond(sort_key, with = "ip_string", scalar = "S")]
+        address: IpAddr,
+        #[aymond(with = "duration_millis", scalar = "N")]
+        latency: Duration,
+        #[aymond(with = "duration_millis")]
+        timeout: Option<Duration>,
+    }
+
+    let it_factory = || Probe {
+        region: "eu".to_string(),
+        address: "10.0.0.1".parse().unwrap(),
+        latency: Duration::from_millis(42),
+        timeout: None,
+    };
+
+    // Converter errors are reported against the attribute
+    let mut map: HashMap<String, AttributeValue> = it_factory().try_into().unwrap();
+    assert_eq!(map["latency"], AttributeValue::N("42".to_string()));
+    map.insert("address".to_string(), AttributeValue::S("nope".to_string()));
+    let err = Probe::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "address");
+    assert_eq!(err.expected, "S");
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = ProbeTable::new(&aymond, "custom_converter");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    table
+        .put()
+        .item(it_factory())
+        .send()
+        .await
+        .expect("Failed to write");
+
+    let address: IpAddr = "10.0.0.1".parse().unwrap();
+    let get = table
+        .get()
+        .region("eu")
+        .address(address)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(get.unwrap(), it_factory());
+
+    table
+        .update()
+        .region("eu")
+        .address(address)
+        .expression(|e| e.timeout().set(Duration::from_secs(5)))
+        .condition(|c| c.latency().lt(Duration::from_millis(100)))
+        .send()
+        .await
+        .expect("Update should succeed");
+
+    let updated = table
+        .get()
+        .region("eu")
+        .address(address)
+        .send()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.timeout, Some(Duration::from_secs(5)));
+}
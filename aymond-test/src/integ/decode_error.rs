@@ -0,0 +1,188 @@
+#[test]
+fn test() {
+    use aymond::{prelude::*, shim::aws_sdk_dynamodb::types::AttributeValue};
+    use std::collections::{BTreeMap, HashMap};
+
+    #[aymond(item)]
+    struct Car {
+        #[aymond(hash_key)]
+        make: String,
+        hp: i16,
+        variants: Vec<String>,
+        production: Production,
+        nickname: Option<String>,
+        counters: Option<BTreeMap<String, i64>>,
+        drive: Option<Drive>,
+    }
+
+    #[aymond(enum)]
+    enum Drive {
+        Rear,
+        All,
+    }
+
+    #[aymond(nested_item)]
+    struct Production {
+        began: i32,
+    }
+
+    let valid = || {
+        let mut production = HashMap::new();
+        production.insert("began".to_string(), AttributeValue::N("1964".into()));
+        let mut map = HashMap::new();
+        map.insert("make".to_string(), AttributeValue::S("Porsche".into()));
+        map.insert("hp".to_string(), AttributeValue::N("518".into()));
+        map.insert(
+            "variants".to_string(),
+            AttributeValue::L(vec![AttributeValue::S("Carrera".into())]),
+        );
+        map.insert("production".to_string(), AttributeValue::M(production));
+        map.insert("nickname".to_string(), AttributeValue::Null(true));
+        map
+    };
+
+    let car = Car::try_from(&valid()).expect("Valid item should decode");
+    assert_eq!(car.nickname, None);
+
+    // Missing required attribute
+    let mut map = valid();
+    map.remove("hp");
+    let err = Car::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "hp");
+    assert_eq!(err.expected, "N");
+    assert_eq!(err.actual, None);
+
+    // Wrong type inside a list
+    let mut map = valid();
+    map.insert(
+        "variants".to_string(),
+        AttributeValue::L(vec![
+            AttributeValue::S("Carrera".into()),
+            AttributeValue::Bool(true),
+        ]),
+    );
+    let err = Car::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "variants[1]");
+    assert_eq!(err.expected, "S");
+    assert_eq!(err.actual, Some("BOOL"));
+
+    // Wrong type inside a nested item
+    let mut map = valid();
+    let mut production = HashMap::new();
+    production.insert("began".to_string(), AttributeValue::S("1964".into()));
+    map.insert("production".to_string(), AttributeValue::M(production));
+    let err = Car::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "production.began");
+    assert_eq!(
+        err.to_string(),
+        "failed to decode `production.began`: expected N, found S"
+    );
+
+    // Number out of range for the field type
+    let mut map = valid();
+    map.insert("hp".to_string(), AttributeValue::N("99999".into()));
+    let err = Car::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "hp");
+    assert_eq!(err.actual, None);
+    assert!(err.reason.is_some());
+
+    // Wrong type for an optional attribute is still an error
+    let mut map = valid();
+    map.insert("nickname".to_string(), AttributeValue::N("1".into()));
+    let err = Car::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "nickname");
+
+    // Wrong type for a map value
+    let mut map = valid();
+    let mut counters = HashMap::new();
+    counters.insert("clicks".to_string(), AttributeValue::S("3".into()));
+    map.insert("counters".to_string(), AttributeValue::M(counters));
+    let err = Car::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "counters.clicks");
+
+    // Unknown enum variant
+    let mut map = valid();
+    map.insert("drive".to_string(), AttributeValue::S("Front".into()));
+    let err = Car::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "drive");
+    assert_eq!(err.actual, None);
+    assert_eq!(
+        err.to_string(),
+        "failed to decode `drive`: invalid S value: unknown variant `Front`"
+    );
+}
+
+#[test]
+fn test_converter_errors() {
+    use aymond::prelude::*;
+    use aymond::shim::aws_sdk_dynamodb::types::AttributeValue;
+    use aymond::shim::{
+        chrono::{DateTime, Utc},
+        uuid::Uuid,
+    };
+    use std::collections::HashMap;
+
+    mod upper {
+        use aymond::shim::aws_sdk_dynamodb::types::AttributeValue;
+
+        pub fn to_attribute_value(v: &str) -> AttributeValue {
+            AttributeValue::S(v.to_uppercase())
+        }
+
+        pub fn from_attribute_value(v: &AttributeValue) -> Result<String, String> {
+            v.as_s()
+                .map(|s| s.to_lowercase())
+                .map_err(|_| "not a string".to_string())
+        }
+    }
+
+    #[aymond(item)]
+    struct Session {
+        #[aymond(hash_key, with = "aymond::marshal::uuid::binary", scalar = "B")]
+        id: Uuid,
+        #[aymond(with = "aymond::marshal::chrono::epoch_seconds")]
+        expires: DateTime<Utc>,
+        #[aymond(with = "upper")]
+        owner: String,
+    }
+
+    let valid = || {
+        HashMap::from([
+            (
+                "id".to_string(),
+                AttributeValue::B(Uuid::nil().as_bytes().to_vec().into()),
+            ),
+            (
+                "expires".to_string(),
+                AttributeValue::N("1700000000".into()),
+            ),
+            ("owner".to_string(), AttributeValue::S("AL".into())),
+        ])
+    };
+    assert_eq!(Session::try_from(&valid()).unwrap().owner, "al");
+
+    // The converter's own error is kept, with the attribute's path added
+    let mut map = valid();
+    map.insert("expires".to_string(), AttributeValue::S("soon".into()));
+    let err = Session::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "expires");
+    assert_eq!(err.expected, "N");
+    assert_eq!(err.actual, Some("S"));
+
+    let mut map = valid();
+    map.insert("id".to_string(), AttributeValue::B(vec![1, 2, 3].into()));
+    let err = Session::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "id");
+    assert_eq!(err.expected, "B");
+    assert!(err.reason.is_some());
+
+    // Other errors become the reason
+    let mut map = valid();
+    map.insert("owner".to_string(), AttributeValue::Bool(true));
+    let err = Session::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "owner");
+    assert_eq!(
+        err.to_string(),
+        "failed to decode `owner`: invalid custom value: not a string"
+    );
+}
@@ -0,0 +1,119 @@
+#[tokio::test]
+async fn test() {
+    use aymond::{Aymond, prelude::*};
+
+    #[aymond(item, table)]
+    struct Order {
+        #[aymond(hash_key)]
+        id: String,
+        status: OrderState,
+        payment: Payment,
+        previous: Option<OrderState>,
+    }
+
+    #[aymond(enum)]
+    enum OrderState {
+        Pending,
+        Shipped,
+        Delivered,
+    }
+
+    #[aymond(enum, tag = "kind")]
+    enum Payment {
+        Card {
+            last4: String,
+            #[aymond(attribute(name = "exp"))]
+            expires: Option<i32>,
+        },
+        Invoice {
+            due_days: u16,
+        },
+        Cash,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = OrderTable::new(&aymond, "enum_attribute");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    let it_factory = || Order {
+        id: "o-1".to_string(),
+        status: OrderState::Pending,
+        payment: Payment::Card {
+            last4: "4242".to_string(),
+            expires: Some(2030),
+        },
+        previous: None,
+    };
+    table
+        .put()
+        .item(it_factory())
+        .send()
+        .await
+        .expect("Failed to write");
+
+    let get = table.get().id("o-1").send().await.unwrap();
+    assert_eq!(get.unwrap(), it_factory());
+
+    // Conditions on unit variants and on the tag and fields of a tagged variant
+    table
+        .put()
+        .item(it_factory())
+        .condition(|c| {
+            c.status()
+                .eq(OrderState::Pending)
+                .and(c.payment().tag().eq("Card"))
+                .and(c.payment().card().last4().eq("4242"))
+        })
+        .send()
+        .await
+        .expect("Enum condition should succeed");
+
+    let result = table
+        .put()
+        .item(it_factory())
+        .condition(|c| c.status().eq(OrderState::Shipped))
+        .send()
+        .await;
+    assert!(result.is_err(), "Enum condition should fail");
+
+    // Update enums as a whole
+    table
+        .update()
+        .id("o-1")
+        .expression(|e| {
+            e.status()
+                .set(OrderState::Shipped)
+                .and(e.previous().set(OrderState::Pending))
+                .and(e.payment().set(Payment::Cash))
+        })
+        .send()
+        .await
+        .expect("Update should succeed");
+
+    let updated = table.get().id("o-1").send().await.unwrap().unwrap();
+    assert_eq!(updated.status, OrderState::Shipped);
+    assert_eq!(updated.previous, Some(OrderState::Pending));
+    assert_eq!(updated.payment, Payment::Cash);
+
+    // Update a field inside the current variant
+    table
+        .put()
+        .item(Order {
+            payment: Payment::Invoice { due_days: 30 },
+            ..it_factory()
+        })
+        .send()
+        .await
+        .expect("Failed to write");
+    table
+        .update()
+        .id("o-1")
+        .expression(|e| e.payment().invoice().due_days().add(15u16))
+        .send()
+        .await
+        .expect("Update should succeed");
+
+    let updated = table.get().id("o-1").send().await.unwrap().unwrap();
+    assert_eq!(updated.payment, Payment::Invoice { due_days: 45 });
+}
@@ -0,0 +1,173 @@
+#[tokio::test]
+async fn test() {
+    use aymond::shim::aws_sdk_dynamodb::types::AttributeValue;
+    use aymond::shim::{
+        chrono::{DateTime, TimeZone, Utc},
+        rust_decimal::Decimal,
+        serde_json::{Value, json},
+        time::OffsetDateTime,
+        uuid::Uuid,
+    };
+    use aymond::{Aymond, prelude::*};
+    use std::collections::HashMap;
+
+    #[aymond(item, table)]
+    struct Invoice {
+        #[aymond(hash_key)]
+        id: Uuid,
+        #[aymond(sort_key)]
+        issued: DateTime<Utc>,
+        #[aymond(with = "aymond::marshal::chrono::epoch_seconds", scalar = "N")]
+        due: DateTime<Utc>,
+        #[aymond(with = "aymond::marshal::uuid::binary", scalar = "B")]
+        customer: Uuid,
+        paid: Option<OffsetDateTime>,
+        total: Decimal,
+        metadata: Value,
+    }
+
+    let it_factory = || Invoice {
+        id: Uuid::from_u128(0x67e5504410b1426f9247bb680e5fe0c8),
+        issued: Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap(),
+        due: Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap(),
+        customer: Uuid::from_u128(1),
+        paid: Some(OffsetDateTime::from_unix_timestamp(1_717_000_000).unwrap()),
+        total: "1234.50".parse().unwrap(),
+        metadata: json!({ "tags": ["a", "b"], "rush": true, "weight": 1.5, "note": null }),
+    };
+
+    // Encodings
+    let mut map: HashMap<String, AttributeValue> = it_factory().try_into().unwrap();
+    assert_eq!(
+        map["id"],
+        AttributeValue::S("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string())
+    );
+    assert_eq!(
+        map["issued"],
+        AttributeValue::S("2024-05-01T12:30:00Z".to_string())
+    );
+    assert_eq!(map["due"], AttributeValue::N("1717200000".to_string()));
+    assert_eq!(map["customer"].as_b().unwrap().as_ref().len(), 16);
+    assert_eq!(map["total"], AttributeValue::N("1234.5".to_string()));
+    let metadata = map["metadata"].as_m().unwrap();
+    assert_eq!(metadata["rush"], AttributeValue::Bool(true));
+    assert_eq!(metadata["note"], AttributeValue::Null(true));
+    assert!(metadata["tags"].is_l());
+    assert_eq!(Invoice::try_from(&map).unwrap(), it_factory());
+
+    // Decimals keep their precision
+    map.insert(
+        "total".to_string(),
+        AttributeValue::N("0.1000000000000000000000000001".to_string()),
+    );
+    let invoice = Invoice::try_from(&map).unwrap();
+    assert_eq!(invoice.total.to_string(), "0.1000000000000000000000000001");
+
+    map.insert(
+        "id".to_string(),
+        AttributeValue::S("not-a-uuid".to_string()),
+    );
+    let err = Invoice::try_from(&map).unwrap_err();
+    assert_eq!(err.path, "id");
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = InvoiceTable::new(&aymond, "feature_types");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    table
+        .put()
+        .item(it_factory())
+        .send()
+        .await
+        .expect("Failed to write");
+
+    let invoice = it_factory();
+    let get = table
+        .get()
+        .id(invoice.id)
+        .issued(invoice.issued)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(get.unwrap(), it_factory());
+
+    table
+        .update()
+        .id(invoice.id)
+        .issued(invoice.issued)
+        .expression(|e| {
+            e.total()
+                .add(Decimal::new(50, 2))
+                .and(e.metadata().set(json!({ "rush": false })))
+        })
+        .condition(|c| {
+            c.due()
+                .lt(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap())
+                .and(c.customer().eq(Uuid::from_u128(1)))
+                .and(c.total().eq(invoice.total))
+        })
+        .send()
+        .await
+        .expect("Update should succeed");
+
+    let updated = table
+        .get()
+        .id(invoice.id)
+        .issued(invoice.issued)
+        .send()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.total, "1235.00".parse::<Decimal>().unwrap());
+    assert_eq!(updated.metadata, json!({ "rush": false }));
+}
+
+#[test]
+fn test_own_types_sharing_feature_type_names() {
+    use aymond::condition::{ConditionPathRoot, PathSegment};
+    use aymond::prelude::*;
+    use aymond::update::UpdatePathRoot;
+
+    // Named like `serde_json::Value` and `uuid::Uuid`, but resolved to their own paths
+    #[aymond(nested_item)]
+    struct Value {
+        note: String,
+    }
+
+    #[aymond(enum)]
+    enum Uuid {
+        Nil,
+    }
+
+    #[aymond(nested_item)]
+    struct Shipment {
+        value: Value,
+        label: Option<Value>,
+        id: Uuid,
+    }
+
+    let cond = ShipmentConditionPath::with_prefix(vec![PathSegment::Attr("s".into())]);
+    let (expr, names, _) = cond
+        .value()
+        .note()
+        .eq("fragile")
+        .and(cond.id().eq(Uuid::Nil))
+        .build()
+        .unwrap();
+    assert_eq!(expr, "(#n0.#n1.#n2 = :v3 AND #n4.#n5 = :v6)");
+    assert_eq!(names["#n2"], "note");
+    assert_eq!(names["#n5"], "id");
+
+    let upd = ShipmentUpdatePath::<aymond::update::Required>::with_prefix(vec![
+        aymond::update::PathSegment::Attr("s".into()),
+    ]);
+    let (expr, names, _) = upd
+        .label()
+        .set(None)
+        .and(upd.value().note().set("fragile"))
+        .build()
+        .unwrap();
+    assert_eq!(expr, "SET #u2.#u3.#u4 = :u5 REMOVE #u0.#u1");
+    assert_eq!(names["#u1"], "label");
+}
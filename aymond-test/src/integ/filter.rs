@@ -0,0 +1,88 @@
+#[tokio::test]
+async fn test() {
+    use aymond::{Aymond, prelude::*, shim::futures::StreamExt};
+
+    #[aymond(item, table)]
+    struct Car {
+        #[aymond(hash_key)]
+        make: String,
+        #[aymond(sort_key)]
+        model: String,
+        hp: i32,
+        #[aymond(gsi("by_class", hash_key))]
+        class: String,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CarTable::new(&aymond, "filter");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    for (make, model, hp) in [
+        ("Porsche", "911", 518),
+        ("Porsche", "944", 160),
+        ("Porsche", "Cayenne", 335),
+        ("Honda", "Civic", 150),
+        ("Honda", "NSX", 573),
+    ] {
+        table
+            .put()
+            .item(Car {
+                make: make.to_string(),
+                model: model.to_string(),
+                hp,
+                class: "car".to_string(),
+            })
+            .send()
+            .await
+            .expect("Failed to write");
+    }
+
+    // Filter placeholders are merged with the key condition's
+    let models: Vec<String> = table
+        .query()
+        .make("Porsche")
+        .model_begins_with("9")
+        .filter(|c| c.hp().gt(300))
+        .send()
+        .await
+        .map(|e| e.unwrap().model)
+        .collect()
+        .await;
+    assert_eq!(models, vec!["911".to_string()]);
+
+    let models: Vec<String> = table
+        .query()
+        .make("Porsche")
+        .model_ge("0")
+        .filter(|c| c.hp().gt(300).and(c.class().begins_with("c")))
+        .send()
+        .await
+        .map(|e| e.unwrap().model)
+        .collect()
+        .await;
+    assert_eq!(models, vec!["911".to_string(), "Cayenne".to_string()]);
+
+    let mut models: Vec<String> = table
+        .query_by_class()
+        .class("car")
+        .filter(|c| c.hp().lt(200))
+        .send()
+        .await
+        .map(|e| e.unwrap().model)
+        .collect()
+        .await;
+    models.sort();
+    assert_eq!(models, vec!["944".to_string(), "Civic".to_string()]);
+
+    let mut models: Vec<String> = table
+        .scan()
+        .filter(|c| c.hp().gt(500))
+        .send()
+        .await
+        .map(|e| e.unwrap().model)
+        .collect()
+        .await;
+    models.sort();
+    assert_eq!(models, vec!["911".to_string(), "NSX".to_string()]);
+}
@@ -0,0 +1,81 @@
+#[tokio::test]
+async fn test() {
+    use aymond::{Aymond, prelude::*};
+    use std::collections::{BTreeMap, HashMap};
+
+    #[aymond(item, table)]
+    struct Page {
+        #[aymond(hash_key)]
+        url: String,
+        labels: HashMap<String, String>,
+        counters: BTreeMap<String, i64>,
+        history: Option<HashMap<String, Vec<i32>>>,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = PageTable::new(&aymond, "map_attribute");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    let it_factory = || Page {
+        url: "/home".to_string(),
+        labels: HashMap::from([
+            ("env".to_string(), "prod".to_string()),
+            ("team".to_string(), "web".to_string()),
+        ]),
+        counters: BTreeMap::from([("clicks".to_string(), 3), ("views".to_string(), 10)]),
+        history: Some(HashMap::from([("2024".to_string(), vec![1, 2, 3])])),
+    };
+    table
+        .put()
+        .item(it_factory())
+        .send()
+        .await
+        .expect("Failed to write");
+
+    let get = table.get().url("/home").send().await.unwrap();
+    assert_eq!(get.unwrap(), it_factory());
+
+    // Condition on a map key
+    table
+        .put()
+        .item(it_factory())
+        .condition(|c| {
+            c.labels()
+                .key("env")
+                .eq("prod")
+                .and(c.history().key("2024").index(0).eq(1))
+        })
+        .send()
+        .await
+        .expect("Map key condition should succeed");
+
+    let result = table
+        .put()
+        .item(it_factory())
+        .condition(|c| c.labels().key("env").eq("staging"))
+        .send()
+        .await;
+    assert!(result.is_err(), "Map key condition should fail");
+
+    // Update map keys
+    table
+        .update()
+        .url("/home")
+        .expression(|e| {
+            e.counters()
+                .key("clicks")
+                .add(1)
+                .and(e.labels().key("owner").set("alice"))
+        })
+        .send()
+        .await
+        .expect("Update should succeed");
+
+    let updated = table.get().url("/home").send().await.unwrap().unwrap();
+    assert_eq!(updated.counters.get("clicks"), Some(&4));
+    assert_eq!(
+        updated.labels.get("owner").map(String::as_str),
+        Some("alice")
+    );
+}
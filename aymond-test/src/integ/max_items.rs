@@ -0,0 +1,102 @@
+#[tokio::test]
+async fn test() {
+    use aymond::{Aymond, error::RequestError, prelude::*, shim::futures::StreamExt};
+
+    #[aymond(item, table)]
+    struct Comment {
+        #[aymond(hash_key)]
+        post: String,
+        #[aymond(sort_key)]
+        id: i32,
+        likes: i32,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CommentTable::new(&aymond, "max_items");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    for id in 0..10 {
+        table
+            .put()
+            .item(Comment {
+                post: "p1".to_string(),
+                id,
+                likes: id % 3,
+            })
+            .send()
+            .await
+            .expect("Failed to write");
+    }
+
+    // Small pages still produce every item
+    let ids: Vec<i32> = table
+        .query()
+        .post("p1")
+        .id_ge(0)
+        .page_size(3)
+        .send()
+        .await
+        .map(|e| e.unwrap().id)
+        .collect()
+        .await;
+    assert_eq!(ids, (0..10).collect::<Vec<_>>());
+
+    let ids: Vec<i32> = table
+        .query()
+        .post("p1")
+        .id_ge(0)
+        .max_items(4)
+        .send()
+        .await
+        .map(|e| e.unwrap().id)
+        .collect()
+        .await;
+    assert_eq!(ids, vec![0, 1, 2, 3]);
+
+    // Keeps reading pages until enough items pass the filter
+    let ids: Vec<i32> = table
+        .query()
+        .post("p1")
+        .id_ge(0)
+        .filter(|c| c.likes().eq(0))
+        .max_items(3)
+        .send()
+        .await
+        .map(|e| e.unwrap().id)
+        .collect()
+        .await;
+    assert_eq!(ids, vec![0, 3, 6]);
+
+    let count = table.scan().max_items(5).send().await.count().await;
+    assert_eq!(count, 5);
+
+    let first = table
+        .query()
+        .post("p1")
+        .id_gt(4)
+        .first()
+        .await
+        .expect("query should succeed");
+    assert_eq!(first.map(|c| c.id), Some(5));
+
+    let first = table
+        .scan()
+        .filter(|c| c.likes().gt(5))
+        .first()
+        .await
+        .expect("scan should succeed");
+    assert_eq!(first, None);
+
+    let one = table
+        .query()
+        .post("p1")
+        .id_eq(7)
+        .one()
+        .await
+        .expect("query should succeed");
+    assert_eq!(one.map(|c| c.id), Some(7));
+
+    let res = table.query().post("p1").id_gt(7).one().await;
+    assert!(matches!(res, Err(RequestError::NotUnique)));
+}
@@ -3,14 +3,32 @@ mod batch_get;
 mod batch_write;
 mod binary_keys;
 mod condition_check;
+mod condition_functions;
+mod count;
+mod custom_converter;
+mod decode_error;
 mod delete_item;
+mod enum_attribute;
+mod feature_types;
+mod filter;
+mod map_attribute;
+mod max_items;
 mod no_sort_key;
 mod no_table;
 mod numeric_keys;
 mod option_attribute;
+mod pagination;
+mod projection;
+mod return_values;
+mod scalar_types;
 mod scan;
 mod secondary_indexes;
+mod serde_attribute;
 mod set_attribute;
+mod transparent_keys;
+mod tx_error;
+mod tx_get;
+mod tx_idempotency;
 mod update_item;
 mod version;
 
@@ -14,5 +14,5 @@ async fn test() {
         make: "Porsche".to_string(),
         hp: 518,
     };
-    let _: HashMap<_, _> = it.into();
+    let _: HashMap<_, _> = it.try_into().unwrap();
 }
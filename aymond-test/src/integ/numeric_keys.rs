@@ -42,7 +42,7 @@ async fn test() {
         .col_gt(10)
         .scan_index_forward(false)
         .consistent_read(true)
-        .limit(1)
+        .page_size(1)
         .send()
         .await;
     let query: Vec<Cell> = res.map(|e| e.ok().unwrap()).collect().await;
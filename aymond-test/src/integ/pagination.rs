@@ -0,0 +1,100 @@
+#[tokio::test]
+async fn test() {
+    use aymond::{
+        Aymond,
+        cursor::{Cursor, CursorError},
+        error::RequestError,
+        prelude::*,
+    };
+
+    #[aymond(item, table)]
+    struct Car {
+        #[aymond(hash_key)]
+        make: String,
+        #[aymond(sort_key)]
+        model: String,
+        #[aymond(gsi("by_hp", hash_key))]
+        hp: i32,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CarTable::new(&aymond, "pagination");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    for (i, model) in ["911", "924", "928", "944", "968"].iter().enumerate() {
+        table
+            .put()
+            .item(Car {
+                make: "Porsche".to_string(),
+                model: model.to_string(),
+                hp: i as i32,
+            })
+            .send()
+            .await
+            .expect("Failed to write");
+    }
+
+    let mut models = vec![];
+    let mut cursor = None;
+    let mut pages = 0;
+    loop {
+        let page = table
+            .query()
+            .make("Porsche")
+            .model_ge("0")
+            .send_page(2, cursor)
+            .await
+            .expect("query should succeed");
+        pages += 1;
+        models.extend(page.items.into_iter().map(|c| c.model));
+        match page.next {
+            Some(next) => cursor = Some(Cursor::from(next.to_string())),
+            None => break,
+        }
+    }
+    assert_eq!(models, vec!["911", "924", "928", "944", "968"]);
+    assert!(pages >= 3);
+
+    let page = table
+        .scan()
+        .send_page(3, None)
+        .await
+        .expect("scan should succeed");
+    assert_eq!(page.items.len(), 3);
+    let next = page.next.expect("scan should have more items");
+    let page = table
+        .scan()
+        .send_page(3, Some(next.clone()))
+        .await
+        .expect("scan should succeed");
+    assert_eq!(page.items.len(), 2);
+
+    // Table cursors lack the index's key attributes
+    let res = table.query_by_hp().hp(1).send_page(1, Some(next)).await;
+    assert!(matches!(
+        res,
+        Err(RequestError::Cursor(CursorError::KeyMismatch(_)))
+    ));
+
+    // Signed cursors can only be resumed by a client with the same key
+    let signing_aymond = aymond.clone().with_cursor_signing_key("secret");
+    let signed = CarTable::new(&signing_aymond, "pagination");
+    let next = signed
+        .scan()
+        .send_page(1, None)
+        .await
+        .expect("scan should succeed")
+        .next
+        .expect("scan should have more items");
+    signed
+        .scan()
+        .send_page(1, Some(next.clone()))
+        .await
+        .expect("signed cursor should resume");
+    let res = table.scan().send_page(1, Some(next)).await;
+    assert!(matches!(
+        res,
+        Err(RequestError::Cursor(CursorError::UnexpectedSignature))
+    ));
+}
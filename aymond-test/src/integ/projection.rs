@@ -0,0 +1,119 @@
+#[tokio::test]
+async fn test() {
+    use aymond::{Aymond, prelude::*, shim::futures::StreamExt};
+
+    #[aymond(item, table)]
+    struct Car {
+        #[aymond(hash_key)]
+        make: String,
+        #[aymond(sort_key)]
+        model: String,
+        #[aymond(attribute(name = "horsepower"))]
+        hp: i32,
+        description: String,
+        #[aymond(gsi("by_class", hash_key))]
+        class: String,
+    }
+
+    #[aymond(projection_of = Car)]
+    struct CarSummary {
+        model: String,
+        hp: i32,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CarTable::new(&aymond, "projection");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    for (model, hp) in [("911", 518), ("944", 160)] {
+        table
+            .put()
+            .item(Car {
+                make: "Porsche".to_string(),
+                model: model.to_string(),
+                hp,
+                description: "a long description".to_string(),
+                class: "sports".to_string(),
+            })
+            .send()
+            .await
+            .expect("Failed to write");
+    }
+
+    let summary = CarSummary {
+        model: "911".to_string(),
+        hp: 518,
+    };
+
+    let res = table
+        .get()
+        .make("Porsche")
+        .model("911")
+        .project::<CarSummary>()
+        .send()
+        .await
+        .expect("get should succeed");
+    assert_eq!(res, Some(summary));
+
+    let res: Vec<CarSummary> = table
+        .query()
+        .make("Porsche")
+        .model_begins_with("9")
+        .filter(|c| c.hp().gt(300))
+        .project::<CarSummary>()
+        .send()
+        .await
+        .map(|e| e.unwrap())
+        .collect()
+        .await;
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].hp, 518);
+
+    let mut res: Vec<CarSummary> = table
+        .query_by_class()
+        .class("sports")
+        .project::<CarSummary>()
+        .send()
+        .await
+        .map(|e| e.unwrap())
+        .collect()
+        .await;
+    res.sort_by(|a, b| a.model.cmp(&b.model));
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[1].hp, 160);
+
+    let res: Vec<CarSummary> = table
+        .scan()
+        .project::<CarSummary>()
+        .send()
+        .await
+        .map(|e| e.unwrap())
+        .collect()
+        .await;
+    assert_eq!(res.len(), 2);
+
+    let res = table
+        .batch_get()
+        .make_and_model("Porsche", "911")
+        .make_and_model("Porsche", "944")
+        .project::<CarSummary>()
+        .send()
+        .await
+        .expect("batch get should succeed");
+    assert_eq!(res.len(), 2);
+    assert!(res.iter().all(|r| r.is_ok()));
+
+    // Only the projected attributes are fetched
+    let res = table
+        .get()
+        .make("Porsche")
+        .model("911")
+        .project::<CarSummary>()
+        .raw(|r| r)
+        .await
+        .expect("get should succeed");
+    let item = res.item().expect("item should exist");
+    assert_eq!(item.len(), 2);
+    assert!(item.contains_key("horsepower"));
+}
@@ -0,0 +1,171 @@
+#[tokio::test]
+async fn test_return_values() {
+    use aymond::{Aymond, prelude::*};
+
+    #[aymond(item, table)]
+    struct Car {
+        #[aymond(hash_key)]
+        make: String,
+        #[aymond(sort_key)]
+        model: String,
+        hp: i32,
+        colour: Option<String>,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CarTable::new(&aymond, "return_values");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    // Nothing to return when putting a new item
+    let old = table
+        .put()
+        .item(Car {
+            make: "Porsche".to_string(),
+            model: "911".to_string(),
+            hp: 400,
+            colour: None,
+        })
+        .send_returning_old()
+        .await
+        .expect("put should succeed");
+    assert_eq!(old, None);
+
+    // Overwriting returns the previous item
+    let old = table
+        .put()
+        .item(Car {
+            make: "Porsche".to_string(),
+            model: "911".to_string(),
+            hp: 450,
+            colour: Some("red".to_string()),
+        })
+        .send_returning_old()
+        .await
+        .expect("put should succeed")
+        .expect("old item should be returned");
+    assert_eq!(old.hp, 400);
+    assert_eq!(old.colour, None);
+
+    let new = table
+        .update()
+        .make("Porsche")
+        .model("911")
+        .expression(|e| e.hp().add(50))
+        .send_returning(ReturnNew)
+        .await
+        .expect("update should succeed")
+        .expect("new item should be returned");
+    assert_eq!(new.hp, 500);
+    assert_eq!(new.colour, Some("red".to_string()));
+
+    let old = table
+        .update()
+        .make("Porsche")
+        .model("911")
+        .expression(|e| e.hp().set(520))
+        .send_returning(ReturnOld)
+        .await
+        .expect("update should succeed")
+        .expect("old item should be returned");
+    assert_eq!(old.hp, 500);
+
+    // Only the updated attributes come back
+    let updated = table
+        .update()
+        .make("Porsche")
+        .model("911")
+        .expression(|e| e.colour().set("blue"))
+        .send_returning(UpdatedNew)
+        .await
+        .expect("update should succeed")
+        .expect("updated attributes should be returned");
+    assert_eq!(
+        updated,
+        CarPartial {
+            colour: Some("blue".to_string()),
+            ..Default::default()
+        }
+    );
+
+    let updated = table
+        .update()
+        .make("Porsche")
+        .model("911")
+        .expression(|e| e.hp().set(530))
+        .send_returning(UpdatedOld)
+        .await
+        .expect("update should succeed")
+        .expect("updated attributes should be returned");
+    assert_eq!(updated.hp, Some(520));
+    assert_eq!(updated.make, None);
+
+    let old = table
+        .delete_item()
+        .make("Porsche")
+        .model("911")
+        .send_returning_old()
+        .await
+        .expect("delete should succeed")
+        .expect("deleted item should be returned");
+    assert_eq!(old.hp, 530);
+    assert_eq!(old.colour, Some("blue".to_string()));
+
+    let old = table
+        .delete_item()
+        .make("Porsche")
+        .model("911")
+        .send_returning_old()
+        .await
+        .expect("delete should succeed");
+    assert_eq!(old, None);
+}
+
+#[test]
+fn test_partial_nested_items() {
+    use aymond::prelude::*;
+    use aymond::shim::aws_sdk_dynamodb::types::AttributeValue;
+    use std::collections::HashMap;
+
+    #[aymond(item)]
+    struct Car {
+        #[aymond(hash_key)]
+        make: String,
+        engine: Engine,
+        trims: Vec<Engine>,
+        previous: Option<Engine>,
+    }
+
+    #[aymond(nested_item)]
+    struct Engine {
+        hp: i32,
+        cylinders: i32,
+    }
+
+    // What UPDATED_NEW returns after setting `engine.hp` and `trims[0].hp`
+    let hp = |n: &str| {
+        AttributeValue::M(HashMap::from([(
+            "hp".to_string(),
+            AttributeValue::N(n.to_string()),
+        )]))
+    };
+    let map = HashMap::from([
+        ("engine".to_string(), hp("450")),
+        ("trims".to_string(), AttributeValue::L(vec![hp("500")])),
+    ]);
+    let partial = CarPartial::try_from(&map).unwrap();
+    assert_eq!(
+        partial,
+        CarPartial {
+            engine: Some(EnginePartial {
+                hp: Some(450),
+                cylinders: None,
+            }),
+            trims: Some(vec![EnginePartial {
+                hp: Some(500),
+                cylinders: None,
+            }]),
+            ..Default::default()
+        }
+    );
+}
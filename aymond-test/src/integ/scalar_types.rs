@@ -0,0 +1,137 @@
+#[tokio::test]
+async fn test() {
+    use aymond::{Aymond, prelude::*};
+    use std::collections::{BTreeSet, HashSet};
+
+    #[aymond(item, table)]
+    struct Reading {
+        #[aymond(hash_key)]
+        sensor: String,
+        #[aymond(sort_key)]
+        at: f64,
+        calibrated: bool,
+        celsius: f32,
+        ports: HashSet<u32>,
+        samples: BTreeSet<i64>,
+        alarm: Option<bool>,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = ReadingTable::new(&aymond, "scalar_types");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    let it_factory = || Reading {
+        sensor: "s1".to_string(),
+        at: 1700000000.25,
+        calibrated: true,
+        celsius: 21.5,
+        ports: HashSet::from([80, 443]),
+        samples: BTreeSet::from([-3, 7, 12]),
+        alarm: Some(false),
+    };
+    table
+        .put()
+        .item(it_factory())
+        .send()
+        .await
+        .expect("Failed to write");
+
+    let get = table
+        .get()
+        .sensor("s1")
+        .at(1700000000.25)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(get.unwrap(), it_factory());
+
+    // Conditions on bool, float and number set paths
+    table
+        .put()
+        .item(it_factory())
+        .condition(|c| {
+            c.calibrated()
+                .eq(true)
+                .and(c.celsius().gt(20.0f32))
+                .and(c.ports().contains(443u32))
+                .and(c.samples().contains(7i64))
+        })
+        .send()
+        .await
+        .expect("Condition should succeed");
+
+    let result = table
+        .put()
+        .item(it_factory())
+        .condition(|c| c.alarm().eq(true))
+        .send()
+        .await;
+    assert!(result.is_err(), "Condition on false bool should fail");
+
+    // Update float, bool and number sets
+    table
+        .update()
+        .sensor("s1")
+        .at(1700000000.25)
+        .expression(|e| {
+            e.celsius()
+                .add(1.5f32)
+                .and(e.calibrated().set(false))
+                .and(e.ports().delete(80u32))
+                .and(e.samples().delete_set(HashSet::from([-3i64, 12])))
+        })
+        .send()
+        .await
+        .expect("Update should succeed");
+
+    let updated = table
+        .get()
+        .sensor("s1")
+        .at(1700000000.25)
+        .send()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.celsius, 23.0);
+    assert!(!updated.calibrated);
+    assert_eq!(updated.ports, HashSet::from([443]));
+    assert_eq!(updated.samples, BTreeSet::from([7]));
+}
+
+#[test]
+fn test_non_finite_floats() {
+    use aymond::prelude::*;
+    use aymond::shim::aws_sdk_dynamodb::types::AttributeValue;
+    use std::collections::HashMap;
+
+    #[aymond(item)]
+    struct Reading {
+        #[aymond(hash_key)]
+        sensor: String,
+        celsius: f32,
+        history: Vec<f64>,
+    }
+
+    let reading = || Reading {
+        sensor: "s1".to_string(),
+        celsius: 21.5,
+        history: vec![20.0, 21.0],
+    };
+    let map: HashMap<String, AttributeValue> = reading().try_into().unwrap();
+    assert_eq!(map["celsius"], AttributeValue::N("21.5".to_string()));
+
+    let err = HashMap::try_from(Reading {
+        celsius: f32::NAN,
+        ..reading()
+    })
+    .unwrap_err();
+    assert_eq!(err.path, "celsius");
+
+    let err = HashMap::try_from(Reading {
+        history: vec![20.0, f64::INFINITY],
+        ..reading()
+    })
+    .unwrap_err();
+    assert_eq!(err.path, "history[1]");
+}
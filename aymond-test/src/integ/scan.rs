@@ -44,3 +44,111 @@ async fn test() {
 
     assert_eq!(results, items);
 }
+
+#[tokio::test]
+async fn test_parallel() {
+    use aymond::{Aymond, prelude::*, shim::futures::StreamExt};
+
+    #[aymond(item, table)]
+    struct Widget {
+        #[aymond(hash_key)]
+        id: String,
+        value: i32,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = WidgetTable::new(&aymond, "scan_parallel");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    for i in 0..20 {
+        table
+            .put()
+            .item(Widget {
+                id: format!("w{i:02}"),
+                value: i,
+            })
+            .send()
+            .await
+            .expect("Failed to write");
+    }
+
+    let mut values: Vec<i32> = table
+        .scan()
+        .parallel(4, 2)
+        .send()
+        .await
+        .map(|e| e.unwrap().value)
+        .collect()
+        .await;
+    values.sort();
+    assert_eq!(values, (0..20).collect::<Vec<_>>());
+
+    // Filters apply to every segment
+    let segments = table
+        .scan()
+        .filter(|c| c.value().ge(10))
+        .parallel(3, 3)
+        .send_segments();
+    assert_eq!(segments.len(), 3);
+    let mut values = vec![];
+    for segment in segments {
+        let mut segment_values: Vec<i32> = segment.map(|e| e.unwrap().value).collect().await;
+        values.append(&mut segment_values);
+    }
+    values.sort();
+    assert_eq!(values, (10..20).collect::<Vec<_>>());
+
+    // Errors carry the segment that failed
+    let missing = WidgetTable::new(&aymond, "scan_parallel_missing");
+    missing.delete(false).await.expect("Failed to delete");
+    let mut errors: Vec<i32> = missing
+        .scan()
+        .parallel(2, 2)
+        .send()
+        .await
+        .map(|e| e.unwrap_err().segment)
+        .collect()
+        .await;
+    errors.sort();
+    assert_eq!(errors, vec![0, 1]);
+}
+
+#[tokio::test]
+async fn test_parallel_segment_limits() {
+    use aymond::error::RequestError;
+    use aymond::{Aymond, prelude::*, shim::futures::StreamExt};
+
+    #[aymond(item, table)]
+    struct Widget {
+        #[aymond(hash_key)]
+        id: String,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = WidgetTable::new(&aymond, "scan_parallel");
+
+    // Fails before sending, so this doesn't need DynamoDB
+    for total_segments in [0, -1, 1_000_001] {
+        let results: Vec<_> = table
+            .scan()
+            .parallel(total_segments, 4)
+            .send()
+            .await
+            .collect()
+            .await;
+        assert_eq!(results.len(), 1);
+        let err = results.into_iter().next().unwrap().unwrap_err();
+        assert_eq!(err.segment, 0);
+        let RequestError::SdkError(sdk) = err.error else {
+            panic!("expected a construction failure, got {err}");
+        };
+        let source = std::error::Error::source(sdk.as_ref()).unwrap();
+        assert_eq!(
+            source.to_string(),
+            format!(
+                "failed to encode value: total_segments must be between 1 and 1000000, got {total_segments}"
+            )
+        );
+    }
+}
@@ -0,0 +1,141 @@
+#[tokio::test]
+async fn test() {
+    use aymond::shim::aws_sdk_dynamodb::types::AttributeValue;
+    use aymond::{Aymond, prelude::*};
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    // Stand-ins for types from other crates that can't be annotated with #[aymond(...)]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Settings {
+        theme: String,
+        shortcuts: Vec<String>,
+        volume: Option<f64>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "kind")]
+    enum Plan {
+        Free,
+        Paid { seats: u32 },
+    }
+
+    #[aymond(item, table)]
+    struct Account {
+        #[aymond(hash_key)]
+        id: String,
+        #[aymond(serde)]
+        settings: Settings,
+        #[aymond(json)]
+        plan: Plan,
+        #[aymond(serde)]
+        previous: Option<Plan>,
+    }
+
+    let it_factory = || Account {
+        id: "a-1".to_string(),
+        settings: Settings {
+            theme: "dark".to_string(),
+            shortcuts: vec!["ctrl+k".to_string()],
+            volume: None,
+        },
+        plan: Plan::Paid { seats: 5 },
+        previous: Some(Plan::Free),
+    };
+
+    // Native document vs JSON string
+    let map: HashMap<String, AttributeValue> = it_factory().try_into().unwrap();
+    let settings = map["settings"].as_m().unwrap();
+    assert_eq!(settings["theme"], AttributeValue::S("dark".to_string()));
+    assert!(settings["shortcuts"].is_l());
+    assert_eq!(settings["volume"], AttributeValue::Null(true));
+    assert_eq!(
+        map["plan"],
+        AttributeValue::S(r#"{"kind":"Paid","seats":5}"#.to_string())
+    );
+    assert_eq!(Account::try_from(&map).unwrap(), it_factory());
+
+    // Dynamic conversion
+    let item = aymond::serde::to_item(&it_factory().settings).unwrap();
+    assert_eq!(item["theme"], AttributeValue::S("dark".to_string()));
+    let settings: Settings = aymond::serde::from_item(&item).unwrap();
+    assert_eq!(settings, it_factory().settings);
+    assert!(aymond::serde::to_item(&5).is_err());
+
+    let mut item = item;
+    item.insert("theme".to_string(), AttributeValue::N("1".to_string()));
+    assert!(aymond::serde::from_item::<Settings>(&item).is_err());
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = AccountTable::new(&aymond, "serde_attribute");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    table
+        .put()
+        .item(it_factory())
+        .send()
+        .await
+        .expect("Failed to write");
+
+    let get = table.get().id("a-1").send().await.unwrap();
+    assert_eq!(get.unwrap(), it_factory());
+
+    table
+        .update()
+        .id("a-1")
+        .expression(|e| {
+            e.plan()
+                .set(Plan::Free)
+                .and(e.previous().set(Plan::Paid { seats: 5 }))
+        })
+        .condition(|c| c.plan().eq(Plan::Paid { seats: 5 }))
+        .send()
+        .await
+        .expect("Update should succeed");
+
+    let updated = table.get().id("a-1").send().await.unwrap().unwrap();
+    assert_eq!(updated.plan, Plan::Free);
+    assert_eq!(updated.previous, Some(Plan::Paid { seats: 5 }));
+}
+
+#[test]
+fn test_unserializable_values() {
+    use aymond::prelude::*;
+    use aymond::shim::aws_sdk_dynamodb::types::AttributeValue;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[aymond(item)]
+    struct Ledger {
+        #[aymond(hash_key)]
+        id: String,
+        #[aymond(serde)]
+        balance: u128,
+        #[aymond(json)]
+        grid: BTreeMap<(u8, u8), String>,
+    }
+
+    let ledger = || Ledger {
+        id: "l-1".to_string(),
+        balance: u64::MAX as u128,
+        grid: BTreeMap::new(),
+    };
+    let map: HashMap<String, AttributeValue> = ledger().try_into().unwrap();
+    assert_eq!(map["balance"], AttributeValue::N(u64::MAX.to_string()));
+
+    // Beyond what serde_json::Value holds
+    let err = HashMap::try_from(Ledger {
+        balance: u64::MAX as u128 + 1,
+        ..ledger()
+    })
+    .unwrap_err();
+    assert_eq!(err.path, "balance");
+
+    // JSON object keys must be strings
+    let err = HashMap::try_from(Ledger {
+        grid: BTreeMap::from([((0, 0), "x".to_string())]),
+        ..ledger()
+    })
+    .unwrap_err();
+    assert_eq!(err.path, "grid");
+}
@@ -89,4 +89,32 @@ async fn test() {
     let updated = table.get().pk("row1").send().await.unwrap().unwrap();
     assert_eq!(updated.labels, HashSet::from(["rust".to_string()]));
     assert_eq!(updated.blobs, HashSet::from([vec![4u8, 5, 6]]));
+
+    // Update expression: add one and many items to sets, creating missing ones
+    table
+        .update()
+        .pk("row1")
+        .expression(|e| {
+            e.labels()
+                .insert("systems")
+                .and(e.blobs().insert(vec![7u8]))
+                .and(
+                    e.extra()
+                        .insert_all(HashSet::from(["a".to_string(), "b".to_string()])),
+                )
+        })
+        .send()
+        .await
+        .expect("Failed to update");
+
+    let updated = table.get().pk("row1").send().await.unwrap().unwrap();
+    assert_eq!(
+        updated.labels,
+        HashSet::from(["rust".to_string(), "systems".to_string()])
+    );
+    assert_eq!(updated.blobs, HashSet::from([vec![4u8, 5, 6], vec![7u8]]));
+    assert_eq!(
+        updated.extra,
+        Some(HashSet::from(["a".to_string(), "b".to_string()]))
+    );
 }
@@ -0,0 +1,85 @@
+#[tokio::test]
+async fn test() {
+    use aymond::{Aymond, prelude::*, shim::futures::StreamExt};
+
+    #[aymond(transparent)]
+    struct UserId(String);
+
+    #[aymond(transparent)]
+    struct OrderNo(u64);
+
+    #[aymond(transparent)]
+    struct Region(UserId);
+
+    #[aymond(item, table)]
+    struct Order {
+        #[aymond(hash_key)]
+        user_id: UserId,
+
+        #[aymond(sort_key)]
+        order_no: OrderNo,
+
+        #[aymond(gsi("by-region", hash_key))]
+        region: Region,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = OrderTable::new(&aymond, "transparent_keys");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    let it_factory = |n: u64| Order {
+        user_id: UserId("u-1".to_string()),
+        order_no: OrderNo(n),
+        region: Region(UserId("eu".to_string())),
+    };
+    for n in 1..=3 {
+        table
+            .put()
+            .item(it_factory(n))
+            .send()
+            .await
+            .expect("Failed to write");
+    }
+
+    let get = table
+        .get()
+        .user_id(UserId("u-1".to_string()))
+        .order_no(OrderNo(2))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(get.unwrap(), it_factory(2));
+
+    let results: Vec<Order> = table
+        .query()
+        .user_id(UserId("u-1".to_string()))
+        .order_no_gt(OrderNo(1))
+        .send()
+        .await
+        .map(|e| e.ok().unwrap())
+        .collect()
+        .await;
+    assert_eq!(results, vec![it_factory(2), it_factory(3)]);
+
+    let results: Vec<Order> = table
+        .query_by_region()
+        .region(Region(UserId("eu".to_string())))
+        .send()
+        .await
+        .map(|e| e.ok().unwrap())
+        .collect()
+        .await;
+    assert_eq!(results.len(), 3);
+
+    // Newtypes are compared and set as their inner value
+    table
+        .update()
+        .user_id(UserId("u-1".to_string()))
+        .order_no(OrderNo(1))
+        .expression(|e| e.region().set(Region(UserId("us".to_string()))))
+        .condition(|c| c.region().eq(Region(UserId("eu".to_string()))))
+        .send()
+        .await
+        .expect("Update should succeed");
+}
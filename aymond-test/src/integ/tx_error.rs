@@ -0,0 +1,92 @@
+#[tokio::test]
+async fn test_tx_error_cancellation_reasons() {
+    use aymond::{
+        Aymond,
+        error::{CancellationCode, TxError},
+        prelude::*,
+        shim::aws_sdk_dynamodb::types::AttributeValue,
+    };
+    use std::collections::HashMap;
+
+    #[aymond(item, table)]
+    #[derive(Clone)]
+    struct Car {
+        #[aymond(hash_key)]
+        make: String,
+        #[aymond(sort_key)]
+        model: String,
+        hp: i16,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CarTable::new(&aymond, "tx_error");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    let porsche = Car {
+        make: "Porsche".to_string(),
+        model: "911".to_string(),
+        hp: 518,
+    };
+    table
+        .put()
+        .item(porsche.clone())
+        .send()
+        .await
+        .expect("Failed to seed");
+
+    let err = aymond
+        .tx()
+        .put(table.put().item(Car {
+            make: "BMW".to_string(),
+            model: "M3".to_string(),
+            hp: 473,
+        }))
+        .label("create bmw")
+        .condition_check(
+            table
+                .condition_check()
+                .make("Porsche")
+                .model("911")
+                .condition(|c| c.hp().gt(600i16)),
+        )
+        .label("porsche is fast")
+        .return_old_on_condition_failure()
+        .send()
+        .await
+        .expect_err("Condition check should fail");
+
+    let TxError::Canceled(reasons) = &err else {
+        panic!("Expected a canceled transaction, got {err:?}");
+    };
+    assert_eq!(reasons.len(), 2);
+
+    assert_eq!(reasons[0].label.as_deref(), Some("create bmw"));
+    assert_eq!(reasons[0].table, "tx_error");
+    assert_eq!(
+        reasons[0].key,
+        HashMap::from([
+            ("make".to_string(), AttributeValue::S("BMW".to_string())),
+            ("model".to_string(), AttributeValue::S("M3".to_string())),
+        ])
+    );
+    assert_eq!(reasons[0].code, CancellationCode::None);
+    assert_eq!(reasons[0].item, None);
+
+    let failures: Vec<_> = err.failures().collect();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].label.as_deref(), Some("porsche is fast"));
+    assert_eq!(failures[0].code, CancellationCode::ConditionalCheckFailed);
+    assert_eq!(
+        failures[0].key,
+        HashMap::from([
+            ("make".to_string(), AttributeValue::S("Porsche".to_string())),
+            ("model".to_string(), AttributeValue::S("911".to_string())),
+        ])
+    );
+    let old = failures[0]
+        .item
+        .as_ref()
+        .expect("Old item should be returned");
+    assert_eq!(Car::try_from(old).unwrap(), porsche);
+}
@@ -0,0 +1,85 @@
+#[tokio::test]
+async fn test_tx_get_across_tables() {
+    use aymond::{Aymond, prelude::*};
+
+    #[aymond(item, table)]
+    struct Car {
+        #[aymond(hash_key)]
+        make: String,
+        #[aymond(sort_key)]
+        model: String,
+        hp: i16,
+    }
+
+    #[aymond(projection_of = Car)]
+    struct CarPower {
+        hp: i16,
+    }
+
+    #[aymond(item, table)]
+    struct Person {
+        #[aymond(hash_key)]
+        name: String,
+        age: i32,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let cars = CarTable::new(&aymond, "tx_get_cars");
+    let people = PersonTable::new(&aymond, "tx_get_people");
+    cars.delete(false).await.expect("Failed to delete");
+    cars.create(false).await.expect("Failed to create");
+    people.delete(false).await.expect("Failed to delete");
+    people.create(false).await.expect("Failed to create");
+
+    cars.put()
+        .item(Car {
+            make: "Porsche".to_string(),
+            model: "911".to_string(),
+            hp: 518,
+        })
+        .send()
+        .await
+        .expect("Failed to put car");
+    people
+        .put()
+        .item(Person {
+            name: "Ferdinand".to_string(),
+            age: 75,
+        })
+        .send()
+        .await
+        .expect("Failed to put person");
+
+    let (car, person, missing, power) = aymond
+        .tx_get()
+        .get(cars.get().make("Porsche").model("911"))
+        .get(people.get().name("Ferdinand"))
+        .get(people.get().name("Nobody"))
+        .get(
+            cars.get()
+                .make("Porsche")
+                .model("911")
+                .project::<CarPower>(),
+        )
+        .send()
+        .await
+        .expect("Failed to tx_get");
+
+    assert_eq!(
+        car,
+        Some(Car {
+            make: "Porsche".to_string(),
+            model: "911".to_string(),
+            hp: 518,
+        })
+    );
+    assert_eq!(
+        person,
+        Some(Person {
+            name: "Ferdinand".to_string(),
+            age: 75,
+        })
+    );
+    assert_eq!(missing, None);
+    assert_eq!(power, Some(CarPower { hp: 518 }));
+}
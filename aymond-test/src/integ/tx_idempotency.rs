@@ -0,0 +1,64 @@
+#[tokio::test]
+async fn test_tx_idempotency_token() {
+    use aymond::{Aymond, error::TxError, prelude::*};
+
+    #[aymond(item, table)]
+    struct Counter {
+        #[aymond(hash_key)]
+        id: String,
+        value: i32,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CounterTable::new(&aymond, "tx_idempotency");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    table
+        .put()
+        .item(Counter {
+            id: "c".to_string(),
+            value: 0,
+        })
+        .send()
+        .await
+        .expect("Failed to seed");
+
+    let increment = |token: &str, by: i32| {
+        aymond
+            .tx()
+            .update(table.update().id("c").expression(|e| e.value().add(by)))
+            .idempotency_token(token)
+    };
+
+    increment("increment-once", 1)
+        .send()
+        .await
+        .expect("First send should succeed");
+    increment("increment-once", 1)
+        .send()
+        .await
+        .expect("Resending with the same token should succeed");
+
+    let counter = table.get().id("c").send().await.unwrap().unwrap();
+    assert_eq!(counter.value, 1, "Transaction should only apply once");
+
+    let err = increment("increment-once", 2)
+        .send()
+        .await
+        .expect_err("Reusing the token for another transaction should fail");
+    assert!(
+        matches!(err, TxError::IdempotentParameterMismatch { .. }),
+        "Unexpected error: {err:?}"
+    );
+
+    aymond
+        .tx()
+        .update(table.update().id("c").expression(|e| e.value().add(1)))
+        .auto_idempotency_token()
+        .send()
+        .await
+        .expect("Send with a generated token should succeed");
+    let counter = table.get().id("c").send().await.unwrap().unwrap();
+    assert_eq!(counter.value, 2);
+}
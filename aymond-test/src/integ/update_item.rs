@@ -152,3 +152,221 @@ async fn test_transaction_update_expression() {
         .expect("Item should exist");
     assert_eq!(res.value, 42);
 }
+
+#[tokio::test]
+async fn test_update_item_functions_and_arithmetic() {
+    use aymond::{Aymond, prelude::*};
+
+    #[aymond(item, table)]
+    struct Order {
+        #[aymond(hash_key)]
+        id: String,
+        created_at: Option<i64>,
+        stock: i32,
+        subtotal: i32,
+        total: Option<i32>,
+        tags: Option<Vec<String>>,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = OrderTable::new(&aymond, "update_item_functions");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    table
+        .put()
+        .item(Order {
+            id: "o-1".to_string(),
+            created_at: None,
+            stock: 10,
+            subtotal: 95,
+            total: None,
+            tags: None,
+        })
+        .send()
+        .await
+        .expect("Failed to write");
+
+    let update = |created_at: i64| {
+        table
+            .update()
+            .id("o-1")
+            .expression(move |e| {
+                e.created_at()
+                    .set_if_not_exists(created_at)
+                    .and(e.stock().subtract(3))
+                    .and(e.total().copy_from_plus(e.subtotal(), 5))
+            })
+            .send()
+    };
+    update(1000).await.expect("First update should succeed");
+    update(2000).await.expect("Second update should succeed");
+
+    table
+        .update()
+        .id("o-1")
+        .expression(|e| e.tags().append(vec!["b".to_string(), "c".to_string()]))
+        .send()
+        .await
+        .expect("Append to missing list should succeed");
+    table
+        .update()
+        .id("o-1")
+        .expression(|e| e.tags().prepend(vec!["a".to_string()]))
+        .send()
+        .await
+        .expect("Prepend should succeed");
+
+    let res = table
+        .get()
+        .id("o-1")
+        .send()
+        .await
+        .expect("Get should succeed")
+        .expect("Item should exist");
+    assert_eq!(res.created_at, Some(1000));
+    assert_eq!(res.stock, 4);
+    assert_eq!(res.total, Some(100));
+    assert_eq!(
+        res.tags,
+        Some(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+    );
+}
+
+#[tokio::test]
+async fn test_update_item_add_initialises_missing_number() {
+    use aymond::{Aymond, prelude::*};
+
+    #[aymond(item, table)]
+    struct Counter {
+        #[aymond(hash_key)]
+        id: String,
+        views: Option<i64>,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CounterTable::new(&aymond, "update_item_add_missing");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    for _ in 0..2 {
+        table
+            .update()
+            .id("page")
+            .expression(|e| e.views().add(5))
+            .send()
+            .await
+            .expect("Update should succeed");
+    }
+
+    let res = table.get().id("page").send().await.unwrap().unwrap();
+    assert_eq!(res.views, Some(10));
+}
+
+#[tokio::test]
+async fn test_update_item_whole_nested_items_and_optional_fields() {
+    use aymond::{Aymond, prelude::*};
+
+    #[aymond(nested_item)]
+    struct Address {
+        city: String,
+        zip: Option<String>,
+    }
+
+    #[aymond(item, table)]
+    struct Customer {
+        #[aymond(hash_key)]
+        id: String,
+        home: Address,
+        work: Option<Address>,
+        previous: Vec<Address>,
+        nickname: Option<String>,
+        tags: Vec<String>,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CustomerTable::new(&aymond, "update_item_nested_values");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    table
+        .put()
+        .item(Customer {
+            id: "c-1".to_string(),
+            home: Address {
+                city: "Seattle".to_string(),
+                zip: Some("98101".to_string()),
+            },
+            work: Some(Address {
+                city: "Redmond".to_string(),
+                zip: None,
+            }),
+            previous: vec![],
+            nickname: Some("Al".to_string()),
+            tags: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        })
+        .send()
+        .await
+        .expect("Failed to write");
+
+    table
+        .update()
+        .id("c-1")
+        .expression(|e| {
+            e.home()
+                .set(Address {
+                    city: "Portland".to_string(),
+                    zip: None,
+                })
+                .and(e.previous().append(vec![Address {
+                    city: "Seattle".to_string(),
+                    zip: Some("98101".to_string()),
+                }]))
+                .and(e.work().set(None))
+                .and(e.nickname().set(None))
+                .and(e.tags().index(1).remove())
+        })
+        .send()
+        .await
+        .expect("Update should succeed");
+
+    let res = table.get().id("c-1").send().await.unwrap().unwrap();
+    assert_eq!(
+        res.home,
+        Address {
+            city: "Portland".to_string(),
+            zip: None,
+        }
+    );
+    assert_eq!(res.work, None);
+    assert_eq!(
+        res.previous,
+        vec![Address {
+            city: "Seattle".to_string(),
+            zip: Some("98101".to_string()),
+        }]
+    );
+    assert_eq!(res.nickname, None);
+    assert_eq!(res.tags, vec!["a".to_string(), "c".to_string()]);
+
+    table
+        .update()
+        .id("c-1")
+        .expression(|e| {
+            e.work()
+                .set(Address {
+                    city: "Bellevue".to_string(),
+                    zip: None,
+                })
+                .and(e.nickname().set("Ally"))
+                .and(e.home().zip().set(Some("97201".to_string())))
+        })
+        .send()
+        .await
+        .expect("Update should succeed");
+
+    let res = table.get().id("c-1").send().await.unwrap().unwrap();
+    assert_eq!(res.work.map(|a| a.city), Some("Bellevue".to_string()));
+    assert_eq!(res.nickname, Some("Ally".to_string()));
+    assert_eq!(res.home.zip, Some("97201".to_string()));
+}
@@ -586,3 +586,204 @@ async fn test_must_not_exist_on_non_versioned_item() {
         "must_not_exist should fail when item already exists"
     );
 }
+
+#[tokio::test]
+async fn test_version_update_expected_version() {
+    use aymond::shim::aws_sdk_dynamodb::error::SdkError;
+    use aymond::{Aymond, prelude::*};
+
+    #[aymond(item, table)]
+    struct Counter {
+        #[aymond(hash_key)]
+        id: String,
+        count: i32,
+        #[aymond(attribute(version))]
+        ver: i64,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CounterTable::new(&aymond, "version_update");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    // Version-zero update creates the item, DB gets ver=1
+    table
+        .update()
+        .id("c1")
+        .expected_version(0)
+        .expression(|e| e.count().set(1))
+        .send()
+        .await
+        .expect("Version-zero update should create the item");
+    let get = table.get().id("c1").send().await.unwrap().unwrap();
+    assert_eq!(get.ver, 1);
+
+    // Update with correct ver=1, DB gets ver=2
+    table
+        .update()
+        .id("c1")
+        .expected_version(1)
+        .expression(|e| e.count().set(5))
+        .send()
+        .await
+        .expect("Update with correct version should succeed");
+    let get = table.get().id("c1").send().await.unwrap().unwrap();
+    assert_eq!(get.ver, 2);
+    assert_eq!(get.count, 5);
+
+    // Update with stale ver=1 — should fail
+    let result = table
+        .update()
+        .id("c1")
+        .expected_version(1)
+        .expression(|e| e.count().set(99))
+        .send()
+        .await;
+    assert!(result.is_err(), "Update with stale version should fail");
+
+    // Update without an expected version is rejected before sending
+    let result = table
+        .update()
+        .id("c1")
+        .expression(|e| e.count().add(1))
+        .send()
+        .await;
+    assert!(
+        matches!(result, Err(SdkError::ConstructionFailure(_))),
+        "Update without an expected version should fail"
+    );
+    let get = table.get().id("c1").send().await.unwrap().unwrap();
+    assert_eq!(get.ver, 2);
+    assert_eq!(get.count, 5);
+}
+
+#[tokio::test]
+async fn test_version_update_disable_versioning() {
+    use aymond::{Aymond, prelude::*};
+
+    #[aymond(item, table)]
+    struct Counter {
+        #[aymond(hash_key)]
+        id: String,
+        count: i32,
+        #[aymond(attribute(version))]
+        ver: i64,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CounterTable::new(&aymond, "version_update_disable");
+    table.delete(false).await.expect("Failed to delete");
+    table.create(false).await.expect("Failed to create");
+
+    // Insert ver=0, DB gets ver=1
+    let c = Counter {
+        id: "c1".to_string(),
+        count: 0,
+        ver: 0,
+    };
+    table
+        .put()
+        .item(c)
+        .send()
+        .await
+        .expect("put should succeed");
+
+    // Stale expected version is ignored and the version left alone
+    table
+        .update()
+        .id("c1")
+        .expected_version(99)
+        .expression(|e| e.count().set(7))
+        .condition(|c| c.disable_versioning())
+        .send()
+        .await
+        .expect("Update with disable_versioning should bypass version check");
+    let get = table.get().id("c1").send().await.unwrap().unwrap();
+    assert_eq!(get.ver, 1);
+    assert_eq!(get.count, 7);
+}
+
+#[test]
+fn test_version_update_rejects_version_changes() {
+    use aymond::shim::aws_sdk_dynamodb::types::Update;
+    use aymond::{Aymond, prelude::*};
+
+    #[aymond(item, table)]
+    struct Counter {
+        #[aymond(hash_key)]
+        id: String,
+        count: i32,
+        #[aymond(attribute(version))]
+        ver: i64,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CounterTable::new(&aymond, "version_update_rejects");
+
+    // Setting the version would overlap with the automatic `ADD ver 1`
+    let err = Update::try_from(
+        table
+            .update()
+            .id("c1")
+            .expected_version(1)
+            .expression(|e| e.count().set(1).and(e.ver().set(5))),
+    )
+    .unwrap_err();
+    assert_eq!(err.path, "ver");
+    assert!(err.reason.contains("incremented automatically"));
+
+    let update = Update::try_from(
+        table
+            .update()
+            .id("c1")
+            .expression(|e| e.ver().set(5))
+            .condition(|c| c.disable_versioning()),
+    )
+    .unwrap();
+    assert_eq!(update.update_expression, "SET #u0 = :u1");
+
+    let update = Update::try_from(
+        table
+            .update()
+            .id("c1")
+            .expected_version(1)
+            .expression(|e| e.count().add(1)),
+    )
+    .unwrap();
+    assert_eq!(update.update_expression, "ADD #u0 :u1, #u2 :u3");
+}
+
+#[test]
+fn test_version_update_requires_expected_version() {
+    use aymond::shim::aws_sdk_dynamodb::types::Update;
+    use aymond::{Aymond, prelude::*};
+
+    #[aymond(item, table)]
+    struct Counter {
+        #[aymond(hash_key)]
+        id: String,
+        count: i32,
+        #[aymond(attribute(version))]
+        ver: i64,
+    }
+
+    let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+    let table = CounterTable::new(&aymond, "version_update_requires");
+
+    // Bumping the version without checking it would be an unconditional write
+    let err =
+        Update::try_from(table.update().id("c1").expression(|e| e.count().add(1))).unwrap_err();
+    assert_eq!(err.path, "ver");
+    assert!(err.reason.contains("expected version is required"));
+
+    let update = Update::try_from(
+        table
+            .update()
+            .id("c1")
+            .expression(|e| e.count().add(1))
+            .condition(|c| c.disable_versioning()),
+    )
+    .unwrap();
+    assert_eq!(update.update_expression, "ADD #u0 :u1");
+    assert_eq!(update.condition_expression, None);
+}
@@ -58,7 +58,7 @@ async fn main() {
         .send()
         .await;
 
-    let _: Vec<Car> = table
+    let _: Vec<Result<Car, _>> = table
         .batch_get()
         .make_and_model("Porsche", "911")
         .make_and_model("Honda", "Civic")
@@ -0,0 +1,15 @@
+use aymond::prelude::*;
+
+#[aymond(enum)]
+enum Owner {
+    Person { name: String },
+    Company { registry: String },
+}
+
+#[aymond(item, table)]
+struct Car {
+    #[aymond(hash_key)]
+    owner: Owner,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use aymond::prelude::*;
+
+#[aymond(item, table)]
+struct Car {
+    #[aymond(hash_key)]
+    make: String,
+    hp: i16,
+}
+
+#[aymond(projection_of = Car)]
+struct CarPower {
+    hp: i32,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use aymond::prelude::*;
+
+#[aymond(item, table)]
+struct Car {
+    #[aymond(hash_key)]
+    make: String,
+    hp: i16,
+}
+
+#[aymond(transparent, projection_of = Car)]
+struct Horsepower(i16);
+
+fn main() {}
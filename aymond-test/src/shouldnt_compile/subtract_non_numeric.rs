@@ -0,0 +1,17 @@
+use aymond::prelude::*;
+
+#[aymond(item, table)]
+struct Car {
+    #[aymond(hash_key)]
+    make: String,
+    model: String,
+}
+
+fn update(table: &CarTable) {
+    let _ = table
+        .update()
+        .make("Porsche")
+        .expression(|e| e.model().subtract("911"));
+}
+
+fn main() {}
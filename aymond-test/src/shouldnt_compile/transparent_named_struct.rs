@@ -0,0 +1,8 @@
+use aymond::prelude::*;
+
+#[aymond(transparent)]
+struct UserId {
+    id: String,
+}
+
+fn main() {}
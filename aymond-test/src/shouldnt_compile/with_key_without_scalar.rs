@@ -0,0 +1,22 @@
+use aymond::prelude::*;
+
+mod ip_string {
+    use aymond::shim::aws_sdk_dynamodb::types::AttributeValue;
+    use std::net::IpAddr;
+
+    pub fn to_attribute_value(v: &IpAddr) -> AttributeValue {
+        AttributeValue::S(v.to_string())
+    }
+
+    pub fn from_attribute_value(v: &AttributeValue) -> Result<IpAddr, String> {
+        v.as_s().unwrap().parse().map_err(|e| format!("{e}"))
+    }
+}
+
+#[aymond(item, table)]
+struct Probe {
+    #[aymond(hash_key, with = "ip_string")]
+    address: std::net::IpAddr,
+}
+
+fn main() {}
@@ -3,10 +3,20 @@ use std::marker::PhantomData;
 
 use aws_sdk_dynamodb::types::AttributeValue;
 
-/// A segment of a DynamoDB document path.
-#[derive(Clone)]
+use crate::error::EncodeError;
+use crate::eval::{self, resolve};
+
+/// A rendered expression string, with the expression attribute names and values it refers to.
+pub type BuiltExpression = (
+    String,
+    HashMap<String, String>,
+    HashMap<String, AttributeValue>,
+);
+
+/// A segment of a DynamoDB document path, in conditions and updates alike.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PathSegment {
-    /// Map key — gets an expression attribute name (#nN).
+    /// Map key — gets an expression attribute name (#nN in conditions, #uN in updates).
     Attr(String),
     /// List index — literal [N] in the expression.
     Index(usize),
@@ -41,6 +51,60 @@ pub enum CondExpr {
     AttributeNotExists {
         path: Vec<PathSegment>,
     },
+    AttributeType {
+        path: Vec<PathSegment>,
+        attribute_type: AttributeType,
+    },
+    /// `size(path) op value`
+    SizeComparison {
+        path: Vec<PathSegment>,
+        op: &'static str,
+        value: AttributeValue,
+    },
+    In {
+        path: Vec<PathSegment>,
+        values: Vec<AttributeValue>,
+    },
+    /// `path op other`, comparing two attributes of the same item.
+    AttributeComparison {
+        path: Vec<PathSegment>,
+        op: &'static str,
+        other: Vec<PathSegment>,
+    },
+    /// A value that couldn't be encoded. Building the expression returns the error.
+    Invalid(EncodeError),
+}
+
+/// A DynamoDB attribute type, as checked by `attribute_type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeType {
+    S,
+    Ss,
+    N,
+    Ns,
+    B,
+    Bs,
+    Bool,
+    Null,
+    L,
+    M,
+}
+
+impl AttributeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttributeType::S => "S",
+            AttributeType::Ss => "SS",
+            AttributeType::N => "N",
+            AttributeType::Ns => "NS",
+            AttributeType::B => "B",
+            AttributeType::Bs => "BS",
+            AttributeType::Bool => "BOOL",
+            AttributeType::Null => "NULL",
+            AttributeType::L => "L",
+            AttributeType::M => "M",
+        }
+    }
 }
 
 impl CondExpr {
@@ -57,20 +121,103 @@ impl CondExpr {
         CondExpr::Not(Box::new(self))
     }
 
+    /// Evaluates the condition against an item the way DynamoDB would, without sending it.
+    ///
+    /// Comparisons between values of different types and against missing attributes are false,
+    /// except for `<>`, which is true. Functions applied to a missing attribute are false, and so
+    /// are expressions with a value that couldn't be encoded, even under `not`.
+    pub fn evaluate(&self, item: &HashMap<String, AttributeValue>) -> bool {
+        self.is_valid() && self.matches(item)
+    }
+
+    /// Whether every value in the expression was encoded, i.e. whether it builds.
+    fn is_valid(&self) -> bool {
+        match self {
+            CondExpr::And(left, right) | CondExpr::Or(left, right) => {
+                left.is_valid() && right.is_valid()
+            }
+            CondExpr::Not(inner) => inner.is_valid(),
+            CondExpr::Invalid(_) => false,
+            _ => true,
+        }
+    }
+
+    fn matches(&self, item: &HashMap<String, AttributeValue>) -> bool {
+        match self {
+            CondExpr::Comparison { path, op, value } => {
+                eval::compare_op(op, resolve(item, path), Some(value))
+            }
+            CondExpr::And(left, right) => left.matches(item) && right.matches(item),
+            CondExpr::Or(left, right) => left.matches(item) || right.matches(item),
+            CondExpr::Not(inner) => !inner.matches(item),
+            CondExpr::Contains { path, value } => match (resolve(item, path), value) {
+                (Some(AttributeValue::S(s)), AttributeValue::S(v)) => s.contains(v.as_str()),
+                (Some(AttributeValue::Ss(set)), AttributeValue::S(v)) => set.contains(v),
+                (Some(AttributeValue::Ns(set)), AttributeValue::N(v)) => {
+                    eval::contains_number(set, v)
+                }
+                (Some(AttributeValue::Bs(set)), AttributeValue::B(v)) => set.contains(v),
+                (Some(AttributeValue::L(list)), v) => list.iter().any(|e| eval::equals(e, v)),
+                _ => false,
+            },
+            CondExpr::BeginsWith { path, value } => match (resolve(item, path), value) {
+                (Some(AttributeValue::S(s)), AttributeValue::S(v)) => s.starts_with(v.as_str()),
+                (Some(AttributeValue::B(b)), AttributeValue::B(v)) => {
+                    b.as_ref().starts_with(v.as_ref())
+                }
+                _ => false,
+            },
+            CondExpr::Between { path, low, high } => {
+                let value = resolve(item, path);
+                eval::compare_op(">=", value, Some(low))
+                    && eval::compare_op("<=", value, Some(high))
+            }
+            CondExpr::AttributeExists { path } => resolve(item, path).is_some(),
+            CondExpr::AttributeNotExists { path } => resolve(item, path).is_none(),
+            CondExpr::AttributeType {
+                path,
+                attribute_type,
+            } => resolve(item, path).is_some_and(|v| eval::type_name(v) == attribute_type.as_str()),
+            CondExpr::SizeComparison { path, op, value } => {
+                let size = match resolve(item, path) {
+                    Some(AttributeValue::S(s)) => s.len(),
+                    Some(AttributeValue::B(b)) => b.as_ref().len(),
+                    Some(AttributeValue::L(l)) => l.len(),
+                    Some(AttributeValue::M(m)) => m.len(),
+                    Some(AttributeValue::Ss(s)) => s.len(),
+                    Some(AttributeValue::Ns(s)) => s.len(),
+                    Some(AttributeValue::Bs(s)) => s.len(),
+                    _ => return false,
+                };
+                eval::compare_op(op, Some(&AttributeValue::N(size.to_string())), Some(value))
+            }
+            CondExpr::In { path, values } => resolve(item, path)
+                .is_some_and(|v| values.iter().any(|candidate| eval::equals(v, candidate))),
+            CondExpr::AttributeComparison { path, op, other } => {
+                eval::compare_op(op, resolve(item, path), resolve(item, other))
+            }
+            CondExpr::Invalid(_) => false,
+        }
+    }
+
     /// Renders the expression tree into a condition expression string,
     /// expression attribute names, and expression attribute values.
-    pub fn build(
-        self,
-    ) -> (
-        String,
-        HashMap<String, String>,
-        HashMap<String, AttributeValue>,
-    ) {
+    ///
+    /// Fails with the first value in the tree that couldn't be encoded.
+    pub fn build(self) -> Result<BuiltExpression, EncodeError> {
         let mut names = HashMap::new();
         let mut values = HashMap::new();
         let mut counter: usize = 0;
-        let expr = self.render(&mut counter, &mut names, &mut values);
-        (expr, names, values)
+        let expr = self.render(&mut counter, &mut names, &mut values)?;
+        Ok((expr, names, values))
+    }
+
+    /// An expression on a value that may have failed to encode.
+    fn with_value(
+        value: Result<AttributeValue, EncodeError>,
+        f: impl FnOnce(AttributeValue) -> CondExpr,
+    ) -> CondExpr {
+        value.map_or_else(CondExpr::Invalid, f)
     }
 
     fn render_path(
@@ -103,8 +250,8 @@ impl CondExpr {
         counter: &mut usize,
         names: &mut HashMap<String, String>,
         values: &mut HashMap<String, AttributeValue>,
-    ) -> String {
-        match self {
+    ) -> Result<String, EncodeError> {
+        let rendered = match self {
             CondExpr::Comparison { path, op, value } => {
                 let path_str = Self::render_path(path, counter, names);
                 let val_placeholder = format!(":v{}", *counter);
@@ -113,17 +260,17 @@ impl CondExpr {
                 format!("{} {} {}", path_str, op, val_placeholder)
             }
             CondExpr::And(left, right) => {
-                let l = left.render(counter, names, values);
-                let r = right.render(counter, names, values);
+                let l = left.render(counter, names, values)?;
+                let r = right.render(counter, names, values)?;
                 format!("({} AND {})", l, r)
             }
             CondExpr::Or(left, right) => {
-                let l = left.render(counter, names, values);
-                let r = right.render(counter, names, values);
+                let l = left.render(counter, names, values)?;
+                let r = right.render(counter, names, values)?;
                 format!("({} OR {})", l, r)
             }
             CondExpr::Not(inner) => {
-                let i = inner.render(counter, names, values);
+                let i = inner.render(counter, names, values)?;
                 format!("(NOT {})", i)
             }
             CondExpr::Contains { path, value } => {
@@ -161,7 +308,57 @@ impl CondExpr {
                 let path_str = Self::render_path(path, counter, names);
                 format!("attribute_not_exists({})", path_str)
             }
-        }
+            CondExpr::AttributeType {
+                path,
+                attribute_type,
+            } => {
+                let path_str = Self::render_path(path, counter, names);
+                let val_placeholder = format!(":v{}", *counter);
+                *counter += 1;
+                values.insert(
+                    val_placeholder.clone(),
+                    AttributeValue::S(attribute_type.as_str().to_string()),
+                );
+                format!("attribute_type({}, {})", path_str, val_placeholder)
+            }
+            CondExpr::SizeComparison { path, op, value } => {
+                let path_str = Self::render_path(path, counter, names);
+                let val_placeholder = format!(":v{}", *counter);
+                *counter += 1;
+                values.insert(val_placeholder.clone(), value.clone());
+                format!("size({}) {} {}", path_str, op, val_placeholder)
+            }
+            CondExpr::In {
+                path,
+                values: in_values,
+            } => {
+                let path_str = Self::render_path(path, counter, names);
+                // `IN ()` isn't valid, so an empty list renders an expression that's always false
+                if in_values.is_empty() {
+                    return Ok(format!(
+                        "(attribute_exists({}) AND attribute_not_exists({}))",
+                        path_str, path_str
+                    ));
+                }
+                let placeholders: Vec<String> = in_values
+                    .iter()
+                    .map(|value| {
+                        let val_placeholder = format!(":v{}", *counter);
+                        *counter += 1;
+                        values.insert(val_placeholder.clone(), value.clone());
+                        val_placeholder
+                    })
+                    .collect();
+                format!("{} IN ({})", path_str, placeholders.join(", "))
+            }
+            CondExpr::AttributeComparison { path, op, other } => {
+                let path_str = Self::render_path(path, counter, names);
+                let other_str = Self::render_path(other, counter, names);
+                format!("{} {} {}", path_str, op, other_str)
+            }
+            CondExpr::Invalid(err) => return Err(err.clone()),
+        };
+        Ok(rendered)
     }
 }
 
@@ -169,30 +366,32 @@ impl CondExpr {
 
 /// Converts a Rust value into a DynamoDB `AttributeValue` for use in condition expressions.
 pub trait IntoConditionValue {
-    fn into_condition_value(self) -> AttributeValue;
+    fn into_condition_value(self) -> Result<AttributeValue, EncodeError>;
 }
 
 impl IntoConditionValue for String {
-    fn into_condition_value(self) -> AttributeValue {
-        AttributeValue::S(self)
+    fn into_condition_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::S(self))
     }
 }
 
 impl IntoConditionValue for &str {
-    fn into_condition_value(self) -> AttributeValue {
-        AttributeValue::S(self.to_string())
+    fn into_condition_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::S(self.to_string()))
     }
 }
 
 impl IntoConditionValue for bool {
-    fn into_condition_value(self) -> AttributeValue {
-        AttributeValue::Bool(self)
+    fn into_condition_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::Bool(self))
     }
 }
 
 impl IntoConditionValue for Vec<u8> {
-    fn into_condition_value(self) -> AttributeValue {
-        AttributeValue::B(aws_sdk_dynamodb::primitives::Blob::new(self))
+    fn into_condition_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::B(aws_sdk_dynamodb::primitives::Blob::new(
+            self,
+        )))
     }
 }
 
@@ -200,8 +399,8 @@ macro_rules! impl_into_condition_value_numeric {
     ($($t:ty),+) => {
         $(
             impl IntoConditionValue for $t {
-                fn into_condition_value(self) -> AttributeValue {
-                    AttributeValue::N(self.to_string())
+                fn into_condition_value(self) -> Result<AttributeValue, EncodeError> {
+                    Ok(AttributeValue::N(self.to_string()))
                 }
             }
         )+
@@ -210,6 +409,18 @@ macro_rules! impl_into_condition_value_numeric {
 
 impl_into_condition_value_numeric!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
 
+impl IntoConditionValue for f32 {
+    fn into_condition_value(self) -> Result<AttributeValue, EncodeError> {
+        crate::marshal::encode_float(self)
+    }
+}
+
+impl IntoConditionValue for f64 {
+    fn into_condition_value(self) -> Result<AttributeValue, EncodeError> {
+        crate::marshal::encode_float(self)
+    }
+}
+
 // ── ConditionPathRoot ──
 
 /// Trait for condition path types that can be constructed with a path prefix.
@@ -217,6 +428,57 @@ pub trait ConditionPathRoot: Sized {
     fn with_prefix(path: Vec<PathSegment>) -> Self;
 }
 
+/// Names the condition path of a type the derive doesn't know by name: nested items, enums,
+/// transparent newtypes and the types behind `aymond`'s cargo features.
+pub trait HasConditionPath {
+    type Path: ConditionPathRoot;
+}
+
+/// A path whose values can be compared directly, so that lists of it support `contains`.
+pub trait ConditionValuePath: ConditionPathRoot {
+    type Value;
+
+    fn to_attribute_value(v: Self::Value) -> Result<AttributeValue, EncodeError>;
+}
+
+/// Functions that apply to a path of any type, expanded inside each path's `impl` block. Also
+/// expanded by the derive into generated path structs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_attribute_functions {
+    ($path:ident) => {
+        pub fn attribute_exists(self) -> $crate::condition::CondExpr {
+            $crate::condition::CondExpr::AttributeExists { path: self.$path }
+        }
+
+        pub fn attribute_not_exists(self) -> $crate::condition::CondExpr {
+            $crate::condition::CondExpr::AttributeNotExists { path: self.$path }
+        }
+
+        pub fn attribute_type(
+            self,
+            attribute_type: $crate::condition::AttributeType,
+        ) -> $crate::condition::CondExpr {
+            $crate::condition::CondExpr::AttributeType {
+                path: self.$path,
+                attribute_type,
+            }
+        }
+    };
+}
+
+/// `size()` for paths to strings, binary values, lists, maps and sets.
+macro_rules! impl_size {
+    ($path:ident) => {
+        /// The length of a string or binary value, or the number of elements in a collection.
+        pub fn size(&self) -> SizeConditionPath {
+            SizeConditionPath {
+                path: self.$path.clone(),
+            }
+        }
+    };
+}
+
 // ── ScalarConditionPath ──
 
 /// A typed path to a scalar DynamoDB attribute. Provides comparison methods.
@@ -235,64 +497,99 @@ impl<T: IntoConditionValue> ConditionPathRoot for ScalarConditionPath<T> {
 }
 
 impl<T: IntoConditionValue> ScalarConditionPath<T> {
-    pub fn eq(self, v: impl Into<T>) -> CondExpr {
-        CondExpr::Comparison {
+    fn compare(self, op: &'static str, v: T) -> CondExpr {
+        CondExpr::with_value(v.into_condition_value(), |value| CondExpr::Comparison {
             path: self.path,
-            op: "=",
-            value: v.into().into_condition_value(),
-        }
+            op,
+            value,
+        })
+    }
+
+    pub fn eq(self, v: impl Into<T>) -> CondExpr {
+        self.compare("=", v.into())
     }
 
     pub fn ne(self, v: impl Into<T>) -> CondExpr {
-        CondExpr::Comparison {
-            path: self.path,
-            op: "<>",
-            value: v.into().into_condition_value(),
-        }
+        self.compare("<>", v.into())
     }
 
     pub fn lt(self, v: impl Into<T>) -> CondExpr {
-        CondExpr::Comparison {
-            path: self.path,
-            op: "<",
-            value: v.into().into_condition_value(),
-        }
+        self.compare("<", v.into())
     }
 
     pub fn gt(self, v: impl Into<T>) -> CondExpr {
-        CondExpr::Comparison {
-            path: self.path,
-            op: ">",
-            value: v.into().into_condition_value(),
-        }
+        self.compare(">", v.into())
     }
 
     pub fn le(self, v: impl Into<T>) -> CondExpr {
-        CondExpr::Comparison {
-            path: self.path,
-            op: "<=",
-            value: v.into().into_condition_value(),
-        }
+        self.compare("<=", v.into())
     }
 
     pub fn ge(self, v: impl Into<T>) -> CondExpr {
-        CondExpr::Comparison {
-            path: self.path,
-            op: ">=",
-            value: v.into().into_condition_value(),
-        }
+        self.compare(">=", v.into())
     }
 
     pub fn between(self, low: impl Into<T>, high: impl Into<T>) -> CondExpr {
-        CondExpr::Between {
+        between(
+            self.path,
+            low.into().into_condition_value(),
+            high.into().into_condition_value(),
+        )
+    }
+
+    /// `path IN (v1, v2, ...)`. DynamoDB accepts up to 100 values, and more fail the request
+    /// with an [`EncodeError`]. No values never match.
+    pub fn is_in<V: Into<T>>(self, values: impl IntoIterator<Item = V>) -> CondExpr {
+        is_in(
+            self.path,
+            values.into_iter().map(|v| v.into().into_condition_value()),
+        )
+    }
+
+    fn compare_attr(self, op: &'static str, other: ScalarConditionPath<T>) -> CondExpr {
+        CondExpr::AttributeComparison {
             path: self.path,
-            low: low.into().into_condition_value(),
-            high: high.into().into_condition_value(),
+            op,
+            other: other.path,
         }
     }
+
+    pub fn eq_attr(self, other: ScalarConditionPath<T>) -> CondExpr {
+        self.compare_attr("=", other)
+    }
+
+    pub fn ne_attr(self, other: ScalarConditionPath<T>) -> CondExpr {
+        self.compare_attr("<>", other)
+    }
+
+    pub fn lt_attr(self, other: ScalarConditionPath<T>) -> CondExpr {
+        self.compare_attr("<", other)
+    }
+
+    pub fn gt_attr(self, other: ScalarConditionPath<T>) -> CondExpr {
+        self.compare_attr(">", other)
+    }
+
+    pub fn le_attr(self, other: ScalarConditionPath<T>) -> CondExpr {
+        self.compare_attr("<=", other)
+    }
+
+    pub fn ge_attr(self, other: ScalarConditionPath<T>) -> CondExpr {
+        self.compare_attr(">=", other)
+    }
+
+    impl_attribute_functions!(path);
+}
+
+impl<T: IntoConditionValue> ConditionValuePath for ScalarConditionPath<T> {
+    type Value = T;
+
+    fn to_attribute_value(v: T) -> Result<AttributeValue, EncodeError> {
+        v.into_condition_value()
+    }
 }
 
-// begins_with for String paths
+// begins_with, contains and size for String paths
 impl ScalarConditionPath<String> {
     pub fn begins_with(self, v: impl Into<String>) -> CondExpr {
         CondExpr::BeginsWith {
@@ -300,9 +597,19 @@ impl ScalarConditionPath<String> {
             value: AttributeValue::S(v.into()),
         }
     }
+
+    /// Checks that the string contains `v` as a substring.
+    pub fn contains(self, v: impl Into<String>) -> CondExpr {
+        CondExpr::Contains {
+            path: self.path,
+            value: AttributeValue::S(v.into()),
+        }
+    }
+
+    impl_size!(path);
 }
 
-// begins_with for binary paths
+// begins_with and size for binary paths
 impl ScalarConditionPath<Vec<u8>> {
     pub fn begins_with(self, v: Vec<u8>) -> CondExpr {
         CondExpr::BeginsWith {
@@ -310,6 +617,146 @@ impl ScalarConditionPath<Vec<u8>> {
             value: AttributeValue::B(aws_sdk_dynamodb::primitives::Blob::new(v)),
         }
     }
+
+    impl_size!(path);
+}
+
+// ── ConvertedConditionPath ──
+
+/// A typed path to an attribute marshalled by a `#[aymond(with = "...")]` converter. Values are
+/// converted with the field's `to_attribute_value` function before being compared.
+pub struct ConvertedConditionPath<T> {
+    path: Vec<PathSegment>,
+    convert: fn(&T) -> Result<AttributeValue, EncodeError>,
+}
+
+impl<T> ConvertedConditionPath<T> {
+    pub fn new(
+        path: Vec<PathSegment>,
+        convert: fn(&T) -> Result<AttributeValue, EncodeError>,
+    ) -> Self {
+        Self { path, convert }
+    }
+
+    fn compare(self, op: &'static str, v: T) -> CondExpr {
+        CondExpr::with_value((self.convert)(&v), |value| CondExpr::Comparison {
+            path: self.path,
+            op,
+            value,
+        })
+    }
+
+    pub fn eq(self, v: impl Into<T>) -> CondExpr {
+        self.compare("=", v.into())
+    }
+
+    pub fn ne(self, v: impl Into<T>) -> CondExpr {
+        self.compare("<>", v.into())
+    }
+
+    pub fn lt(self, v: impl Into<T>) -> CondExpr {
+        self.compare("<", v.into())
+    }
+
+    pub fn gt(self, v: impl Into<T>) -> CondExpr {
+        self.compare(">", v.into())
+    }
+
+    pub fn le(self, v: impl Into<T>) -> CondExpr {
+        self.compare("<=", v.into())
+    }
+
+    pub fn ge(self, v: impl Into<T>) -> CondExpr {
+        self.compare(">=", v.into())
+    }
+
+    pub fn between(self, low: impl Into<T>, high: impl Into<T>) -> CondExpr {
+        between(
+            self.path,
+            (self.convert)(&low.into()),
+            (self.convert)(&high.into()),
+        )
+    }
+
+    /// `path IN (v1, v2, ...)`. DynamoDB accepts up to 100 values, and more fail the request
+    /// with an [`EncodeError`]. No values never match.
+    pub fn is_in<V: Into<T>>(self, values: impl IntoIterator<Item = V>) -> CondExpr {
+        let convert = self.convert;
+        is_in(self.path, values.into_iter().map(|v| convert(&v.into())))
+    }
+
+    impl_attribute_functions!(path);
+}
+
+fn between(
+    path: Vec<PathSegment>,
+    low: Result<AttributeValue, EncodeError>,
+    high: Result<AttributeValue, EncodeError>,
+) -> CondExpr {
+    match (low, high) {
+        (Ok(low), Ok(high)) => CondExpr::Between { path, low, high },
+        (Err(err), _) | (_, Err(err)) => CondExpr::Invalid(err),
+    }
+}
+
+fn is_in(
+    path: Vec<PathSegment>,
+    values: impl Iterator<Item = Result<AttributeValue, EncodeError>>,
+) -> CondExpr {
+    match values.collect::<Result<Vec<_>, _>>() {
+        Ok(values) if values.len() > 100 => CondExpr::Invalid(EncodeError::new(format!(
+            "IN takes at most 100 values, got {}",
+            values.len()
+        ))),
+        Ok(values) => CondExpr::In { path, values },
+        Err(err) => CondExpr::Invalid(err),
+    }
+}
+
+// ── SizeConditionPath ──
+
+/// The `size()` of a string, binary value or collection, compared as a number.
+pub struct SizeConditionPath {
+    path: Vec<PathSegment>,
+}
+
+impl SizeConditionPath {
+    fn compare(self, op: &'static str, v: usize) -> CondExpr {
+        CondExpr::SizeComparison {
+            path: self.path,
+            op,
+            value: AttributeValue::N(v.to_string()),
+        }
+    }
+
+    pub fn eq(self, v: usize) -> CondExpr {
+        self.compare("=", v)
+    }
+
+    pub fn ne(self, v: usize) -> CondExpr {
+        self.compare("<>", v)
+    }
+
+    pub fn lt(self, v: usize) -> CondExpr {
+        self.compare("<", v)
+    }
+
+    pub fn gt(self, v: usize) -> CondExpr {
+        self.compare(">", v)
+    }
+
+    pub fn le(self, v: usize) -> CondExpr {
+        self.compare("<=", v)
+    }
+
+    pub fn ge(self, v: usize) -> CondExpr {
+        self.compare(">=", v)
+    }
+
+    pub fn between(self, low: usize, high: usize) -> CondExpr {
+        let path = self.path.clone();
+        self.ge(low).and(SizeConditionPath { path }.le(high))
+    }
 }
 
 // ── ListConditionPath ──
@@ -335,6 +782,50 @@ impl<T: ConditionPathRoot> ListConditionPath<T> {
         path.push(PathSegment::Index(i));
         T::with_prefix(path)
     }
+
+    impl_size!(path_prefix);
+    impl_attribute_functions!(path_prefix);
+}
+
+impl<T: ConditionValuePath> ListConditionPath<T> {
+    /// Checks that the list has an element equal to `v`.
+    pub fn contains(self, v: impl Into<T::Value>) -> CondExpr {
+        CondExpr::with_value(T::to_attribute_value(v.into()), |value| {
+            CondExpr::Contains {
+                path: self.path_prefix,
+                value,
+            }
+        })
+    }
+}
+
+// ── MapConditionPath ──
+
+/// A typed path to a DynamoDB map (M) attribute with free-form keys. Use `.key("x")` to access
+/// values.
+pub struct MapConditionPath<T: ConditionPathRoot> {
+    path_prefix: Vec<PathSegment>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: ConditionPathRoot> ConditionPathRoot for MapConditionPath<T> {
+    fn with_prefix(path: Vec<PathSegment>) -> Self {
+        Self {
+            path_prefix: path,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: ConditionPathRoot> MapConditionPath<T> {
+    pub fn key(&self, k: impl Into<String>) -> T {
+        let mut path = self.path_prefix.clone();
+        path.push(PathSegment::Attr(k.into()));
+        T::with_prefix(path)
+    }
+
+    impl_size!(path_prefix);
+    impl_attribute_functions!(path_prefix);
 }
 
 // ── StringSetConditionPath ──
@@ -357,6 +848,40 @@ impl StringSetConditionPath {
             value: AttributeValue::S(v.into()),
         }
     }
+
+    impl_size!(path);
+    impl_attribute_functions!(path);
+}
+
+// ── NumberSetConditionPath ──
+
+/// A typed path to a DynamoDB number set (NS) attribute.
+pub struct NumberSetConditionPath<T: IntoConditionValue> {
+    path: Vec<PathSegment>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: IntoConditionValue> ConditionPathRoot for NumberSetConditionPath<T> {
+    fn with_prefix(path: Vec<PathSegment>) -> Self {
+        Self {
+            path,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: IntoConditionValue> NumberSetConditionPath<T> {
+    pub fn contains(self, v: impl Into<T>) -> CondExpr {
+        CondExpr::with_value(v.into().into_condition_value(), |value| {
+            CondExpr::Contains {
+                path: self.path,
+                value,
+            }
+        })
+    }
+
+    impl_size!(path);
+    impl_attribute_functions!(path);
 }
 
 // ── BinarySetConditionPath ──
@@ -379,6 +904,9 @@ impl BinarySetConditionPath {
             value: AttributeValue::B(aws_sdk_dynamodb::primitives::Blob::new(v)),
         }
     }
+
+    impl_size!(path);
+    impl_attribute_functions!(path);
 }
 
 // ── ExistenceCheck ──
@@ -409,3 +937,161 @@ impl IntoOptionalCondExpr for () {
         Option::None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AttributeType, CondExpr, ConditionPathRoot, ConvertedConditionPath, ListConditionPath,
+        MapConditionPath, PathSegment, ScalarConditionPath, StringSetConditionPath,
+    };
+    use crate::error::EncodeError;
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use std::collections::HashMap;
+
+    fn path<P: ConditionPathRoot>(name: &str) -> P {
+        P::with_prefix(vec![PathSegment::Attr(name.into())])
+    }
+
+    fn item() -> HashMap<String, AttributeValue> {
+        HashMap::from([
+            ("status".into(), AttributeValue::S("open".into())),
+            ("start".into(), AttributeValue::N("10".into())),
+            ("end".into(), AttributeValue::N("9.5".into())),
+            (
+                "tags".into(),
+                AttributeValue::L(vec![
+                    AttributeValue::S("a".into()),
+                    AttributeValue::S("b".into()),
+                ]),
+            ),
+            (
+                "watchers".into(),
+                AttributeValue::Ss(vec!["carol".into(), "dave".into()]),
+            ),
+            (
+                "owner".into(),
+                AttributeValue::M(HashMap::from([(
+                    "name".into(),
+                    AttributeValue::S("alice".into()),
+                )])),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_evaluate_comparisons() {
+        let item = item();
+        let status = || path::<ScalarConditionPath<String>>("status");
+        let start = || path::<ScalarConditionPath<f64>>("start");
+        let end = || path::<ScalarConditionPath<f64>>("end");
+        let missing = || path::<ScalarConditionPath<i32>>("missing");
+
+        assert!(status().eq("open").evaluate(&item));
+        assert!(start().gt(9.99).evaluate(&item));
+        assert!(start().eq(10.0).evaluate(&item));
+        assert!(end().lt_attr(start()).evaluate(&item));
+        assert!(start().between(10.0, 11.0).evaluate(&item));
+        assert!(status().is_in(["closed", "open"]).evaluate(&item));
+        // Numbers and strings are never equal, and strings aren't ordered against numbers
+        let start_as_string = path::<ScalarConditionPath<String>>("start");
+        assert!(!start_as_string.eq("10").evaluate(&item));
+        assert!(
+            !path::<ScalarConditionPath<String>>("start")
+                .gt("1")
+                .evaluate(&item)
+        );
+        // Only <> is true for a missing attribute
+        assert!(!missing().eq(1).evaluate(&item));
+        assert!(!missing().lt(1).evaluate(&item));
+        assert!(missing().ne(1).evaluate(&item));
+        assert!(missing().lt(1).not().evaluate(&item));
+    }
+
+    #[test]
+    fn test_evaluate_functions() {
+        let item = item();
+        let status = || path::<ScalarConditionPath<String>>("status");
+        let tags = || path::<ListConditionPath<ScalarConditionPath<String>>>("tags");
+        let watchers = || path::<StringSetConditionPath>("watchers");
+        let owner = || path::<MapConditionPath<ScalarConditionPath<String>>>("owner");
+
+        assert!(status().begins_with("op").evaluate(&item));
+        assert!(status().contains("pe").evaluate(&item));
+        assert!(tags().contains("b").evaluate(&item));
+        assert!(!tags().contains("c").evaluate(&item));
+        assert!(watchers().contains("dave").evaluate(&item));
+        assert!(tags().size().eq(2).evaluate(&item));
+        assert!(status().size().between(4, 4).evaluate(&item));
+        assert!(owner().key("name").eq("alice").evaluate(&item));
+        assert!(tags().index(1).eq("b").evaluate(&item));
+        assert!(!tags().index(2).attribute_exists().evaluate(&item));
+        assert!(owner().attribute_type(AttributeType::M).evaluate(&item));
+        assert!(!watchers().attribute_type(AttributeType::L).evaluate(&item));
+        assert!(
+            CondExpr::AttributeNotExists {
+                path: vec![PathSegment::Attr("missing".into())],
+            }
+            .and(status().attribute_exists())
+            .evaluate(&item)
+        );
+    }
+
+    #[test]
+    fn test_values_that_fail_to_encode() {
+        let expires = || {
+            ConvertedConditionPath::<u64>::new(vec![PathSegment::Attr("expires".into())], |_| {
+                Err(EncodeError::new("out of range"))
+            })
+        };
+        let expr = path::<ScalarConditionPath<String>>("status")
+            .eq("open")
+            .and(expires().gt(1u64));
+        assert!(!expr.evaluate(&item()));
+        assert!(!expr.not().evaluate(&item()));
+        let either = path::<ScalarConditionPath<String>>("status")
+            .eq("open")
+            .or(expires().gt(1u64));
+        assert!(!either.evaluate(&item()));
+        let err = either.build().unwrap_err();
+        assert_eq!(err.to_string(), "failed to encode value: out of range");
+
+        let ratio = || path::<ScalarConditionPath<f64>>("ratio");
+        assert!(ratio().gt(0.5).build().is_ok());
+        let err = ratio().gt(f64::NAN).build().unwrap_err();
+        assert_eq!(err.reason, "NaN can't be stored as a DynamoDB number");
+        assert!(ratio().between(0.0, f64::INFINITY).build().is_err());
+        assert!(
+            path::<ScalarConditionPath<f32>>("ratio")
+                .eq(f32::NEG_INFINITY)
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_is_in_limits() {
+        let status = || path::<ScalarConditionPath<String>>("status");
+
+        let none = status().is_in(Vec::<String>::new());
+        assert!(!none.evaluate(&item()));
+        let (expr, names, values) = none.build().unwrap();
+        assert_eq!(
+            expr,
+            "(attribute_exists(#n0) AND attribute_not_exists(#n0))"
+        );
+        assert_eq!(names["#n0"], "status");
+        assert!(values.is_empty());
+        assert!(status().is_in(Vec::<String>::new()).not().evaluate(&item()));
+
+        let limit = || (0..100).map(|i| i.to_string());
+        assert!(status().is_in(limit()).build().is_ok());
+        let err = status()
+            .is_in(limit().chain(["open".to_string()]))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to encode value: IN takes at most 100 values, got 101"
+        );
+    }
+}
@@ -0,0 +1,9 @@
+/// The result of a count-only query or scan.
+///
+/// `count` is the number of items that matched, after any filter. `scanned_count` is the number
+/// of items DynamoDB read to find them, which is what the request consumed capacity for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Count {
+    pub count: u64,
+    pub scanned_count: u64,
+}
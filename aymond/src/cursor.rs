@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use aws_sdk_dynamodb::primitives::Blob;
+use aws_sdk_dynamodb::types::{AttributeValue, ScalarAttributeType};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+const VERSION: u8 = 1;
+const UNSIGNED: u8 = 0;
+const SIGNED: u8 = 1;
+const TAG_LEN: usize = 32;
+
+/// One page of a query or scan, and the cursor to resume from if there are more items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<Cursor>,
+}
+
+/// An opaque, URL-safe encoding of the key a query or scan should resume after.
+///
+/// Cursors are signed with HMAC-SHA256 when the client has a signing key set with
+/// [`Aymond::with_cursor_signing_key`](crate::Aymond::with_cursor_signing_key), in which case
+/// cursors that weren't produced with the same key are rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cursor(String);
+
+/// A cursor that couldn't be turned back into a start key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CursorError {
+    /// The cursor isn't something this library encoded.
+    Malformed,
+    /// The cursor is unsigned while a signing key is set, or its signature doesn't match.
+    InvalidSignature,
+    /// The cursor is signed but the client has no signing key.
+    UnexpectedSignature,
+    /// The encoded key doesn't match the key schema of the table or index being read.
+    KeyMismatch(String),
+}
+
+impl fmt::Display for CursorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CursorError::Malformed => write!(f, "malformed cursor"),
+            CursorError::InvalidSignature => write!(f, "cursor signature is missing or invalid"),
+            CursorError::UnexpectedSignature => {
+                write!(f, "cursor is signed but no signing key is set")
+            }
+            CursorError::KeyMismatch(reason) => {
+                write!(f, "cursor doesn't match key schema: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CursorError {}
+
+impl Cursor {
+    /// Encodes a `LastEvaluatedKey`, signing it if `signing_key` is set.
+    pub fn encode(key: &HashMap<String, AttributeValue>, signing_key: Option<&[u8]>) -> Self {
+        let mut attrs: Vec<_> = key.iter().collect();
+        attrs.sort_by_key(|(name, _)| *name);
+
+        let mut buf = vec![VERSION, UNSIGNED];
+        for (name, value) in attrs {
+            let (ty, bytes) = match value {
+                AttributeValue::S(s) => (b'S', s.as_bytes()),
+                AttributeValue::N(n) => (b'N', n.as_bytes()),
+                AttributeValue::B(b) => (b'B', b.as_ref()),
+                // Keys can only be strings, numbers or binary
+                _ => unreachable!("key attribute `{name}` is not a scalar"),
+            };
+            buf.extend_from_slice(&(name.len() as u16).to_be_bytes());
+            buf.extend_from_slice(name.as_bytes());
+            buf.push(ty);
+            buf.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+            buf.extend_from_slice(bytes);
+        }
+
+        if let Some(signing_key) = signing_key {
+            buf[1] = SIGNED;
+            let tag = mac(signing_key, &buf).finalize().into_bytes();
+            buf.extend_from_slice(&tag);
+        }
+        Self(URL_SAFE_NO_PAD.encode(buf))
+    }
+
+    /// Decodes the start key, checking that it has exactly the attributes in `key_schema` and
+    /// that its signature matches `signing_key`.
+    pub fn decode(
+        &self,
+        key_schema: &[(&str, ScalarAttributeType)],
+        signing_key: Option<&[u8]>,
+    ) -> Result<HashMap<String, AttributeValue>, CursorError> {
+        let buf = URL_SAFE_NO_PAD
+            .decode(&self.0)
+            .map_err(|_| CursorError::Malformed)?;
+        if buf.len() < 2 || buf[0] != VERSION {
+            return Err(CursorError::Malformed);
+        }
+
+        let payload = match (buf[1], signing_key) {
+            (SIGNED, Some(signing_key)) => {
+                if buf.len() < 2 + TAG_LEN {
+                    return Err(CursorError::Malformed);
+                }
+                let (payload, tag) = buf.split_at(buf.len() - TAG_LEN);
+                mac(signing_key, payload)
+                    .verify_slice(tag)
+                    .map_err(|_| CursorError::InvalidSignature)?;
+                payload
+            }
+            (SIGNED, None) => return Err(CursorError::UnexpectedSignature),
+            (UNSIGNED, Some(_)) => return Err(CursorError::InvalidSignature),
+            (UNSIGNED, None) => &buf[..],
+            _ => return Err(CursorError::Malformed),
+        };
+
+        let mut key = HashMap::new();
+        let mut rest = &payload[2..];
+        while !rest.is_empty() {
+            let name_len = u16::from_be_bytes(take(&mut rest, 2)?.try_into().unwrap()) as usize;
+            let name = String::from_utf8(take(&mut rest, name_len)?.to_vec())
+                .map_err(|_| CursorError::Malformed)?;
+            let ty = take(&mut rest, 1)?[0];
+            let value_len = u32::from_be_bytes(take(&mut rest, 4)?.try_into().unwrap()) as usize;
+            let bytes = take(&mut rest, value_len)?;
+            let value = match ty {
+                b'S' | b'N' => {
+                    let s =
+                        String::from_utf8(bytes.to_vec()).map_err(|_| CursorError::Malformed)?;
+                    if ty == b'S' {
+                        AttributeValue::S(s)
+                    } else {
+                        AttributeValue::N(s)
+                    }
+                }
+                b'B' => AttributeValue::B(Blob::new(bytes)),
+                _ => return Err(CursorError::Malformed),
+            };
+            key.insert(name, value);
+        }
+
+        for (name, scalar) in key_schema {
+            let matches = match (key.get(*name), scalar) {
+                (Some(AttributeValue::S(_)), ScalarAttributeType::S)
+                | (Some(AttributeValue::N(_)), ScalarAttributeType::N)
+                | (Some(AttributeValue::B(_)), ScalarAttributeType::B) => true,
+                (Some(_), _) => false,
+                (None, _) => return Err(CursorError::KeyMismatch(format!("missing `{name}`"))),
+            };
+            if !matches {
+                return Err(CursorError::KeyMismatch(format!(
+                    "`{name}` should be {}",
+                    scalar.as_str()
+                )));
+            }
+        }
+        if let Some(name) = key
+            .keys()
+            .find(|k| !key_schema.iter().any(|(name, _)| name == k))
+        {
+            return Err(CursorError::KeyMismatch(format!("unexpected `{name}`")));
+        }
+        Ok(key)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+fn mac(signing_key: &[u8], payload: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(signing_key).expect("HMAC accepts any key length");
+    mac.update(payload);
+    mac
+}
+
+fn take<'a>(rest: &mut &'a [u8], n: usize) -> Result<&'a [u8], CursorError> {
+    if rest.len() < n {
+        return Err(CursorError::Malformed);
+    }
+    let (head, tail) = rest.split_at(n);
+    *rest = tail;
+    Ok(head)
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for Cursor {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl From<&str> for Cursor {
+    fn from(s: &str) -> Self {
+        Self(s.to_string())
+    }
+}
+
+impl From<Cursor> for String {
+    fn from(c: Cursor) -> Self {
+        c.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for Cursor {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for Cursor {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cursor, CursorError};
+    use aws_sdk_dynamodb::types::{AttributeValue, ScalarAttributeType};
+    use std::collections::HashMap;
+
+    fn key() -> HashMap<String, AttributeValue> {
+        HashMap::from([
+            ("make".to_string(), AttributeValue::S("Porsche".into())),
+            ("year".to_string(), AttributeValue::N("1964".into())),
+        ])
+    }
+
+    const SCHEMA: [(&str, ScalarAttributeType); 2] = [
+        ("make", ScalarAttributeType::S),
+        ("year", ScalarAttributeType::N),
+    ];
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor::encode(&key(), None);
+        assert_eq!(cursor.decode(&SCHEMA, None), Ok(key()));
+
+        let cursor = Cursor::encode(&key(), Some(b"secret"));
+        assert_eq!(cursor.decode(&SCHEMA, Some(b"secret")), Ok(key()));
+        assert_eq!(
+            cursor.decode(&SCHEMA, Some(b"other")),
+            Err(CursorError::InvalidSignature)
+        );
+        assert_eq!(
+            cursor.decode(&SCHEMA, None),
+            Err(CursorError::UnexpectedSignature)
+        );
+        assert_eq!(
+            Cursor::encode(&key(), None).decode(&SCHEMA, Some(b"secret")),
+            Err(CursorError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_cursor_validates_key_schema() {
+        let cursor = Cursor::encode(&key(), None);
+        assert_eq!(
+            cursor.decode(&[("make", ScalarAttributeType::S)], None),
+            Err(CursorError::KeyMismatch("unexpected `year`".into()))
+        );
+        assert_eq!(
+            cursor.decode(
+                &[
+                    ("make", ScalarAttributeType::S),
+                    ("year", ScalarAttributeType::S)
+                ],
+                None
+            ),
+            Err(CursorError::KeyMismatch("`year` should be S".into()))
+        );
+        assert_eq!(
+            Cursor::from("not a cursor").decode(&SCHEMA, None),
+            Err(CursorError::Malformed)
+        );
+    }
+}
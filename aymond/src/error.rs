@@ -1,7 +1,12 @@
 use aws_sdk_dynamodb::config::http::HttpResponse;
 use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use std::collections::HashMap;
 use std::fmt;
 
+use crate::cursor::CursorError;
+
 #[derive(Debug)]
 pub enum BatchError<E: fmt::Debug + std::error::Error + 'static> {
     SdkError(Box<SdkError<E, HttpResponse>>),
@@ -33,3 +38,380 @@ impl<E: fmt::Debug + std::error::Error + 'static> From<SdkError<E, HttpResponse>
         BatchError::SdkError(Box::new(err))
     }
 }
+
+/// Error returned by requests that read items back, such as `get`, `query` and `scan`.
+///
+/// `Cursor` is only returned by `send_page`, for cursors that can't be resumed from.
+#[derive(Debug)]
+pub enum RequestError<E: fmt::Debug + std::error::Error + 'static> {
+    SdkError(Box<SdkError<E, HttpResponse>>),
+    Decode(DecodeError),
+    Cursor(CursorError),
+    /// Returned by `one()` when more than one item matched.
+    NotUnique,
+}
+
+impl<E: fmt::Debug + std::error::Error + 'static> fmt::Display for RequestError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestError::SdkError(e) => write!(f, "{e}"),
+            RequestError::Decode(e) => write!(f, "{e}"),
+            RequestError::Cursor(e) => write!(f, "{e}"),
+            RequestError::NotUnique => write!(f, "expected at most one item, found more"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + std::error::Error + 'static> std::error::Error for RequestError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RequestError::SdkError(e) => Some(e.as_ref()),
+            RequestError::Decode(e) => Some(e),
+            RequestError::Cursor(e) => Some(e),
+            RequestError::NotUnique => None,
+        }
+    }
+}
+
+impl<E: fmt::Debug + std::error::Error + 'static> From<SdkError<E, HttpResponse>>
+    for RequestError<E>
+{
+    fn from(err: SdkError<E, HttpResponse>) -> Self {
+        RequestError::SdkError(Box::new(err))
+    }
+}
+
+impl<E: fmt::Debug + std::error::Error + 'static> From<DecodeError> for RequestError<E> {
+    fn from(err: DecodeError) -> Self {
+        RequestError::Decode(err)
+    }
+}
+
+impl<E: fmt::Debug + std::error::Error + 'static> From<CursorError> for RequestError<E> {
+    fn from(err: CursorError) -> Self {
+        RequestError::Cursor(err)
+    }
+}
+
+/// Error returned by one segment of a parallel scan.
+#[derive(Debug)]
+pub struct SegmentError<E: fmt::Debug + std::error::Error + 'static> {
+    pub segment: i32,
+    pub error: RequestError<E>,
+}
+
+impl<E: fmt::Debug + std::error::Error + 'static> fmt::Display for SegmentError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "segment {}: {}", self.segment, self.error)
+    }
+}
+
+impl<E: fmt::Debug + std::error::Error + 'static> std::error::Error for SegmentError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Error returned by `Tx::send`.
+///
+/// When DynamoDB cancels the transaction, `Canceled` holds one entry per operation in the order
+/// they were added to the `Tx`, including the ones that didn't cause the cancellation.
+/// `IdempotentParameterMismatch` means the idempotency token was already used by a different
+/// transaction.
+#[derive(Debug)]
+pub enum TxError {
+    Canceled(Vec<TxCancellation>),
+    IdempotentParameterMismatch { message: Option<String> },
+    SdkError(Box<SdkError<TransactWriteItemsError, HttpResponse>>),
+}
+
+impl TxError {
+    /// The operations that caused the transaction to be canceled.
+    pub fn failures(&self) -> impl Iterator<Item = &TxCancellation> {
+        let reasons = match self {
+            TxError::Canceled(reasons) => reasons.as_slice(),
+            TxError::IdempotentParameterMismatch { .. } | TxError::SdkError(_) => &[],
+        };
+        reasons.iter().filter(|r| r.code != CancellationCode::None)
+    }
+}
+
+impl fmt::Display for TxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxError::Canceled(_) => {
+                write!(f, "transaction canceled")?;
+                for (i, failure) in self.failures().enumerate() {
+                    write!(f, "{}", if i == 0 { ": " } else { ", " })?;
+                    match &failure.label {
+                        Some(label) => write!(f, "{label}")?,
+                        None => write!(f, "operation on {}", failure.table)?,
+                    }
+                    write!(f, " ({})", failure.code)?;
+                }
+                Ok(())
+            }
+            TxError::IdempotentParameterMismatch { message } => {
+                write!(f, "idempotency token was used by a different transaction")?;
+                match message {
+                    Some(message) => write!(f, ": {message}"),
+                    None => Ok(()),
+                }
+            }
+            TxError::SdkError(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for TxError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TxError::Canceled(_) | TxError::IdempotentParameterMismatch { .. } => None,
+            TxError::SdkError(e) => Some(e.as_ref()),
+        }
+    }
+}
+
+impl From<SdkError<TransactWriteItemsError, HttpResponse>> for TxError {
+    fn from(err: SdkError<TransactWriteItemsError, HttpResponse>) -> Self {
+        TxError::SdkError(Box::new(err))
+    }
+}
+
+/// What happened to one operation of a canceled transaction.
+///
+/// `key` is empty for puts that weren't built by a generated builder, since the key attributes of
+/// a raw `Put` aren't known. `item` is only set when the operation asked for it with
+/// `return_old_on_condition_failure()` and its condition failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxCancellation {
+    pub label: Option<String>,
+    pub table: String,
+    pub key: HashMap<String, AttributeValue>,
+    pub code: CancellationCode,
+    pub message: Option<String>,
+    pub item: Option<HashMap<String, AttributeValue>>,
+}
+
+/// The reason code DynamoDB gives for each operation of a canceled transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CancellationCode {
+    /// The operation didn't cause the cancellation.
+    None,
+    ConditionalCheckFailed,
+    ItemCollectionSizeLimitExceeded,
+    TransactionConflict,
+    ProvisionedThroughputExceeded,
+    ThrottlingError,
+    ValidationError,
+    Other(String),
+}
+
+impl From<&str> for CancellationCode {
+    fn from(code: &str) -> Self {
+        match code {
+            "None" => CancellationCode::None,
+            "ConditionalCheckFailed" => CancellationCode::ConditionalCheckFailed,
+            "ItemCollectionSizeLimitExceeded" => CancellationCode::ItemCollectionSizeLimitExceeded,
+            "TransactionConflict" => CancellationCode::TransactionConflict,
+            "ProvisionedThroughputExceeded" => CancellationCode::ProvisionedThroughputExceeded,
+            "ThrottlingError" => CancellationCode::ThrottlingError,
+            "ValidationError" => CancellationCode::ValidationError,
+            other => CancellationCode::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for CancellationCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CancellationCode::None => write!(f, "None"),
+            CancellationCode::ConditionalCheckFailed => write!(f, "ConditionalCheckFailed"),
+            CancellationCode::ItemCollectionSizeLimitExceeded => {
+                write!(f, "ItemCollectionSizeLimitExceeded")
+            }
+            CancellationCode::TransactionConflict => write!(f, "TransactionConflict"),
+            CancellationCode::ProvisionedThroughputExceeded => {
+                write!(f, "ProvisionedThroughputExceeded")
+            }
+            CancellationCode::ThrottlingError => write!(f, "ThrottlingError"),
+            CancellationCode::ValidationError => write!(f, "ValidationError"),
+            CancellationCode::Other(code) => write!(f, "{code}"),
+        }
+    }
+}
+
+/// An item attribute that could not be converted into its Rust type.
+///
+/// `path` is the document path of the offending attribute (e.g. `production.began` or
+/// `variants[2]`) and `expected` is the DynamoDB type the field maps to. `actual` is the type
+/// that was found when it didn't match, and `reason` explains why a value of the right type
+/// couldn't be parsed. Both are `None` if the attribute was missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    pub path: String,
+    pub expected: &'static str,
+    pub actual: Option<&'static str>,
+    pub reason: Option<String>,
+}
+
+impl DecodeError {
+    pub fn missing(expected: &'static str) -> Self {
+        Self {
+            path: String::new(),
+            expected,
+            actual: None,
+            reason: None,
+        }
+    }
+
+    pub fn mismatch(expected: &'static str, actual: &'static str) -> Self {
+        Self {
+            path: String::new(),
+            expected,
+            actual: Some(actual),
+            reason: None,
+        }
+    }
+
+    pub fn invalid(expected: &'static str, reason: impl fmt::Display) -> Self {
+        Self {
+            path: String::new(),
+            expected,
+            actual: None,
+            reason: Some(reason.to_string()),
+        }
+    }
+
+    /// Prefixes the error path with a map key.
+    pub fn at_attr(mut self, name: &str) -> Self {
+        self.path = if self.path.is_empty() {
+            name.to_string()
+        } else if self.path.starts_with('[') {
+            format!("{}{}", name, self.path)
+        } else {
+            format!("{}.{}", name, self.path)
+        };
+        self
+    }
+
+    /// Prefixes the error path with a list index.
+    pub fn at_index(mut self, i: usize) -> Self {
+        self.path = if self.path.is_empty() || self.path.starts_with('[') {
+            format!("[{}]{}", i, self.path)
+        } else {
+            format!("[{}].{}", i, self.path)
+        };
+        self
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.actual, &self.reason) {
+            (_, Some(reason)) => write!(
+                f,
+                "failed to decode `{}`: invalid {} value: {}",
+                self.path, self.expected, reason
+            ),
+            (Some(actual), None) => write!(
+                f,
+                "failed to decode `{}`: expected {}, found {}",
+                self.path, self.expected, actual
+            ),
+            (None, None) => write!(
+                f,
+                "failed to decode `{}`: expected {}, but the attribute is missing",
+                self.path, self.expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// A value that could not be converted into an attribute value, such as a timestamp outside the
+/// range RFC 3339 can represent or a value whose `Serialize` impl failed.
+///
+/// `path` is the document path of the offending attribute, or empty for a value that isn't part
+/// of an item. Requests surface it as an `SdkError::ConstructionFailure` without sending anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodeError {
+    pub path: String,
+    pub reason: String,
+}
+
+impl EncodeError {
+    pub fn new(reason: impl fmt::Display) -> Self {
+        Self {
+            path: String::new(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Prefixes the error path with a map key.
+    pub fn at_attr(mut self, name: &str) -> Self {
+        self.path = if self.path.is_empty() {
+            name.to_string()
+        } else if self.path.starts_with('[') {
+            format!("{}{}", name, self.path)
+        } else {
+            format!("{}.{}", name, self.path)
+        };
+        self
+    }
+
+    /// Prefixes the error path with a list index.
+    pub fn at_index(mut self, i: usize) -> Self {
+        self.path = if self.path.is_empty() || self.path.starts_with('[') {
+            format!("[{}]{}", i, self.path)
+        } else {
+            format!("[{}].{}", i, self.path)
+        };
+        self
+    }
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "failed to encode value: {}", self.reason)
+        } else {
+            write!(f, "failed to encode `{}`: {}", self.path, self.reason)
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+impl From<std::convert::Infallible> for EncodeError {
+    fn from(err: std::convert::Infallible) -> Self {
+        match err {}
+    }
+}
+
+/// An update expression that DynamoDB would reject for the item it was applied to.
+///
+/// `path` is the document path of the action that failed (e.g. `stock` or `tags[2]`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplyError {
+    pub path: String,
+    pub reason: String,
+}
+
+impl ApplyError {
+    pub fn new(path: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for ApplyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot update `{}`: {}", self.path, self.reason)
+    }
+}
+
+impl std::error::Error for ApplyError {}
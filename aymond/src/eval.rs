@@ -0,0 +1,352 @@
+//! DynamoDB's rules for comparing and combining attribute values, shared by the in-memory
+//! evaluation of condition and update expressions.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::condition::PathSegment;
+
+/// Looks up the value at `path`, descending into maps by key and lists by index.
+pub(crate) fn resolve<'a>(
+    item: &'a HashMap<String, AttributeValue>,
+    path: &[PathSegment],
+) -> Option<&'a AttributeValue> {
+    let (first, rest) = path.split_first()?;
+    let PathSegment::Attr(name) = first else {
+        return None;
+    };
+    rest.iter()
+        .try_fold(item.get(name)?, |value, seg| match (seg, value) {
+            (PathSegment::Attr(key), AttributeValue::M(map)) => map.get(key),
+            (PathSegment::Index(i), AttributeValue::L(list)) => list.get(*i),
+            _ => None,
+        })
+}
+
+/// The type name `attribute_type` checks against.
+pub(crate) fn type_name(v: &AttributeValue) -> &'static str {
+    match v {
+        AttributeValue::S(_) => "S",
+        AttributeValue::Ss(_) => "SS",
+        AttributeValue::N(_) => "N",
+        AttributeValue::Ns(_) => "NS",
+        AttributeValue::B(_) => "B",
+        AttributeValue::Bs(_) => "BS",
+        AttributeValue::Bool(_) => "BOOL",
+        AttributeValue::Null(_) => "NULL",
+        AttributeValue::L(_) => "L",
+        AttributeValue::M(_) => "M",
+        _ => "",
+    }
+}
+
+/// `=`: values of different types are never equal, numbers compare by value, sets ignore order
+/// and documents compare deeply.
+pub(crate) fn equals(a: &AttributeValue, b: &AttributeValue) -> bool {
+    match (a, b) {
+        (AttributeValue::N(a), AttributeValue::N(b)) => {
+            compare_numbers(a, b) == Some(Ordering::Equal)
+        }
+        (AttributeValue::Ns(a), AttributeValue::Ns(b)) => {
+            a.len() == b.len() && a.iter().all(|a| contains_number(b, a))
+        }
+        (AttributeValue::Ss(a), AttributeValue::Ss(b)) => {
+            a.len() == b.len() && a.iter().all(|a| b.contains(a))
+        }
+        (AttributeValue::Bs(a), AttributeValue::Bs(b)) => {
+            a.len() == b.len() && a.iter().all(|a| b.contains(a))
+        }
+        (AttributeValue::L(a), AttributeValue::L(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equals(a, b))
+        }
+        (AttributeValue::M(a), AttributeValue::M(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(k, a)| b.get(k).is_some_and(|b| equals(a, b)))
+        }
+        _ => a == b,
+    }
+}
+
+/// Orders two values for `<`, `<=`, `>`, `>=` and `BETWEEN`. Only strings (by UTF-8 bytes),
+/// numbers and binary values of the same type are ordered.
+pub(crate) fn compare(a: &AttributeValue, b: &AttributeValue) -> Option<Ordering> {
+    match (a, b) {
+        (AttributeValue::S(a), AttributeValue::S(b)) => Some(a.as_bytes().cmp(b.as_bytes())),
+        (AttributeValue::N(a), AttributeValue::N(b)) => compare_numbers(a, b),
+        (AttributeValue::B(a), AttributeValue::B(b)) => Some(a.as_ref().cmp(b.as_ref())),
+        _ => None,
+    }
+}
+
+/// The result of a comparison operator, where a missing attribute is only ever not equal.
+pub(crate) fn compare_op(op: &str, a: Option<&AttributeValue>, b: Option<&AttributeValue>) -> bool {
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return op == "<>",
+    };
+    match op {
+        "=" => equals(a, b),
+        "<>" => !equals(a, b),
+        _ => compare(a, b).is_some_and(|ord| match op {
+            "<" => ord.is_lt(),
+            "<=" => ord.is_le(),
+            ">" => ord.is_gt(),
+            ">=" => ord.is_ge(),
+            _ => false,
+        }),
+    }
+}
+
+pub(crate) fn contains_number(set: &[String], n: &str) -> bool {
+    set.iter()
+        .any(|m| compare_numbers(m, n) == Some(Ordering::Equal))
+}
+
+/// Orders two number strings exactly, or `None` if either isn't a number.
+fn compare_numbers(a: &str, b: &str) -> Option<Ordering> {
+    Some(Number::parse(a)?.cmp(&Number::parse(b)?))
+}
+
+/// `a + b` on number strings, computed exactly.
+pub(crate) fn add_numbers(a: &str, b: &str) -> Result<String, &'static str> {
+    let a = Number::parse_storable(a)?;
+    let b = Number::parse_storable(b)?;
+    a.add(b).to_dynamodb_string()
+}
+
+/// `a - b` on number strings, computed exactly.
+pub(crate) fn subtract_numbers(a: &str, b: &str) -> Result<String, &'static str> {
+    let a = Number::parse_storable(a)?;
+    let mut b = Number::parse_storable(b)?;
+    b.negative = !b.negative && !b.digits.is_empty();
+    a.add(b).to_dynamodb_string()
+}
+
+/// An exact decimal number: `digits` (most significant first) times `10^exponent`.
+///
+/// Digits carry no leading or trailing zeros, so every value has one representation and zero
+/// has no digits.
+#[derive(Debug, PartialEq, Eq)]
+struct Number {
+    negative: bool,
+    digits: Vec<u8>,
+    exponent: i64,
+}
+
+impl Number {
+    /// The most significant digits DynamoDB stores.
+    const MAX_DIGITS: usize = 38;
+
+    /// Bounds written exponents far beyond what DynamoDB stores, so that arithmetic on exponents
+    /// can't overflow.
+    const MAX_EXPONENT: i64 = 1 << 32;
+
+    /// Parses the number formats DynamoDB accepts, such as `-12`, `0.5`, `.5` and `1.5E-3`.
+    fn parse(s: &str) -> Option<Self> {
+        let (negative, s) = match s.as_bytes().first()? {
+            b'-' => (true, &s[1..]),
+            b'+' => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (mantissa, exponent) = match s.find(['e', 'E']) {
+            Some(i) => (&s[..i], s[i + 1..].parse::<i64>().ok()?),
+            None => (s, 0),
+        };
+        if !(-Self::MAX_EXPONENT..=Self::MAX_EXPONENT).contains(&exponent) {
+            return None;
+        }
+        let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if int.is_empty() && frac.is_empty() {
+            return None;
+        }
+        let mut digits = Vec::with_capacity(int.len() + frac.len());
+        for c in int.bytes().chain(frac.bytes()) {
+            if !c.is_ascii_digit() {
+                return None;
+            }
+            digits.push(c - b'0');
+        }
+        let exponent = exponent.checked_sub(frac.len() as i64)?;
+        Some(Self::normalized(negative, digits, exponent))
+    }
+
+    /// Parses a number within the range DynamoDB stores, which bounds the digits arithmetic on it
+    /// needs.
+    fn parse_storable(s: &str) -> Result<Self, &'static str> {
+        let n = Self::parse(s).ok_or("not a number")?;
+        n.check_range()?;
+        Ok(n)
+    }
+
+    /// DynamoDB stores magnitudes from 1E-130 to just under 1E+126.
+    fn check_range(&self) -> Result<(), &'static str> {
+        match self.magnitude() {
+            _ if self.digits.is_empty() => Ok(()),
+            m if m > 126 => Err("number overflow"),
+            m if m < -129 => Err("number underflow"),
+            _ => Ok(()),
+        }
+    }
+
+    fn normalized(negative: bool, mut digits: Vec<u8>, mut exponent: i64) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+            exponent += 1;
+        }
+        let leading = digits.iter().take_while(|d| **d == 0).count();
+        digits.drain(..leading);
+        Self {
+            negative: negative && !digits.is_empty(),
+            digits,
+            exponent,
+        }
+    }
+
+    /// The power of ten just above the most significant digit.
+    fn magnitude(&self) -> i64 {
+        self.digits.len() as i64 + self.exponent
+    }
+
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        match (self.digits.is_empty(), other.digits.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            // Without trailing zeros, a shorter run of the same digits is the smaller number
+            _ => (self.magnitude().cmp(&other.magnitude()))
+                .then_with(|| self.digits.cmp(&other.digits)),
+        }
+    }
+
+    /// The digits scaled to `10^exponent`, which must not exceed the number's own exponent.
+    fn scaled_to(&self, exponent: i64) -> Vec<u8> {
+        let mut digits = self.digits.clone();
+        digits.resize(digits.len() + (self.exponent - exponent) as usize, 0);
+        digits
+    }
+
+    fn add(self, other: Self) -> Self {
+        let exponent = self.exponent.min(other.exponent);
+        let (larger, smaller) = match self.cmp_magnitude(&other) {
+            Ordering::Less => (other, self),
+            _ => (self, other),
+        };
+        let a = larger.scaled_to(exponent);
+        let mut b = smaller.scaled_to(exponent);
+        // Right-align the smaller magnitude under the larger one
+        let mut padded = vec![0; a.len().saturating_sub(b.len())];
+        padded.append(&mut b);
+
+        let mut digits = vec![0; a.len() + 1];
+        let mut carry = 0i8;
+        for i in (0..a.len()).rev() {
+            let d = if larger.negative == smaller.negative {
+                a[i] as i8 + padded[i] as i8 + carry
+            } else {
+                a[i] as i8 - padded[i] as i8 + carry
+            };
+            carry = d.div_euclid(10);
+            digits[i + 1] = d.rem_euclid(10) as u8;
+        }
+        digits[0] = carry as u8;
+        Self::normalized(larger.negative, digits, exponent)
+    }
+
+    /// Formats the number the way DynamoDB stores it, failing for numbers it can't store.
+    fn to_dynamodb_string(&self) -> Result<String, &'static str> {
+        if self.digits.is_empty() {
+            return Ok("0".to_string());
+        }
+        if self.digits.len() > Self::MAX_DIGITS {
+            return Err("the result has more than 38 significant digits");
+        }
+        self.check_range()?;
+        let digits: String = self.digits.iter().map(|d| char::from(b'0' + d)).collect();
+        let sign = if self.negative { "-" } else { "" };
+        let magnitude = self.magnitude();
+        Ok(if self.exponent >= 0 {
+            format!("{sign}{digits}{}", "0".repeat(self.exponent as usize))
+        } else if magnitude > 0 {
+            let (int, frac) = digits.split_at(magnitude as usize);
+            format!("{sign}{int}.{frac}")
+        } else {
+            format!("{sign}0.{}{digits}", "0".repeat(-magnitude as usize))
+        })
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.cmp_magnitude(other),
+            (true, true) => other.cmp_magnitude(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_numbers, compare_numbers, subtract_numbers};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_compare_numbers() {
+        assert_eq!(compare_numbers("1e2", "100.0"), Some(Ordering::Equal));
+        assert_eq!(compare_numbers("-0", "0"), Some(Ordering::Equal));
+        assert_eq!(compare_numbers("0.001", ".01"), Some(Ordering::Less));
+        assert_eq!(compare_numbers("-2", "-10"), Some(Ordering::Greater));
+        // Beyond f64 and i128 precision
+        assert_eq!(
+            compare_numbers(
+                "12345678901234567890123456789012345678",
+                "12345678901234567890123456789012345679"
+            ),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_numbers("0.10000000000000000000000000000000000001", "0.1"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(compare_numbers("1", "one"), None);
+        assert_eq!(compare_numbers("NaN", "NaN"), None);
+        // Exponents that would overflow are not numbers
+        assert_eq!(compare_numbers("10e9223372036854775807", "1"), None);
+        assert_eq!(compare_numbers("1", "0.1e-9223372036854775808"), None);
+        assert_eq!(compare_numbers("1e200", "1e199"), Some(Ordering::Greater));
+        assert!(add_numbers("10e9223372036854775807", "1").is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(add_numbers("0.1", "0.2").unwrap(), "0.3");
+        assert_eq!(add_numbers("99", "1").unwrap(), "100");
+        assert_eq!(add_numbers("1.5", "-3").unwrap(), "-1.5");
+        assert_eq!(add_numbers("-1.5", "1.5").unwrap(), "0");
+        assert_eq!(subtract_numbers("1", "0.001").unwrap(), "0.999");
+        assert_eq!(subtract_numbers("-1", "-1e3").unwrap(), "999");
+        assert_eq!(subtract_numbers("0", "0").unwrap(), "0");
+        assert_eq!(
+            add_numbers("12345678901234567890123456789012345678", "1").unwrap(),
+            "12345678901234567890123456789012345679"
+        );
+        assert_eq!(add_numbers("1e-20", "0").unwrap(), "0.00000000000000000001");
+        assert!(add_numbers("1e30", "1e-30").is_err(), "more than 38 digits");
+        assert!(add_numbers("9e125", "9e125").is_err(), "overflow");
+        assert!(add_numbers("1", "x").is_err());
+        assert!(
+            add_numbers("1e1000000000", "1").is_err(),
+            "operand out of range"
+        );
+    }
+}
@@ -2,18 +2,32 @@ use std::sync::Arc;
 
 use aws_config::Region;
 use aws_credential_types::Credentials;
-use aws_sdk_dynamodb::types::{ConditionCheck, Delete, Put, TransactWriteItem, Update};
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
+use aws_sdk_dynamodb::types::{
+    AttributeValue, ConditionCheck, Delete, ReturnValuesOnConditionCheckFailure, TransactGetItem,
+    TransactWriteItem, Update,
+};
 use aws_types::sdk_config::SharedCredentialsProvider;
 
 pub mod prelude {
     pub use crate::retry::{ExponentialBackoff, RetryStrategy};
     pub use crate::traits::{Item, NestedItem, Table};
+    pub use crate::update::{ReturnNew, ReturnOld, UpdatedNew, UpdatedOld};
     pub use aymond_derive::aymond;
 }
 
 pub mod condition;
+pub mod count;
+pub mod cursor;
 pub mod error;
+mod eval;
+pub mod marshal;
+pub mod projection;
 pub mod retry;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod shim;
 pub mod traits;
 pub mod update;
@@ -21,11 +35,38 @@ pub mod update;
 pub struct Tx<'a> {
     client: &'a Aymond,
     transact_items: Vec<TransactWriteItem>,
+    operations: Vec<TxOperation>,
+    retry_strategy: Option<retry::RetryStrategy>,
+    client_request_token: Option<String>,
+    /// Whether to generate a token when none is set and the retry strategy can retry.
+    auto_token: bool,
+    /// The first operation that couldn't be encoded, failing the transaction before it's sent.
+    error: Option<error::EncodeError>,
+}
+
+/// What a transaction remembers about each operation, to report it if the transaction fails.
+struct TxOperation {
+    label: Option<String>,
+    table: String,
+    key: HashMap<String, AttributeValue>,
+}
+
+/// A `TransactGetItems` request, reading a consistent snapshot of items from one or more tables.
+///
+/// `T` is the tuple of results, which grows by one `Option` per call to [`TxGet::get`].
+pub struct TxGet<'a, T> {
+    client: &'a Aymond,
+    transact_items: Vec<TransactGetItem>,
+    results: std::marker::PhantomData<T>,
+    retry_strategy: Option<retry::RetryStrategy>,
+    /// The first read that couldn't be encoded, failing the request before it's sent.
+    error: Option<error::EncodeError>,
 }
 
 pub struct Aymond {
     pub client: Arc<aws_sdk_dynamodb::Client>,
     pub retry_strategy: retry::RetryStrategy,
+    pub cursor_signing_key: Option<Arc<[u8]>>,
 }
 
 impl Clone for Aymond {
@@ -33,6 +74,7 @@ impl Clone for Aymond {
         Self {
             client: self.client.clone(),
             retry_strategy: self.retry_strategy.clone(),
+            cursor_signing_key: self.cursor_signing_key.clone(),
         }
     }
 }
@@ -42,6 +84,10 @@ impl std::fmt::Debug for Aymond {
         f.debug_struct("Aymond")
             .field("client", &self.client)
             .field("retry_strategy", &"<closure>")
+            .field(
+                "cursor_signing_key",
+                &self.cursor_signing_key.as_ref().map(|_| "<redacted>"),
+            )
             .finish()
     }
 }
@@ -80,10 +126,19 @@ impl<'a> Aymond {
         Self::new_with_client(client)
     }
 
+    /// Wraps a client, turning off its own retries: requests are retried by the
+    /// [retry strategy](Self::with_retry_strategy) instead, which knows which are safe to send
+    /// again.
     pub fn new_with_client(client: ::std::sync::Arc<aws_sdk_dynamodb::Client>) -> Self {
+        let config = client
+            .config()
+            .to_builder()
+            .retry_config(aws_sdk_dynamodb::config::retry::RetryConfig::disabled())
+            .build();
         Self {
-            client,
+            client: Arc::new(aws_sdk_dynamodb::Client::from_conf(config)),
             retry_strategy: retry::default_retry_strategy(),
+            cursor_signing_key: None,
         }
     }
 
@@ -92,39 +147,190 @@ impl<'a> Aymond {
         self
     }
 
+    /// Signs pagination cursors with HMAC-SHA256 so clients can't tamper with them.
+    pub fn with_cursor_signing_key(mut self, key: impl AsRef<[u8]>) -> Self {
+        self.cursor_signing_key = Some(Arc::from(key.as_ref()));
+        self
+    }
+
     pub fn tx(&'a self) -> Tx<'a> {
         Tx {
             client: self,
             transact_items: vec![],
+            operations: vec![],
+            retry_strategy: None,
+            client_request_token: None,
+            auto_token: true,
+            error: None,
+        }
+    }
+
+    pub fn tx_get(&'a self) -> TxGet<'a, ()> {
+        TxGet {
+            client: self,
+            transact_items: vec![],
+            results: std::marker::PhantomData,
+            retry_strategy: None,
+            error: None,
         }
     }
 }
 
 impl<'a> Tx<'a> {
-    pub fn put(mut self, put: impl Into<Put>) -> Self {
-        self.transact_items
-            .push(TransactWriteItem::builder().put(put.into()).build());
+    /// Adds a put, from a table's `put()` builder or a raw [`Put`](aws_sdk_dynamodb::types::Put).
+    ///
+    /// This takes a [`TxPut`](traits::TxPut) rather than any `impl Into<Put>`, so that a canceled
+    /// transaction can report the key of the item each put wrote. Other types that convert into
+    /// a `Put` can implement `TxPut`, returning their table's key schema, or be converted with
+    /// `.into()` first.
+    pub fn put<P: traits::TxPut>(self, put: P) -> Self {
+        let put = match put.try_into() {
+            Ok(put) => put,
+            Err(err) => return self.fail(err.into()),
+        };
+        let key = P::key_schemas()
+            .iter()
+            .filter_map(|k| {
+                let name = k.attribute_name();
+                put.item.get(name).map(|v| (name.to_string(), v.clone()))
+            })
+            .collect();
+        self.push(put.table_name.clone(), key, |b| b.put(put))
+    }
+
+    pub fn delete<D>(self, delete: D) -> Self
+    where
+        D: TryInto<Delete, Error: Into<error::EncodeError>>,
+    {
+        let delete = match delete.try_into() {
+            Ok(delete) => delete,
+            Err(err) => return self.fail(err.into()),
+        };
+        self.push(delete.table_name.clone(), delete.key.clone(), |b| {
+            b.delete(delete)
+        })
+    }
+
+    pub fn update<U>(self, update: U) -> Self
+    where
+        U: TryInto<Update, Error: Into<error::EncodeError>>,
+    {
+        let update = match update.try_into() {
+            Ok(update) => update,
+            Err(err) => return self.fail(err.into()),
+        };
+        self.push(update.table_name.clone(), update.key.clone(), |b| {
+            b.update(update)
+        })
+    }
+
+    pub fn condition_check<C>(self, condition_check: C) -> Self
+    where
+        C: TryInto<ConditionCheck, Error: Into<error::EncodeError>>,
+    {
+        let condition_check = match condition_check.try_into() {
+            Ok(condition_check) => condition_check,
+            Err(err) => return self.fail(err.into()),
+        };
+        self.push(
+            condition_check.table_name.clone(),
+            condition_check.key.clone(),
+            |b| b.condition_check(condition_check),
+        )
+    }
+
+    /// Labels the most recently added operation, to tell it apart in a [`error::TxError`].
+    ///
+    /// Does nothing before the first operation, which debug builds assert against.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.debug_assert_operation("label()");
+        if let Some(operation) = self.operations.last_mut() {
+            operation.label = Some(label.into());
+        }
         self
     }
 
-    pub fn delete(mut self, delete: impl Into<Delete>) -> Self {
-        self.transact_items
-            .push(TransactWriteItem::builder().delete(delete.into()).build());
+    /// Returns the current item in [`error::TxError`] if the condition of the most recently added
+    /// operation fails.
+    ///
+    /// Does nothing before the first operation, which debug builds assert against.
+    pub fn return_old_on_condition_failure(mut self) -> Self {
+        self.debug_assert_operation("return_old_on_condition_failure()");
+        let Some(item) = self.transact_items.last_mut() else {
+            return self;
+        };
+        let all_old = Some(ReturnValuesOnConditionCheckFailure::AllOld);
+        if let Some(put) = item.put.as_mut() {
+            put.return_values_on_condition_check_failure = all_old;
+        } else if let Some(delete) = item.delete.as_mut() {
+            delete.return_values_on_condition_check_failure = all_old;
+        } else if let Some(update) = item.update.as_mut() {
+            update.return_values_on_condition_check_failure = all_old;
+        } else if let Some(condition_check) = item.condition_check.as_mut() {
+            condition_check.return_values_on_condition_check_failure = all_old;
+        }
         self
     }
 
-    pub fn update(mut self, update: impl Into<Update>) -> Self {
-        self.transact_items
-            .push(TransactWriteItem::builder().update(update.into()).build());
+    /// Overrides the client's retry strategy for this request.
+    pub fn retry_strategy(mut self, s: retry::RetryStrategy) -> Self {
+        self.retry_strategy = Some(s);
+        self
+    }
+
+    /// Sets the `ClientRequestToken`, so that sending the same transaction again with the same
+    /// token within 10 minutes doesn't apply it twice.
+    pub fn idempotency_token(mut self, token: impl Into<String>) -> Self {
+        self.client_request_token = Some(token.into());
         self
     }
 
-    pub fn condition_check(mut self, condition_check: impl Into<ConditionCheck>) -> Self {
-        self.transact_items.push(
-            TransactWriteItem::builder()
-                .condition_check(condition_check.into())
-                .build(),
+    /// Generates a random UUID as the idempotency token, which is reused by every retry of this
+    /// transaction.
+    ///
+    /// This is the default when the retry strategy can retry, so that a transaction whose
+    /// response was lost can be sent again; calling it generates a token regardless.
+    pub fn auto_idempotency_token(mut self) -> Self {
+        self.client_request_token = random_uuid().or(self.client_request_token);
+        self
+    }
+
+    /// Sends the transaction without an idempotency token unless one is set with
+    /// [`Tx::idempotency_token`]. It's then not retried after a timeout or a lost response.
+    pub fn no_auto_idempotency_token(mut self) -> Self {
+        self.auto_token = false;
+        self
+    }
+
+    /// Checks that a modifier follows an operation. One that failed to encode isn't added, but
+    /// that fails the whole transaction anyway.
+    fn debug_assert_operation(&self, modifier: &str) {
+        debug_assert!(
+            !self.operations.is_empty() || self.error.is_some(),
+            "{} must follow an operation",
+            modifier
         );
+    }
+
+    /// Remembers the first operation that couldn't be encoded, to fail the transaction with.
+    fn fail(mut self, err: error::EncodeError) -> Self {
+        self.error.get_or_insert(err);
+        self
+    }
+
+    fn push<F>(mut self, table: String, key: HashMap<String, AttributeValue>, f: F) -> Self
+    where
+        F: FnOnce(
+            aws_sdk_dynamodb::types::builders::TransactWriteItemBuilder,
+        ) -> aws_sdk_dynamodb::types::builders::TransactWriteItemBuilder,
+    {
+        self.transact_items
+            .push(f(TransactWriteItem::builder()).build());
+        self.operations.push(TxOperation {
+            label: None,
+            table,
+            key,
+        });
         self
     }
 
@@ -142,28 +348,198 @@ impl<'a> Tx<'a> {
         F: FnOnce(aws_sdk_dynamodb::operation::transact_write_items::builders::TransactWriteItemsFluentBuilder)
         -> aws_sdk_dynamodb::operation::transact_write_items::builders::TransactWriteItemsFluentBuilder
     {
-        f(self.client.client.transact_write_items())
-            .set_transact_items(self.into())
-            .send()
-            .await
+        if let Some(err) = self.error {
+            return Err(aws_sdk_dynamodb::error::SdkError::construction_failure(err));
+        }
+        let strategy = self
+            .retry_strategy
+            .clone()
+            .unwrap_or_else(|| self.client.retry_strategy.clone());
+        let token = match &self.client_request_token {
+            None if self.auto_token && strategy(0).is_some() => random_uuid(),
+            token => token.clone(),
+        };
+        let req = f(self
+            .client
+            .client
+            .transact_write_items()
+            .set_client_request_token(token))
+        .set_transact_items(self.into());
+        // With a token, DynamoDB applies a transaction sent twice only once
+        if req.get_client_request_token().is_some() {
+            retry::retry(&strategy, || req.clone().send()).await
+        } else {
+            retry::retry_write(&strategy, || req.clone().send()).await
+        }
     }
 
-    pub async fn send(
+    pub async fn send(mut self) -> Result<(), error::TxError> {
+        let operations = std::mem::take(&mut self.operations);
+        match self.raw(|r| r).await {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                let reasons = match err.as_service_error() {
+                    Some(TransactWriteItemsError::TransactionCanceledException(e)) => {
+                        e.cancellation_reasons().to_vec()
+                    }
+                    Some(TransactWriteItemsError::IdempotentParameterMismatchException(e)) => {
+                        return Err(error::TxError::IdempotentParameterMismatch {
+                            message: e.message().map(str::to_string),
+                        });
+                    }
+                    _ => return Err(err.into()),
+                };
+                Err(error::TxError::Canceled(
+                    operations
+                        .into_iter()
+                        .zip(reasons)
+                        .map(|(op, reason)| error::TxCancellation {
+                            label: op.label,
+                            table: op.table,
+                            key: op.key,
+                            code: reason.code().unwrap_or("None").into(),
+                            message: reason.message,
+                            item: reason.item,
+                        })
+                        .collect(),
+                ))
+            }
+        }
+    }
+}
+
+/// A random (version 4) UUID from the operating system's random number generator, or `None` if
+/// it's unavailable.
+fn random_uuid() -> Option<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).ok()?;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    Some(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    ))
+}
+
+impl<'a> From<Tx<'a>> for Option<Vec<TransactWriteItem>> {
+    fn from(val: Tx<'a>) -> Self {
+        Some(val.transact_items)
+    }
+}
+
+impl<'a, T: traits::TxGetResults> TxGet<'a, T> {
+    pub fn get<G>(mut self, get: G) -> TxGet<'a, <T as traits::TxGetPush<G::Output>>::Output>
+    where
+        G: traits::TxGetRequest,
+        T: traits::TxGetPush<G::Output>,
+    {
+        match get.try_into() {
+            Ok(get) => self.transact_items.push(get),
+            Err(err) => {
+                self.error.get_or_insert(err.into());
+            }
+        }
+        TxGet {
+            client: self.client,
+            transact_items: self.transact_items,
+            results: std::marker::PhantomData,
+            retry_strategy: self.retry_strategy,
+            error: self.error,
+        }
+    }
+
+    /// Overrides the client's retry strategy for this request.
+    pub fn retry_strategy(mut self, s: retry::RetryStrategy) -> Self {
+        self.retry_strategy = Some(s);
+        self
+    }
+
+    pub async fn raw<F>(
         self,
+        f: F,
     ) -> Result<
-        (),
+        aws_sdk_dynamodb::operation::transact_get_items::TransactGetItemsOutput,
         aws_sdk_dynamodb::error::SdkError<
-            aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError,
+            aws_sdk_dynamodb::operation::transact_get_items::TransactGetItemsError,
             aws_sdk_dynamodb::config::http::HttpResponse,
         >,
+    >
+    where
+        F: FnOnce(
+            aws_sdk_dynamodb::operation::transact_get_items::builders::TransactGetItemsFluentBuilder,
+        ) -> aws_sdk_dynamodb::operation::transact_get_items::builders::TransactGetItemsFluentBuilder,
+    {
+        if let Some(err) = self.error {
+            return Err(aws_sdk_dynamodb::error::SdkError::construction_failure(err));
+        }
+        let strategy = self
+            .retry_strategy
+            .unwrap_or_else(|| self.client.retry_strategy.clone());
+        let req = f(self.client.client.transact_get_items())
+            .set_transact_items(Some(self.transact_items));
+        retry::retry(&strategy, || req.clone().send()).await
+    }
+
+    pub async fn send(
+        self,
+    ) -> Result<
+        T,
+        error::RequestError<aws_sdk_dynamodb::operation::transact_get_items::TransactGetItemsError>,
     > {
-        self.raw(|r| r).await?;
-        Ok(())
+        let res = self.raw(|r| r).await?;
+        Ok(T::decode(res.responses())?)
     }
 }
 
-impl<'a> From<Tx<'a>> for Option<Vec<TransactWriteItem>> {
-    fn from(val: Tx<'a>) -> Self {
-        Some(val.transact_items)
+#[cfg(test)]
+mod tests {
+    use super::{Aymond, random_uuid};
+    use crate::error::EncodeError;
+    use aws_sdk_dynamodb::types::Delete;
+
+    struct Unencodable;
+
+    impl TryFrom<Unencodable> for Delete {
+        type Error = EncodeError;
+
+        fn try_from(_: Unencodable) -> Result<Self, Self::Error> {
+            Err(EncodeError::new("out of range"))
+        }
+    }
+
+    #[test]
+    fn test_tx_modifiers_after_failed_operation() {
+        let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+        let tx = aymond
+            .tx()
+            .delete(Unencodable)
+            .label("delete")
+            .return_old_on_condition_failure();
+        assert!(tx.operations.is_empty());
+        assert!(tx.error.is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "label() must follow an operation")]
+    fn test_tx_label_without_operation() {
+        let aymond = Aymond::new_with_local_config("http://localhost:8000", "us-west-2");
+        let _ = aymond.tx().label("nothing");
+    }
+
+    #[test]
+    fn test_random_uuid() {
+        let uuid = random_uuid().unwrap();
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(&uuid[14..15], "4", "version 4");
+        assert!(
+            matches!(&uuid[19..20], "8" | "9" | "a" | "b"),
+            "RFC 4122 variant"
+        );
+        assert_ne!(uuid, random_uuid().unwrap());
     }
 }
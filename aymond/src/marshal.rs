@@ -0,0 +1,254 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::error::{DecodeError, EncodeError};
+
+/// Types that convert themselves into a single attribute value, such as nested items and enums.
+///
+/// Generated code falls back to this trait for any attribute type it doesn't recognize.
+pub trait IntoAttributeValue {
+    fn into_attribute_value(self) -> Result<AttributeValue, EncodeError>;
+}
+
+/// The return type of a `with` module's `to_attribute_value`, which is either an
+/// `AttributeValue` or a `Result` whose error is `Display`.
+pub trait IntoEncodeResult {
+    fn into_encode_result(self) -> Result<AttributeValue, EncodeError>;
+}
+
+impl IntoEncodeResult for AttributeValue {
+    fn into_encode_result(self) -> Result<AttributeValue, EncodeError> {
+        Ok(self)
+    }
+}
+
+impl<E: Display> IntoEncodeResult for Result<AttributeValue, E> {
+    fn into_encode_result(self) -> Result<AttributeValue, EncodeError> {
+        self.map_err(EncodeError::new)
+    }
+}
+
+/// Turns the error of a `with` module's `from_attribute_value` into a `DecodeError`. A
+/// `DecodeError`, such as the ones aymond's own converters return, is kept as is, and any other
+/// error becomes the reason `expected` couldn't be decoded.
+pub fn converter_error<E: Display + 'static>(expected: &'static str, e: E) -> DecodeError {
+    let mut e = Some(e);
+    if let Some(err) = (&mut e as &mut dyn Any).downcast_mut::<Option<DecodeError>>() {
+        return err.take().unwrap();
+    }
+    DecodeError::invalid(expected, e.unwrap())
+}
+
+/// The decoding counterpart to [`IntoAttributeValue`].
+pub trait FromAttributeValue: Sized {
+    /// The DynamoDB type descriptor values are stored as, reported when the attribute is missing.
+    const TYPE_NAME: &'static str;
+
+    fn from_attribute_value(v: &AttributeValue) -> Result<Self, DecodeError>;
+}
+
+/// The type a value decodes into in an item's `{Name}Partial`, where a response may only carry
+/// some of a nested item's attributes, such as `UPDATED_NEW` after setting `address.city`.
+///
+/// Nested items decode into their own `{Name}Partial`, while other values decode whole.
+pub trait PartialValue {
+    type Partial: FromAttributeValue;
+}
+
+/// Implements the condition and update value traits for a type in terms of its
+/// [`IntoAttributeValue`] impl, and names its scalar condition and update paths and its partial
+/// type, so it can be compared and set through typed paths.
+#[allow(unused_macros)]
+macro_rules! impl_expression_values {
+    ($t:ty) => {
+        impl $crate::condition::IntoConditionValue for $t {
+            fn into_condition_value(self) -> Result<AttributeValue, $crate::error::EncodeError> {
+                $crate::marshal::IntoAttributeValue::into_attribute_value(self)
+            }
+        }
+
+        impl $crate::update::IntoUpdateValue for $t {
+            fn into_update_value(self) -> Result<AttributeValue, $crate::error::EncodeError> {
+                $crate::marshal::IntoAttributeValue::into_attribute_value(self)
+            }
+        }
+
+        impl $crate::marshal::PartialValue for $t {
+            type Partial = $t;
+        }
+
+        impl $crate::condition::HasConditionPath for $t {
+            type Path = $crate::condition::ScalarConditionPath<$t>;
+        }
+
+        impl $crate::update::HasUpdatePath for $t {
+            type Path<O> = $crate::update::ScalarUpdatePath<$t, O>;
+        }
+    };
+}
+
+#[cfg(feature = "chrono")]
+pub mod chrono;
+#[cfg(feature = "rust_decimal")]
+pub mod rust_decimal;
+#[cfg(feature = "serde_json")]
+pub mod serde_json;
+#[cfg(feature = "time")]
+pub mod time;
+#[cfg(feature = "uuid")]
+pub mod uuid;
+
+/// Checks that a key attribute's [`FromAttributeValue::TYPE_NAME`] is one DynamoDB allows for
+/// keys. Generated code evaluates this in a `const`, so other types fail to compile.
+pub const fn key_type_name(name: &'static str) -> &'static str {
+    match name.as_bytes() {
+        b"S" | b"N" | b"B" => name,
+        _ => panic!("key attributes must be stored as S, N or B"),
+    }
+}
+
+/// Encodes a float as a number (`N`). DynamoDB has no representation for NaN or infinity, so
+/// those fail to encode rather than being rejected by DynamoDB.
+pub fn encode_float<T: Into<f64> + Display + Copy>(v: T) -> Result<AttributeValue, EncodeError> {
+    if v.into().is_finite() {
+        Ok(AttributeValue::N(v.to_string()))
+    } else {
+        Err(EncodeError::new(format!(
+            "{v} can't be stored as a DynamoDB number"
+        )))
+    }
+}
+
+/// Returns the DynamoDB type descriptor (`S`, `N`, `M`, ...) of an attribute value.
+pub fn type_name(v: &AttributeValue) -> &'static str {
+    match v {
+        AttributeValue::B(_) => "B",
+        AttributeValue::Bool(_) => "BOOL",
+        AttributeValue::Bs(_) => "BS",
+        AttributeValue::L(_) => "L",
+        AttributeValue::M(_) => "M",
+        AttributeValue::N(_) => "N",
+        AttributeValue::Ns(_) => "NS",
+        AttributeValue::Null(_) => "NULL",
+        AttributeValue::S(_) => "S",
+        AttributeValue::Ss(_) => "SS",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Looks up a required attribute, failing if it is missing.
+pub fn required<'a>(
+    map: &'a HashMap<String, AttributeValue>,
+    name: &str,
+    expected: &'static str,
+) -> Result<&'a AttributeValue, DecodeError> {
+    map.get(name)
+        .ok_or_else(|| DecodeError::missing(expected).at_attr(name))
+}
+
+/// Looks up an optional attribute. Missing and `NULL` attributes are both treated as absent.
+pub fn optional<'a>(
+    map: &'a HashMap<String, AttributeValue>,
+    name: &str,
+) -> Option<&'a AttributeValue> {
+    map.get(name).filter(|v| !v.is_null())
+}
+
+pub fn decode_s(v: &AttributeValue) -> Result<String, DecodeError> {
+    v.as_s()
+        .map(|s| s.to_string())
+        .map_err(|v| DecodeError::mismatch("S", type_name(v)))
+}
+
+pub fn decode_n<T>(v: &AttributeValue) -> Result<T, DecodeError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let n = v
+        .as_n()
+        .map_err(|v| DecodeError::mismatch("N", type_name(v)))?;
+    n.parse().map_err(|e| DecodeError::invalid("N", e))
+}
+
+pub fn decode_bool(v: &AttributeValue) -> Result<bool, DecodeError> {
+    v.as_bool()
+        .copied()
+        .map_err(|v| DecodeError::mismatch("BOOL", type_name(v)))
+}
+
+pub fn decode_b(v: &AttributeValue) -> Result<Vec<u8>, DecodeError> {
+    v.as_b()
+        .map(|b| b.clone().into_inner())
+        .map_err(|v| DecodeError::mismatch("B", type_name(v)))
+}
+
+pub fn decode_ss<C: FromIterator<String>>(v: &AttributeValue) -> Result<C, DecodeError> {
+    v.as_ss()
+        .map(|ss| ss.iter().cloned().collect())
+        .map_err(|v| DecodeError::mismatch("SS", type_name(v)))
+}
+
+pub fn decode_ns<T, C>(v: &AttributeValue) -> Result<C, DecodeError>
+where
+    T: FromStr,
+    T::Err: Display,
+    C: FromIterator<T>,
+{
+    let ns = v
+        .as_ns()
+        .map_err(|v| DecodeError::mismatch("NS", type_name(v)))?;
+    ns.iter()
+        .map(|n| n.parse().map_err(|e| DecodeError::invalid("NS", e)))
+        .collect()
+}
+
+pub fn decode_bs<C: FromIterator<Vec<u8>>>(v: &AttributeValue) -> Result<C, DecodeError> {
+    v.as_bs()
+        .map(|bs| bs.iter().map(|b| b.clone().into_inner()).collect())
+        .map_err(|v| DecodeError::mismatch("BS", type_name(v)))
+}
+
+/// Decodes each element of a list attribute with `f`, tagging errors with the element index.
+pub fn decode_l<T, C, F>(v: &AttributeValue, f: F) -> Result<C, DecodeError>
+where
+    C: FromIterator<T>,
+    F: Fn(&AttributeValue) -> Result<T, DecodeError>,
+{
+    let l = v
+        .as_l()
+        .map_err(|v| DecodeError::mismatch("L", type_name(v)))?;
+    l.iter()
+        .enumerate()
+        .map(|(i, e)| f(e).map_err(|err| err.at_index(i)))
+        .collect()
+}
+
+pub fn as_m(v: &AttributeValue) -> Result<&HashMap<String, AttributeValue>, DecodeError> {
+    v.as_m()
+        .map_err(|v| DecodeError::mismatch("M", type_name(v)))
+}
+
+/// Decodes each value of a map attribute with `f`, tagging errors with the map key.
+pub fn decode_map<T, C, F>(v: &AttributeValue, f: F) -> Result<C, DecodeError>
+where
+    C: FromIterator<(String, T)>,
+    F: Fn(&AttributeValue) -> Result<T, DecodeError>,
+{
+    as_m(v)?
+        .iter()
+        .map(|(k, e)| f(e).map(|e| (k.clone(), e)).map_err(|err| err.at_attr(k)))
+        .collect()
+}
+
+/// Decodes a map attribute into a nested item.
+pub fn decode_m<T>(v: &AttributeValue) -> Result<T, DecodeError>
+where
+    T: for<'a> TryFrom<&'a HashMap<String, AttributeValue>, Error = DecodeError>,
+{
+    T::try_from(as_m(v)?)
+}
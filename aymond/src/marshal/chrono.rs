@@ -0,0 +1,122 @@
+//! `chrono::DateTime<Utc>` support, stored as an RFC 3339 string (`S`) by default.
+//!
+//! The string always has nine fractional digits, so that timestamps sort the same way as their
+//! strings, e.g. in sort keys and `between` conditions.
+//!
+//! Use `#[aymond(with = "aymond::marshal::chrono::epoch_seconds", scalar = "N")]` (or
+//! `epoch_millis`) to store a field as a Unix timestamp instead.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{DateTime, Datelike, SecondsFormat, Utc};
+
+use crate::error::{DecodeError, EncodeError};
+use crate::marshal::{FromAttributeValue, IntoAttributeValue, decode_n, decode_s};
+
+impl IntoAttributeValue for DateTime<Utc> {
+    /// Fails for years RFC 3339 can't represent, which would neither sort nor decode.
+    fn into_attribute_value(self) -> Result<AttributeValue, EncodeError> {
+        if !(0..=9999).contains(&self.year()) {
+            return Err(EncodeError::new(
+                "RFC 3339 only represents the years 0000 to 9999",
+            ));
+        }
+        Ok(AttributeValue::S(
+            self.to_rfc3339_opts(SecondsFormat::Nanos, true),
+        ))
+    }
+}
+
+impl FromAttributeValue for DateTime<Utc> {
+    const TYPE_NAME: &'static str = "S";
+
+    fn from_attribute_value(v: &AttributeValue) -> Result<Self, DecodeError> {
+        DateTime::parse_from_rfc3339(&decode_s(v)?)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(|e| DecodeError::invalid("S", e))
+    }
+}
+
+impl_expression_values!(DateTime<Utc>);
+
+/// Stores a `DateTime<Utc>` as whole seconds since the Unix epoch (`N`).
+pub mod epoch_seconds {
+    use super::*;
+
+    pub fn to_attribute_value(v: &DateTime<Utc>) -> AttributeValue {
+        AttributeValue::N(v.timestamp().to_string())
+    }
+
+    pub fn from_attribute_value(v: &AttributeValue) -> Result<DateTime<Utc>, DecodeError> {
+        let secs: i64 = decode_n(v)?;
+        DateTime::from_timestamp(secs, 0)
+            .ok_or_else(|| DecodeError::invalid("N", "timestamp out of range"))
+    }
+}
+
+/// Stores a `DateTime<Utc>` as milliseconds since the Unix epoch (`N`).
+pub mod epoch_millis {
+    use super::*;
+
+    pub fn to_attribute_value(v: &DateTime<Utc>) -> AttributeValue {
+        AttributeValue::N(v.timestamp_millis().to_string())
+    }
+
+    pub fn from_attribute_value(v: &AttributeValue) -> Result<DateTime<Utc>, DecodeError> {
+        let millis: i64 = decode_n(v)?;
+        DateTime::from_timestamp_millis(millis)
+            .ok_or_else(|| DecodeError::invalid("N", "timestamp out of range"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strings_sort_like_timestamps() {
+        let mut timestamps: Vec<DateTime<Utc>> = [
+            "2024-05-01T10:00:00Z",
+            "2024-05-01T10:00:00.5Z",
+            "2024-05-01T10:00:00.05Z",
+            "2024-05-01T10:00:00.123456789Z",
+            "2024-05-01T09:59:59.999Z",
+        ]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+        timestamps.sort();
+
+        let strings: Vec<String> = timestamps
+            .iter()
+            .map(|t| match t.into_attribute_value().unwrap() {
+                AttributeValue::S(s) => s,
+                other => panic!("expected S, found {other:?}"),
+            })
+            .collect();
+        let mut sorted = strings.clone();
+        sorted.sort();
+        assert_eq!(strings, sorted);
+        assert_eq!(strings[1], "2024-05-01T10:00:00.000000000Z");
+
+        for (s, t) in strings.iter().zip(&timestamps) {
+            assert_eq!(
+                &DateTime::<Utc>::from_attribute_value(&AttributeValue::S(s.clone())).unwrap(),
+                t
+            );
+        }
+    }
+
+    #[test]
+    fn test_years_outside_rfc_3339_are_rejected() {
+        let max: DateTime<Utc> = "9999-12-31T23:59:59.999999999Z".parse().unwrap();
+        assert!(max.into_attribute_value().is_ok());
+
+        let err = (max + chrono::Duration::nanoseconds(1))
+            .into_attribute_value()
+            .unwrap_err();
+        assert!(err.reason.contains("0000 to 9999"));
+        let before_zero = DateTime::<Utc>::from_timestamp(-62_167_219_201, 0).unwrap();
+        assert_eq!(before_zero.year(), -1);
+        assert!(before_zero.into_attribute_value().is_err());
+    }
+}
@@ -0,0 +1,36 @@
+//! `rust_decimal::Decimal` support, stored as an exact number (`N`).
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use rust_decimal::Decimal;
+
+use crate::error::{DecodeError, EncodeError};
+use crate::marshal::{FromAttributeValue, IntoAttributeValue};
+use crate::update::IntoUpdateNumberValue;
+
+impl IntoAttributeValue for Decimal {
+    fn into_attribute_value(self) -> Result<AttributeValue, EncodeError> {
+        self.into_update_number_value()
+    }
+}
+
+impl FromAttributeValue for Decimal {
+    const TYPE_NAME: &'static str = "N";
+
+    fn from_attribute_value(v: &AttributeValue) -> Result<Self, DecodeError> {
+        let n = v
+            .as_n()
+            .map_err(|v| DecodeError::mismatch("N", crate::marshal::type_name(v)))?;
+        // DynamoDB may return numbers in scientific notation
+        Decimal::from_str_exact(n)
+            .or_else(|_| Decimal::from_scientific(n))
+            .map_err(|e| DecodeError::invalid("N", e))
+    }
+}
+
+impl_expression_values!(Decimal);
+
+impl IntoUpdateNumberValue for Decimal {
+    fn into_update_number_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::N(self.normalize().to_string()))
+    }
+}
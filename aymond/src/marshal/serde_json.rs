@@ -0,0 +1,73 @@
+//! `serde_json::Value` support, stored as the equivalent native DynamoDB document: objects as
+//! `M`, arrays as `L`, and scalars as `S`, `N`, `BOOL` or `NULL`.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use serde_json::{Map, Number, Value};
+
+use crate::error::{DecodeError, EncodeError};
+use crate::marshal::{FromAttributeValue, IntoAttributeValue, type_name};
+
+impl IntoAttributeValue for Value {
+    fn into_attribute_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(to_document(self))
+    }
+}
+
+/// Converts a JSON value into the equivalent document, which can't fail.
+pub(crate) fn to_document(v: Value) -> AttributeValue {
+    match v {
+        Value::Null => AttributeValue::Null(true),
+        Value::Bool(b) => AttributeValue::Bool(b),
+        Value::Number(n) => AttributeValue::N(n.to_string()),
+        Value::String(s) => AttributeValue::S(s),
+        Value::Array(a) => AttributeValue::L(a.into_iter().map(to_document).collect()),
+        Value::Object(o) => {
+            AttributeValue::M(o.into_iter().map(|(k, e)| (k, to_document(e))).collect())
+        }
+    }
+}
+
+fn number(n: &str) -> Result<Value, DecodeError> {
+    n.parse::<Number>()
+        .map(Value::Number)
+        .map_err(|e| DecodeError::invalid("N", e))
+}
+
+impl FromAttributeValue for Value {
+    const TYPE_NAME: &'static str = "M";
+
+    fn from_attribute_value(v: &AttributeValue) -> Result<Self, DecodeError> {
+        match v {
+            AttributeValue::Null(_) => Ok(Value::Null),
+            AttributeValue::Bool(b) => Ok(Value::Bool(*b)),
+            AttributeValue::N(n) => number(n),
+            AttributeValue::S(s) => Ok(Value::String(s.clone())),
+            AttributeValue::Ss(ss) => Ok(Value::Array(
+                ss.iter().map(|s| Value::String(s.clone())).collect(),
+            )),
+            AttributeValue::Ns(ns) => ns
+                .iter()
+                .map(|n| number(n))
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+            AttributeValue::L(l) => l
+                .iter()
+                .enumerate()
+                .map(|(i, e)| Value::from_attribute_value(e).map_err(|err| err.at_index(i)))
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+            AttributeValue::M(m) => m
+                .iter()
+                .map(|(k, e)| {
+                    Value::from_attribute_value(e)
+                        .map(|e| (k.clone(), e))
+                        .map_err(|err| err.at_attr(k))
+                })
+                .collect::<Result<Map<_, _>, _>>()
+                .map(Value::Object),
+            other => Err(DecodeError::mismatch("M", type_name(other))),
+        }
+    }
+}
+
+impl_expression_values!(Value);
@@ -0,0 +1,110 @@
+//! `time::OffsetDateTime` support, stored as an RFC 3339 string (`S`) in UTC by default.
+//!
+//! Use `#[aymond(with = "aymond::marshal::time::epoch_seconds", scalar = "N")]` to store a field
+//! as a Unix timestamp instead.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use time::format_description::well_known::Rfc3339;
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::error::{DecodeError, EncodeError};
+use crate::marshal::{FromAttributeValue, IntoAttributeValue, decode_n, decode_s};
+
+impl IntoAttributeValue for OffsetDateTime {
+    /// Writes the time in UTC with nine fractional digits, so that strings sort like the times
+    /// they represent. Fails for years RFC 3339 can't represent.
+    fn into_attribute_value(self) -> Result<AttributeValue, EncodeError> {
+        let utc = self
+            .checked_to_offset(UtcOffset::UTC)
+            .filter(|utc| (0..=9999).contains(&utc.year()))
+            .ok_or_else(|| EncodeError::new("RFC 3339 only represents the years 0000 to 9999"))?;
+        Ok(AttributeValue::S(format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}Z",
+            utc.year(),
+            u8::from(utc.month()),
+            utc.day(),
+            utc.hour(),
+            utc.minute(),
+            utc.second(),
+            utc.nanosecond()
+        )))
+    }
+}
+
+impl FromAttributeValue for OffsetDateTime {
+    const TYPE_NAME: &'static str = "S";
+
+    fn from_attribute_value(v: &AttributeValue) -> Result<Self, DecodeError> {
+        OffsetDateTime::parse(&decode_s(v)?, &Rfc3339).map_err(|e| DecodeError::invalid("S", e))
+    }
+}
+
+impl_expression_values!(OffsetDateTime);
+
+/// Stores an `OffsetDateTime` as whole seconds since the Unix epoch (`N`).
+pub mod epoch_seconds {
+    use super::*;
+
+    pub fn to_attribute_value(v: &OffsetDateTime) -> AttributeValue {
+        AttributeValue::N(v.unix_timestamp().to_string())
+    }
+
+    pub fn from_attribute_value(v: &AttributeValue) -> Result<OffsetDateTime, DecodeError> {
+        let secs: i64 = decode_n(v)?;
+        OffsetDateTime::from_unix_timestamp(secs).map_err(|e| DecodeError::invalid("N", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    fn encode(t: OffsetDateTime) -> String {
+        match t.into_attribute_value().unwrap() {
+            AttributeValue::S(s) => s,
+            other => panic!("expected S, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_strings_sort_like_timestamps() {
+        let mut timestamps = vec![
+            datetime!(2024-05-01 10:00:00 UTC),
+            datetime!(2024-05-01 10:00:00.5 UTC),
+            datetime!(2024-05-01 10:00:00.05 UTC),
+            datetime!(2024-05-01 12:00:00.123456789 +02:00),
+            datetime!(2024-05-01 09:59:59.999 UTC),
+        ];
+        timestamps.sort();
+
+        let strings: Vec<String> = timestamps.iter().copied().map(encode).collect();
+        let mut sorted = strings.clone();
+        sorted.sort();
+        assert_eq!(strings, sorted);
+        assert_eq!(strings[1], "2024-05-01T10:00:00.000000000Z");
+        assert_eq!(strings[2], "2024-05-01T10:00:00.050000000Z");
+
+        for (s, t) in strings.iter().zip(&timestamps) {
+            let decoded = OffsetDateTime::from_attribute_value(&AttributeValue::S(s.clone()));
+            assert_eq!(&decoded.unwrap(), t);
+        }
+    }
+
+    #[test]
+    fn test_offsets_are_converted_to_utc() {
+        assert_eq!(
+            encode(datetime!(2024-05-01 00:00:00 -05:30:15)),
+            "2024-05-01T05:30:15.000000000Z"
+        );
+        let err = datetime!(9999-12-31 23:00:00 -05:00)
+            .into_attribute_value()
+            .unwrap_err();
+        assert!(err.reason.contains("0000 to 9999"));
+        assert!(
+            datetime!(-0001-01-01 00:00:00 UTC)
+                .into_attribute_value()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,41 @@
+//! `uuid::Uuid` support, stored as a hyphenated string (`S`) by default.
+//!
+//! Use `#[aymond(with = "aymond::marshal::uuid::binary", scalar = "B")]` to store a field as its
+//! 16 raw bytes instead.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use uuid::Uuid;
+
+use crate::error::{DecodeError, EncodeError};
+use crate::marshal::{FromAttributeValue, IntoAttributeValue, decode_b, decode_s};
+
+impl IntoAttributeValue for Uuid {
+    fn into_attribute_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::S(self.hyphenated().to_string()))
+    }
+}
+
+impl FromAttributeValue for Uuid {
+    const TYPE_NAME: &'static str = "S";
+
+    fn from_attribute_value(v: &AttributeValue) -> Result<Self, DecodeError> {
+        Uuid::parse_str(&decode_s(v)?).map_err(|e| DecodeError::invalid("S", e))
+    }
+}
+
+impl_expression_values!(Uuid);
+
+/// Stores a `Uuid` as its 16 raw bytes (`B`).
+pub mod binary {
+    use super::*;
+
+    pub fn to_attribute_value(v: &Uuid) -> AttributeValue {
+        AttributeValue::B(aws_sdk_dynamodb::primitives::Blob::new(
+            v.as_bytes().to_vec(),
+        ))
+    }
+
+    pub fn from_attribute_value(v: &AttributeValue) -> Result<Uuid, DecodeError> {
+        Uuid::from_slice(&decode_b(v)?).map_err(|e| DecodeError::invalid("B", e))
+    }
+}
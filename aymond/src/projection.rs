@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::error::DecodeError;
+use crate::traits::Item;
+
+/// An item attribute as seen by projections: its DynamoDB name and how to decode it.
+///
+/// Items generate one of these per field, which is how `#[aymond(projection_of = ...)]` checks
+/// that its fields exist on the item with the same type.
+pub struct ProjectedAttribute<T> {
+    pub name: &'static str,
+    pub decode: fn(&HashMap<String, AttributeValue>) -> Result<T, DecodeError>,
+}
+
+/// A type that read requests on `T`'s table can decode their results into.
+///
+/// Every item is a projection of itself that fetches all attributes.
+pub trait Projection<T: Item>:
+    for<'a> TryFrom<&'a HashMap<String, AttributeValue>, Error = DecodeError>
+{
+    /// The attributes to fetch, or `None` to fetch whole items.
+    fn attribute_names() -> Option<Vec<&'static str>>;
+}
+
+impl<T: Item> Projection<T> for T {
+    fn attribute_names() -> Option<Vec<&'static str>> {
+        None
+    }
+}
+
+/// Renders the projection expression for `P`, along with its expression attribute names.
+pub fn projection_expression<T: Item, P: Projection<T>>()
+-> Option<(String, HashMap<String, String>)> {
+    let attribute_names = P::attribute_names()?;
+    let mut names = HashMap::new();
+    let mut placeholders = Vec::new();
+    for (i, name) in attribute_names.into_iter().enumerate() {
+        let placeholder = format!("#p{}", i);
+        names.insert(placeholder.clone(), name.to_string());
+        placeholders.push(placeholder);
+    }
+    Some((placeholders.join(", "), names))
+}
@@ -1,6 +1,16 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
+use aws_sdk_dynamodb::config::http::HttpResponse;
+use aws_sdk_dynamodb::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_dynamodb::operation::transact_get_items::TransactGetItemsError;
+use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use futures::{Stream, StreamExt, stream};
+
 pub type RetryStrategy = Arc<dyn Fn(u32) -> Option<Duration> + Send + Sync>;
 
 pub struct ExponentialBackoff {
@@ -56,3 +66,317 @@ impl Default for ExponentialBackoff {
 pub fn default_retry_strategy() -> RetryStrategy {
     ExponentialBackoff::new().build()
 }
+
+/// DynamoDB error codes that say nothing about the request itself, so trying again may succeed.
+const RETRYABLE_CODES: &[&str] = &[
+    "ThrottlingException",
+    "ProvisionedThroughputExceededException",
+    "RequestLimitExceeded",
+    "TransactionConflictException",
+    // A retry of an idempotent transaction that's still being applied
+    "TransactionInProgressException",
+    "InternalServerError",
+    "ServiceUnavailable",
+];
+
+/// Cancellation reasons that make a canceled transaction worth retrying.
+const RETRYABLE_CANCELLATION_CODES: &[&str] = &[
+    "None",
+    "TransactionConflict",
+    "ThrottlingError",
+    "ProvisionedThroughputExceeded",
+];
+
+/// Whether a failed request is transient and can be retried.
+///
+/// Throttling, transaction conflicts, 5xx responses and timeouts are retryable. Everything else,
+/// such as failed conditions or validation errors, would fail the same way again. A canceled
+/// transaction is retryable only if every operation that caused it failed for a retryable reason.
+pub fn is_retryable<E>(err: &SdkError<E, HttpResponse>) -> bool
+where
+    E: ProvideErrorMetadata + 'static,
+{
+    match err {
+        SdkError::TimeoutError(_) | SdkError::ResponseError(_) => true,
+        SdkError::DispatchFailure(e) => e.is_timeout() || e.is_io(),
+        SdkError::ServiceError(e) => {
+            if e.raw().status().is_server_error() {
+                return true;
+            }
+            if let Some(reasons) = cancellation_codes(e.err()) {
+                return reasons.iter().any(|code| *code != "None")
+                    && reasons
+                        .iter()
+                        .all(|code| RETRYABLE_CANCELLATION_CODES.contains(code));
+            }
+            e.err()
+                .code()
+                .is_some_and(|code| RETRYABLE_CODES.contains(&code))
+        }
+        _ => false,
+    }
+}
+
+/// Whether a failed write can be sent again without risking applying it twice.
+///
+/// Only errors DynamoDB responded with are retried, as for [`is_retryable`]. A timeout, a lost
+/// connection or an unreadable response leaves it unknown whether the write was applied, so
+/// sending a conditional write or an `ADD` again could fail its condition or apply it twice.
+/// Such writes are only safe to retry with an idempotency token, which then uses
+/// [`is_retryable`].
+pub fn is_retryable_write<E>(err: &SdkError<E, HttpResponse>) -> bool
+where
+    E: ProvideErrorMetadata + 'static,
+{
+    matches!(err, SdkError::ServiceError(_)) && is_retryable(err)
+}
+
+fn cancellation_codes<E: 'static>(err: &E) -> Option<Vec<&str>> {
+    let err: &dyn Any = err;
+    let reasons = if let Some(TransactWriteItemsError::TransactionCanceledException(e)) =
+        err.downcast_ref::<TransactWriteItemsError>()
+    {
+        e.cancellation_reasons()
+    } else if let Some(TransactGetItemsError::TransactionCanceledException(e)) =
+        err.downcast_ref::<TransactGetItemsError>()
+    {
+        e.cancellation_reasons()
+    } else {
+        return None;
+    };
+    Some(reasons.iter().map(|r| r.code().unwrap_or("None")).collect())
+}
+
+/// Sends a request until it succeeds, fails with an error that isn't [retryable](is_retryable),
+/// or `strategy` gives up.
+pub async fn retry<T, E, F, Fut>(
+    strategy: &RetryStrategy,
+    f: F,
+) -> Result<T, SdkError<E, HttpResponse>>
+where
+    E: ProvideErrorMetadata + 'static,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, SdkError<E, HttpResponse>>>,
+{
+    retry_if(strategy, is_retryable, f).await
+}
+
+/// Like [`retry`] for writes that aren't idempotent, which are only retried on errors that are
+/// [retryable for writes](is_retryable_write).
+pub async fn retry_write<T, E, F, Fut>(
+    strategy: &RetryStrategy,
+    f: F,
+) -> Result<T, SdkError<E, HttpResponse>>
+where
+    E: ProvideErrorMetadata + 'static,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, SdkError<E, HttpResponse>>>,
+{
+    retry_if(strategy, is_retryable_write, f).await
+}
+
+async fn retry_if<T, E, F, Fut>(
+    strategy: &RetryStrategy,
+    retryable: fn(&SdkError<E, HttpResponse>) -> bool,
+    mut f: F,
+) -> Result<T, SdkError<E, HttpResponse>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, SdkError<E, HttpResponse>>>,
+{
+    let mut retries = 0;
+    loop {
+        match f().await {
+            Err(err) if retryable(&err) => match strategy(retries) {
+                Some(duration) => {
+                    retries += 1;
+                    tokio::time::sleep(duration).await;
+                }
+                None => return Err(err),
+            },
+            res => return res,
+        }
+    }
+}
+
+/// Pages through a query or scan, retrying each page with [`retry`].
+///
+/// `send` sends the request starting after the given key, and `next_key` reads the key to
+/// continue from out of a page.
+pub fn paginate<O, E, F, Fut>(
+    strategy: RetryStrategy,
+    send: F,
+    next_key: fn(&O) -> Option<HashMap<String, AttributeValue>>,
+) -> impl Stream<Item = Result<O, SdkError<E, HttpResponse>>>
+where
+    E: ProvideErrorMetadata + 'static,
+    F: Fn(Option<HashMap<String, AttributeValue>>) -> Fut,
+    Fut: Future<Output = Result<O, SdkError<E, HttpResponse>>>,
+{
+    // The key to start the next page from, or `None` once there are no more pages
+    stream::unfold(
+        (strategy, send, Some(None)),
+        move |(strategy, send, start_key)| async move {
+            let start_key = start_key?;
+            match retry(&strategy, || send(start_key.clone())).await {
+                Ok(page) => {
+                    let next = next_key(&page).map(Some);
+                    Some((Ok(page), (strategy, send, next)))
+                }
+                Err(err) => Some((Err(err), (strategy, send, None))),
+            }
+        },
+    )
+}
+
+/// Like [`paginate`], flattened into the items of each page.
+pub fn paginate_items<O, E, F, Fut>(
+    strategy: RetryStrategy,
+    send: F,
+    next_key: fn(&O) -> Option<HashMap<String, AttributeValue>>,
+    items: fn(O) -> Vec<HashMap<String, AttributeValue>>,
+) -> impl Stream<Item = Result<HashMap<String, AttributeValue>, SdkError<E, HttpResponse>>>
+where
+    E: ProvideErrorMetadata + 'static,
+    F: Fn(Option<HashMap<String, AttributeValue>>) -> Fut,
+    Fut: Future<Output = Result<O, SdkError<E, HttpResponse>>>,
+{
+    paginate(strategy, send, next_key).flat_map(move |page| {
+        let items = match page {
+            Ok(page) => items(page).into_iter().map(Ok).collect(),
+            Err(err) => vec![Err(err)],
+        };
+        stream::iter(items)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExponentialBackoff, is_retryable, is_retryable_write, retry, retry_write};
+    use aws_sdk_dynamodb::config::http::HttpResponse;
+    use aws_sdk_dynamodb::error::{ErrorMetadata, SdkError};
+    use aws_sdk_dynamodb::operation::put_item::PutItemError;
+    use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
+    use aws_sdk_dynamodb::types::CancellationReason;
+    use aws_sdk_dynamodb::types::error::TransactionCanceledException;
+    use aws_smithy_types::body::SdkBody;
+    use std::cell::Cell;
+
+    fn response(status: u16) -> HttpResponse {
+        HttpResponse::new(status.try_into().unwrap(), SdkBody::empty())
+    }
+
+    fn put_error(code: &str, status: u16) -> SdkError<PutItemError, HttpResponse> {
+        let err = PutItemError::generic(ErrorMetadata::builder().code(code).build());
+        SdkError::service_error(err, response(status))
+    }
+
+    fn canceled(codes: &[&str]) -> SdkError<TransactWriteItemsError, HttpResponse> {
+        let mut err = TransactionCanceledException::builder();
+        for code in codes {
+            err = err.cancellation_reasons(CancellationReason::builder().code(*code).build());
+        }
+        SdkError::service_error(
+            TransactWriteItemsError::TransactionCanceledException(err.build()),
+            response(400),
+        )
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(is_retryable(&put_error(
+            "ProvisionedThroughputExceededException",
+            400
+        )));
+        assert!(is_retryable(&put_error("ThrottlingException", 400)));
+        assert!(is_retryable(&put_error("InternalServerError", 500)));
+        assert!(is_retryable(&put_error("Unknown", 503)));
+        assert!(!is_retryable(&put_error(
+            "ConditionalCheckFailedException",
+            400
+        )));
+        assert!(!is_retryable(&put_error("ValidationException", 400)));
+        assert!(is_retryable(&SdkError::<PutItemError, _>::timeout_error(
+            "timed out"
+        )));
+        assert!(!is_retryable(
+            &SdkError::<PutItemError, _>::construction_failure("bad request")
+        ));
+    }
+
+    #[test]
+    fn test_is_retryable_write() {
+        assert!(is_retryable_write(&put_error("ThrottlingException", 400)));
+        assert!(is_retryable_write(&put_error("Unknown", 503)));
+        assert!(is_retryable_write(&canceled(&["TransactionConflict"])));
+        assert!(!is_retryable_write(&put_error(
+            "ConditionalCheckFailedException",
+            400
+        )));
+        // The write may have been applied before the response was lost
+        assert!(!is_retryable_write(
+            &SdkError::<PutItemError, _>::timeout_error("timed out")
+        ));
+        assert!(!is_retryable_write(
+            &SdkError::<PutItemError, _>::response_error("unreadable", response(200))
+        ));
+    }
+
+    #[test]
+    fn test_is_retryable_transaction_canceled() {
+        assert!(is_retryable(&canceled(&["None", "TransactionConflict"])));
+        assert!(is_retryable(&canceled(&["ThrottlingError", "None"])));
+        assert!(!is_retryable(&canceled(&[
+            "ConditionalCheckFailed",
+            "TransactionConflict"
+        ])));
+        assert!(!is_retryable(&canceled(&["None", "ValidationError"])));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry() {
+        let strategy = ExponentialBackoff::new().max_retries(3).build();
+
+        let attempts = Cell::new(0);
+        let res = retry(&strategy, || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                if attempt < 3 {
+                    Err(put_error("ThrottlingException", 400))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+        assert_eq!(res.unwrap(), 3);
+
+        attempts.set(0);
+        let res = retry(&strategy, || {
+            attempts.set(attempts.get() + 1);
+            async { Err::<(), _>(put_error("ThrottlingException", 400)) }
+        })
+        .await;
+        assert!(res.is_err());
+        assert_eq!(attempts.get(), 4, "the first attempt plus 3 retries");
+
+        attempts.set(0);
+        let res = retry(&strategy, || {
+            attempts.set(attempts.get() + 1);
+            async { Err::<(), _>(put_error("ConditionalCheckFailedException", 400)) }
+        })
+        .await;
+        assert!(res.is_err());
+        assert_eq!(attempts.get(), 1, "permanent errors aren't retried");
+
+        attempts.set(0);
+        let res = retry_write(&strategy, || {
+            attempts.set(attempts.get() + 1);
+            async { Err::<(), _>(SdkError::<PutItemError, _>::timeout_error("timed out")) }
+        })
+        .await;
+        assert!(res.is_err());
+        assert_eq!(attempts.get(), 1, "ambiguous write failures aren't retried");
+    }
+}
@@ -0,0 +1,84 @@
+//! Bridges any `Serialize`/`Deserialize` type to DynamoDB attribute values.
+//!
+//! Values are stored as native documents (objects as `M`, sequences as `L`, and scalars as `S`,
+//! `N`, `BOOL` or `NULL`) using the same mapping as `serde_json::Value`.
+//!
+//! Values are serialized through a `serde_json::Value`, which brings its limits along: integers
+//! outside the `i64`/`u64` range fail to encode, floats are written with `f64` precision, and
+//! byte buffers such as `Vec<u8>` become lists of numbers rather than binary (`B`) values. Use a
+//! dedicated field type or a `with` converter where those matter.
+
+use std::collections::HashMap;
+
+use ::serde::Serialize;
+use ::serde::de::DeserializeOwned;
+use ::serde::ser::Error as _;
+use aws_sdk_dynamodb::types::AttributeValue;
+use serde_json::Value;
+
+use crate::error::DecodeError;
+use crate::marshal::{FromAttributeValue, type_name};
+
+/// Converts a serializable value into an attribute value.
+pub fn to_attribute_value<T: Serialize + ?Sized>(
+    v: &T,
+) -> Result<AttributeValue, serde_json::Error> {
+    Ok(crate::marshal::serde_json::to_document(
+        serde_json::to_value(v)?,
+    ))
+}
+
+/// Converts an attribute value into a deserializable value.
+pub fn from_attribute_value<T: DeserializeOwned>(v: &AttributeValue) -> Result<T, DecodeError> {
+    serde_json::from_value(Value::from_attribute_value(v)?)
+        .map_err(|e| DecodeError::invalid(type_name(v), e))
+}
+
+/// Converts a serializable struct or map into a DynamoDB item.
+pub fn to_item<T: Serialize + ?Sized>(
+    v: &T,
+) -> Result<HashMap<String, AttributeValue>, serde_json::Error> {
+    match to_attribute_value(v)? {
+        AttributeValue::M(item) => Ok(item),
+        other => Err(serde_json::Error::custom(format!(
+            "expected a struct or map, found {}",
+            type_name(&other)
+        ))),
+    }
+}
+
+/// Converts a DynamoDB item into a deserializable value.
+pub fn from_item<T: DeserializeOwned>(
+    item: &HashMap<String, AttributeValue>,
+) -> Result<T, DecodeError> {
+    from_attribute_value(&AttributeValue::M(item.clone()))
+}
+
+/// Field converter for `#[aymond(serde)]`, storing the value as a native document.
+pub mod document {
+    use super::*;
+
+    pub fn to_attribute_value<T: Serialize>(v: &T) -> Result<AttributeValue, serde_json::Error> {
+        super::to_attribute_value(v)
+    }
+
+    pub fn from_attribute_value<T: DeserializeOwned>(v: &AttributeValue) -> Result<T, DecodeError> {
+        super::from_attribute_value(v)
+    }
+}
+
+/// Field converter for `#[aymond(json)]`, storing the value as a JSON string (`S`).
+pub mod json {
+    use super::*;
+
+    pub fn to_attribute_value<T: Serialize>(v: &T) -> Result<AttributeValue, serde_json::Error> {
+        serde_json::to_string(v).map(AttributeValue::S)
+    }
+
+    pub fn from_attribute_value<T: DeserializeOwned>(v: &AttributeValue) -> Result<T, DecodeError> {
+        let s = v
+            .as_s()
+            .map_err(|v| DecodeError::mismatch("S", type_name(v)))?;
+        serde_json::from_str(s).map_err(|e| DecodeError::invalid("S", e))
+    }
+}
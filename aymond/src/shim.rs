@@ -4,3 +4,14 @@ pub use aws_sdk_dynamodb;
 pub use aws_types;
 pub use futures;
 pub use tokio;
+
+#[cfg(feature = "chrono")]
+pub use chrono;
+#[cfg(feature = "rust_decimal")]
+pub use rust_decimal;
+#[cfg(feature = "serde_json")]
+pub use serde_json;
+#[cfg(feature = "time")]
+pub use time;
+#[cfg(feature = "uuid")]
+pub use uuid;
@@ -2,20 +2,30 @@ use aws_sdk_dynamodb::{
     config::http::HttpResponse,
     error::SdkError,
     operation::{create_table::CreateTableError, delete_table::DeleteTableError},
-    types::{AttributeDefinition, AttributeValue, KeySchemaElement},
+    types::{
+        AttributeDefinition, AttributeValue, ItemResponse, KeySchemaElement, Put, TransactGetItem,
+    },
 };
 use std::collections::HashMap;
 
-use crate::Aymond;
+use crate::{
+    Aymond,
+    error::{DecodeError, EncodeError},
+};
 
 pub trait NestedItem:
-    for<'a> From<&'a HashMap<String, AttributeValue>> + Into<HashMap<String, AttributeValue>>
+    for<'a> TryFrom<&'a HashMap<String, AttributeValue>, Error = DecodeError>
+    + TryInto<HashMap<String, AttributeValue>, Error = EncodeError>
 {
 }
 
 pub trait Item:
-    for<'a> From<&'a HashMap<String, AttributeValue>> + Into<HashMap<String, AttributeValue>>
+    for<'a> TryFrom<&'a HashMap<String, AttributeValue>, Error = DecodeError>
+    + TryInto<HashMap<String, AttributeValue>, Error = EncodeError>
 {
+    /// The item with every attribute optional, for responses that only carry some of them.
+    type Partial: for<'a> TryFrom<&'a HashMap<String, AttributeValue>, Error = DecodeError>;
+
     fn key_schemas() -> Vec<KeySchemaElement>;
     fn key_attribute_defintions() -> Vec<AttributeDefinition>;
 }
@@ -58,3 +68,92 @@ where
 
     fn condition_check(&'a self) -> CHK;
 }
+
+/// A put that can be part of a [`Tx`](crate::Tx), naming the key of the item it writes so that
+/// failures can be attributed to it.
+pub trait TxPut: TryInto<Put, Error: Into<EncodeError>> {
+    fn key_schemas() -> Vec<KeySchemaElement>;
+}
+
+/// Raw puts have no known key schema, so their failures are reported with an empty key.
+impl TxPut for Put {
+    fn key_schemas() -> Vec<KeySchemaElement> {
+        vec![]
+    }
+}
+
+/// A read that can be part of a `TransactGetItems` request, decoding into `Output`.
+pub trait TxGetRequest: TryInto<TransactGetItem, Error: Into<EncodeError>> {
+    type Output: for<'a> TryFrom<&'a HashMap<String, AttributeValue>, Error = DecodeError>;
+}
+
+/// The tuple of results of a `TransactGetItems` request, one `Option` per item requested.
+pub trait TxGetResults: Sized {
+    fn decode(responses: &[ItemResponse]) -> Result<Self, DecodeError>;
+}
+
+/// Appends the result of another read to a [`TxGetResults`] tuple.
+pub trait TxGetPush<T> {
+    type Output: TxGetResults;
+}
+
+fn decode_response<T>(responses: &[ItemResponse], index: usize) -> Result<Option<T>, DecodeError>
+where
+    T: for<'a> TryFrom<&'a HashMap<String, AttributeValue>, Error = DecodeError>,
+{
+    responses
+        .get(index)
+        .and_then(|r| r.item())
+        .map(T::try_from)
+        .transpose()
+        .map_err(|e| e.at_index(index))
+}
+
+macro_rules! impl_tx_get_results {
+    ($($t:ident: $i:tt),*) => {
+        impl<$($t),*> TxGetResults for ($(Option<$t>,)*)
+        where
+            $($t: for<'a> TryFrom<&'a HashMap<String, AttributeValue>, Error = DecodeError>,)*
+        {
+            #[allow(unused_variables)]
+            fn decode(responses: &[ItemResponse]) -> Result<Self, DecodeError> {
+                Ok(($(decode_response::<$t>(responses, $i)?,)*))
+            }
+        }
+    };
+}
+
+macro_rules! impl_tx_get_push {
+    ($($t:ident),*) => {
+        impl<$($t,)* Z> TxGetPush<Z> for ($(Option<$t>,)*)
+        where
+            $($t: for<'a> TryFrom<&'a HashMap<String, AttributeValue>, Error = DecodeError>,)*
+            Z: for<'a> TryFrom<&'a HashMap<String, AttributeValue>, Error = DecodeError>,
+        {
+            type Output = ($(Option<$t>,)* Option<Z>,);
+        }
+    };
+}
+
+impl_tx_get_results!();
+impl_tx_get_results!(A: 0);
+impl_tx_get_results!(A: 0, B: 1);
+impl_tx_get_results!(A: 0, B: 1, C: 2);
+impl_tx_get_results!(A: 0, B: 1, C: 2, D: 3);
+impl_tx_get_results!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_tx_get_results!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_tx_get_results!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_tx_get_results!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+impl_tx_get_results!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8);
+impl_tx_get_results!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9);
+
+impl_tx_get_push!();
+impl_tx_get_push!(A);
+impl_tx_get_push!(A, B);
+impl_tx_get_push!(A, B, C);
+impl_tx_get_push!(A, B, C, D);
+impl_tx_get_push!(A, B, C, D, E);
+impl_tx_get_push!(A, B, C, D, E, F);
+impl_tx_get_push!(A, B, C, D, E, F, G);
+impl_tx_get_push!(A, B, C, D, E, F, G, H);
+impl_tx_get_push!(A, B, C, D, E, F, G, H, I);
@@ -2,21 +2,131 @@ use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::marker::PhantomData;
 
-use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
 
-/// A segment of a DynamoDB document path for update expressions.
-#[derive(Clone)]
-pub enum PathSegment {
-    /// Map key — gets an expression attribute name (#uN).
-    Attr(String),
-    /// List index — literal [N] in the expression.
-    Index(usize),
+use crate::condition::BuiltExpression;
+pub use crate::condition::PathSegment;
+use crate::error::{ApplyError, DecodeError, EncodeError};
+use crate::eval::{self, resolve};
+use crate::traits::Item;
+
+/// The right-hand side of a SET action.
+enum Operand {
+    Value(AttributeValue),
+    Path(Vec<PathSegment>),
+    /// The path being set, rendered with the same placeholders.
+    Target,
+    IfNotExists(Box<Operand>, Box<Operand>),
+    ListAppend(Box<Operand>, Box<Operand>),
+    Plus(Box<Operand>, Box<Operand>),
+    Minus(Box<Operand>, Box<Operand>),
+}
+
+impl Operand {
+    fn render(
+        self,
+        target: &str,
+        counter: &mut usize,
+        names: &mut HashMap<String, String>,
+        values: &mut HashMap<String, AttributeValue>,
+    ) -> String {
+        match self {
+            Operand::Value(value) => {
+                let value_ph = format!(":u{}", counter);
+                *counter += 1;
+                values.insert(value_ph.clone(), value);
+                value_ph
+            }
+            Operand::Path(path) => UpdateExpr::render_path(&path, counter, names),
+            Operand::Target => target.to_string(),
+            Operand::IfNotExists(path, value) => {
+                let path = path.render(target, counter, names, values);
+                let value = value.render(target, counter, names, values);
+                format!("if_not_exists({}, {})", path, value)
+            }
+            Operand::ListAppend(a, b) => {
+                let a = a.render(target, counter, names, values);
+                let b = b.render(target, counter, names, values);
+                format!("list_append({}, {})", a, b)
+            }
+            Operand::Plus(a, b) => {
+                let a = a.render(target, counter, names, values);
+                let b = b.render(target, counter, names, values);
+                format!("{} + {}", a, b)
+            }
+            Operand::Minus(a, b) => {
+                let a = a.render(target, counter, names, values);
+                let b = b.render(target, counter, names, values);
+                format!("{} - {}", a, b)
+            }
+        }
+    }
+
+    /// The value of the operand, reading attributes from the item as it was before the update.
+    fn evaluate(
+        &self,
+        target: &[PathSegment],
+        item: &HashMap<String, AttributeValue>,
+    ) -> Result<AttributeValue, ApplyError> {
+        let read = |path: &[PathSegment]| {
+            resolve(item, path)
+                .cloned()
+                .ok_or_else(|| ApplyError::new(display_path(path), "attribute doesn't exist"))
+        };
+        match self {
+            Operand::Value(value) => Ok(value.clone()),
+            Operand::Path(path) => read(path),
+            Operand::Target => read(target),
+            Operand::IfNotExists(path, value) => {
+                let path = match path.as_ref() {
+                    Operand::Path(path) => path,
+                    _ => target,
+                };
+                match resolve(item, path) {
+                    Some(existing) => Ok(existing.clone()),
+                    None => value.evaluate(target, item),
+                }
+            }
+            Operand::ListAppend(a, b) => {
+                match (a.evaluate(target, item)?, b.evaluate(target, item)?) {
+                    (AttributeValue::L(mut a), AttributeValue::L(b)) => {
+                        a.extend(b);
+                        Ok(AttributeValue::L(a))
+                    }
+                    _ => Err(ApplyError::new(
+                        display_path(target),
+                        "list_append takes two lists",
+                    )),
+                }
+            }
+            Operand::Plus(a, b) | Operand::Minus(a, b) => {
+                let result = match (a.evaluate(target, item)?, b.evaluate(target, item)?) {
+                    (AttributeValue::N(a), AttributeValue::N(b)) => match self {
+                        Operand::Plus(..) => eval::add_numbers(&a, &b),
+                        _ => eval::subtract_numbers(&a, &b),
+                    },
+                    _ => Err("arithmetic takes two numbers"),
+                };
+                result
+                    .map(AttributeValue::N)
+                    .map_err(|reason| ApplyError::new(display_path(target), reason))
+            }
+        }
+    }
+
+    /// The list at the target path, or an empty list if it doesn't exist yet.
+    fn target_list() -> Operand {
+        Operand::IfNotExists(
+            Box::new(Operand::Target),
+            Box::new(Operand::Value(AttributeValue::L(vec![]))),
+        )
+    }
 }
 
 enum UpdateAction {
     Set {
         path: Vec<PathSegment>,
-        value: AttributeValue,
+        value: Operand,
     },
     Add {
         path: Vec<PathSegment>,
@@ -29,6 +139,8 @@ enum UpdateAction {
         path: Vec<PathSegment>,
         value: AttributeValue,
     },
+    /// A value that couldn't be encoded. Building the update returns the error.
+    Invalid(EncodeError),
 }
 
 pub struct UpdateExpr {
@@ -36,64 +148,187 @@ pub struct UpdateExpr {
 }
 
 impl UpdateExpr {
-    pub fn set(path: Vec<PathSegment>, value: AttributeValue) -> Self {
+    fn set_operand(path: Vec<PathSegment>, value: Operand) -> Self {
         Self {
             actions: vec![UpdateAction::Set { path, value }],
         }
     }
 
+    pub fn set(path: Vec<PathSegment>, value: AttributeValue) -> Self {
+        Self::set_operand(path, Operand::Value(value))
+    }
+
+    /// `ADD path value`: adds to a number or unions a set, treating a missing attribute as zero
+    /// or an empty set.
     pub fn add(path: Vec<PathSegment>, value: AttributeValue) -> Self {
         Self {
             actions: vec![UpdateAction::Add { path, value }],
         }
     }
 
+    /// `SET path = path - value`
+    pub fn subtract(path: Vec<PathSegment>, value: AttributeValue) -> Self {
+        Self::set_operand(
+            path,
+            Operand::Minus(Box::new(Operand::Target), Box::new(Operand::Value(value))),
+        )
+    }
+
+    /// `SET path = if_not_exists(path, value)`
+    pub fn set_if_not_exists(path: Vec<PathSegment>, value: AttributeValue) -> Self {
+        Self::set_operand(
+            path,
+            Operand::IfNotExists(Box::new(Operand::Target), Box::new(Operand::Value(value))),
+        )
+    }
+
+    /// `SET path = list_append(path, value)`, treating a missing list as empty.
+    pub fn list_append(path: Vec<PathSegment>, value: AttributeValue) -> Self {
+        Self::set_operand(
+            path,
+            Operand::ListAppend(
+                Box::new(Operand::target_list()),
+                Box::new(Operand::Value(value)),
+            ),
+        )
+    }
+
+    /// `SET path = list_append(value, path)`, treating a missing list as empty.
+    pub fn list_prepend(path: Vec<PathSegment>, value: AttributeValue) -> Self {
+        Self::set_operand(
+            path,
+            Operand::ListAppend(
+                Box::new(Operand::Value(value)),
+                Box::new(Operand::target_list()),
+            ),
+        )
+    }
+
+    /// `SET path = from`
+    pub fn copy(path: Vec<PathSegment>, from: Vec<PathSegment>) -> Self {
+        Self::set_operand(path, Operand::Path(from))
+    }
+
+    /// `SET path = from + value`
+    pub fn copy_plus(
+        path: Vec<PathSegment>,
+        from: Vec<PathSegment>,
+        value: AttributeValue,
+    ) -> Self {
+        Self::set_operand(
+            path,
+            Operand::Plus(
+                Box::new(Operand::Path(from)),
+                Box::new(Operand::Value(value)),
+            ),
+        )
+    }
+
+    /// `SET path = from - value`
+    pub fn copy_minus(
+        path: Vec<PathSegment>,
+        from: Vec<PathSegment>,
+        value: AttributeValue,
+    ) -> Self {
+        Self::set_operand(
+            path,
+            Operand::Minus(
+                Box::new(Operand::Path(from)),
+                Box::new(Operand::Value(value)),
+            ),
+        )
+    }
+
     pub fn remove(path: Vec<PathSegment>) -> Self {
         Self {
             actions: vec![UpdateAction::Remove { path }],
         }
     }
 
+    /// `SET path = value`, or `REMOVE path` when `value` is `None`.
+    pub fn set_or_remove(path: Vec<PathSegment>, value: Option<AttributeValue>) -> Self {
+        match value {
+            Some(value) => Self::set(path, value),
+            None => Self::remove(path),
+        }
+    }
+
+    /// [`UpdateExpr::set_or_remove`] with a value that may have failed to encode.
+    pub fn set_or_remove_encoded(
+        path: Vec<PathSegment>,
+        value: Option<Result<AttributeValue, EncodeError>>,
+    ) -> Self {
+        value
+            .transpose()
+            .map_or_else(Self::invalid, |value| Self::set_or_remove(path, value))
+    }
+
     pub fn delete(path: Vec<PathSegment>, value: AttributeValue) -> Self {
         Self {
             actions: vec![UpdateAction::Delete { path, value }],
         }
     }
 
+    /// Whether any action updates the top-level attribute `name` or a path within it.
+    pub fn updates_attribute(&self, name: &str) -> bool {
+        self.actions.iter().any(|action| {
+            let path = match action {
+                UpdateAction::Set { path, .. }
+                | UpdateAction::Add { path, .. }
+                | UpdateAction::Remove { path }
+                | UpdateAction::Delete { path, .. } => path,
+                UpdateAction::Invalid(_) => return false,
+            };
+            matches!(path.first(), Some(PathSegment::Attr(attr)) if attr == name)
+        })
+    }
+
+    /// An update whose value couldn't be encoded, which fails to build with `error`.
+    pub fn invalid(error: EncodeError) -> Self {
+        Self {
+            actions: vec![UpdateAction::Invalid(error)],
+        }
+    }
+
+    /// An update on a value that may have failed to encode.
+    fn with_value(
+        value: Result<AttributeValue, EncodeError>,
+        f: impl FnOnce(AttributeValue) -> UpdateExpr,
+    ) -> Self {
+        value.map_or_else(Self::invalid, f)
+    }
+
     pub fn and(mut self, mut other: UpdateExpr) -> UpdateExpr {
         self.actions.append(&mut other.actions);
         self
     }
 
-    pub fn build(
-        self,
-    ) -> (
-        String,
-        HashMap<String, String>,
-        HashMap<String, AttributeValue>,
-    ) {
+    /// Renders the actions into an update expression string, expression attribute names, and
+    /// expression attribute values.
+    ///
+    /// Fails with the first value that couldn't be encoded.
+    pub fn build(self) -> Result<BuiltExpression, EncodeError> {
         let mut names = HashMap::new();
         let mut values = HashMap::new();
         let mut counter: usize = 0;
         let mut set_parts = Vec::new();
         let mut remove_parts = Vec::new();
+        let mut add_parts = Vec::new();
         let mut delete_parts = Vec::new();
 
         for action in self.actions {
             match action {
                 UpdateAction::Set { path, value } => {
                     let path_str = Self::render_path(&path, &mut counter, &mut names);
-                    let value_ph = format!(":u{}", counter);
-                    counter += 1;
-                    values.insert(value_ph.clone(), value);
-                    set_parts.push(format!("{} = {}", path_str, value_ph));
+                    let value_str = value.render(&path_str, &mut counter, &mut names, &mut values);
+                    set_parts.push(format!("{} = {}", path_str, value_str));
                 }
                 UpdateAction::Add { path, value } => {
                     let path_str = Self::render_path(&path, &mut counter, &mut names);
                     let value_ph = format!(":u{}", counter);
                     counter += 1;
                     values.insert(value_ph.clone(), value);
-                    set_parts.push(format!("{} = {} + {}", path_str, path_str, value_ph));
+                    add_parts.push(format!("{} {}", path_str, value_ph));
                 }
                 UpdateAction::Remove { path } => {
                     let path_str = Self::render_path(&path, &mut counter, &mut names);
@@ -106,6 +341,7 @@ impl UpdateExpr {
                     values.insert(value_ph.clone(), value);
                     delete_parts.push(format!("{} {}", path_str, value_ph));
                 }
+                UpdateAction::Invalid(err) => return Err(err),
             }
         }
 
@@ -116,10 +352,136 @@ impl UpdateExpr {
         if !remove_parts.is_empty() {
             parts.push(format!("REMOVE {}", remove_parts.join(", ")));
         }
+        if !add_parts.is_empty() {
+            parts.push(format!("ADD {}", add_parts.join(", ")));
+        }
         if !delete_parts.is_empty() {
             parts.push(format!("DELETE {}", delete_parts.join(", ")));
         }
-        (parts.join(" "), names, values)
+        Ok((parts.join(" "), names, values))
+    }
+
+    /// Applies the update to an item the way DynamoDB would, without sending it.
+    ///
+    /// Every operand reads the item as it was before the update, and list elements removed in
+    /// the same update are removed by their original index. Nothing is changed if DynamoDB would
+    /// reject the update, such as arithmetic on a missing attribute, `ADD` to a string, or two
+    /// actions on overlapping paths like `tags` and `tags[0]`.
+    pub fn apply(&self, item: &mut HashMap<String, AttributeValue>) -> Result<(), ApplyError> {
+        self.check_paths()?;
+        let mut updated = item.clone();
+        let mut removes = Vec::new();
+        let mut writes = Vec::new();
+        for action in &self.actions {
+            match action {
+                UpdateAction::Set { path, value } => {
+                    writes.push((path, Some(value.evaluate(path, item)?)));
+                }
+                UpdateAction::Remove { path } => removes.push(path),
+                UpdateAction::Add { path, value } => {
+                    let error = |reason| ApplyError::new(display_path(path), reason);
+                    let added = match (resolve(item, path), value) {
+                        (
+                            None,
+                            value @ (AttributeValue::N(_)
+                            | AttributeValue::Ss(_)
+                            | AttributeValue::Ns(_)
+                            | AttributeValue::Bs(_)),
+                        ) => value.clone(),
+                        (Some(AttributeValue::N(a)), AttributeValue::N(b)) => {
+                            AttributeValue::N(eval::add_numbers(a, b).map_err(error)?)
+                        }
+                        (Some(AttributeValue::Ss(a)), AttributeValue::Ss(b)) => {
+                            AttributeValue::Ss(union(a, b, |set, v| set.contains(v)))
+                        }
+                        (Some(AttributeValue::Ns(a)), AttributeValue::Ns(b)) => {
+                            AttributeValue::Ns(union(a, b, |set, v| eval::contains_number(set, v)))
+                        }
+                        (Some(AttributeValue::Bs(a)), AttributeValue::Bs(b)) => {
+                            AttributeValue::Bs(union(a, b, |set, v| set.contains(v)))
+                        }
+                        _ => return Err(error("ADD takes a number or a set of the same type")),
+                    };
+                    writes.push((path, Some(added)));
+                }
+                UpdateAction::Delete { path, value } => {
+                    let error = || {
+                        ApplyError::new(display_path(path), "DELETE takes a set of the same type")
+                    };
+                    let remaining = match (resolve(item, path), value) {
+                        (None, _) => continue,
+                        (Some(AttributeValue::Ss(a)), AttributeValue::Ss(b)) => {
+                            let a: Vec<_> = a.iter().filter(|v| !b.contains(v)).cloned().collect();
+                            (!a.is_empty()).then_some(AttributeValue::Ss(a))
+                        }
+                        (Some(AttributeValue::Ns(a)), AttributeValue::Ns(b)) => {
+                            let a: Vec<_> = a
+                                .iter()
+                                .filter(|v| !eval::contains_number(b, v))
+                                .cloned()
+                                .collect();
+                            (!a.is_empty()).then_some(AttributeValue::Ns(a))
+                        }
+                        (Some(AttributeValue::Bs(a)), AttributeValue::Bs(b)) => {
+                            let a: Vec<_> = a.iter().filter(|v| !b.contains(v)).cloned().collect();
+                            (!a.is_empty()).then_some(AttributeValue::Bs(a))
+                        }
+                        _ => return Err(error()),
+                    };
+                    writes.push((path, remaining));
+                }
+                UpdateAction::Invalid(err) => {
+                    return Err(ApplyError::new(err.path.clone(), err.reason.clone()));
+                }
+            }
+        }
+
+        for (path, value) in writes {
+            match value {
+                Some(value) => write(&mut updated, path, value)?,
+                // A set emptied by DELETE is removed along with the attribute
+                None => removes.push(path),
+            }
+        }
+        // Highest indexes first, so that earlier elements of the same list keep their index
+        removes.sort_by(|a, b| compare_paths(b, a));
+        for path in removes {
+            remove(&mut updated, path)?;
+        }
+        *item = updated;
+        Ok(())
+    }
+
+    /// Rejects actions on paths that overlap, where one path is or contains the other, and on
+    /// paths that conflict, using the same attribute as both a map and a list.
+    fn check_paths(&self) -> Result<(), ApplyError> {
+        let paths: Vec<&[PathSegment]> = self
+            .actions
+            .iter()
+            .filter_map(|action| match action {
+                UpdateAction::Set { path, .. }
+                | UpdateAction::Add { path, .. }
+                | UpdateAction::Remove { path }
+                | UpdateAction::Delete { path, .. } => Some(path.as_slice()),
+                UpdateAction::Invalid(_) => None,
+            })
+            .collect();
+        for (i, a) in paths.iter().enumerate() {
+            for b in &paths[i + 1..] {
+                let shared = a.iter().zip(b.iter()).take_while(|(a, b)| a == b).count();
+                let reason = match (a.get(shared), b.get(shared)) {
+                    (None, _) | (_, None) => "overlaps with",
+                    (Some(PathSegment::Attr(_)), Some(PathSegment::Index(_)))
+                    | (Some(PathSegment::Index(_)), Some(PathSegment::Attr(_))) => "conflicts with",
+                    _ => continue,
+                };
+                return Err(ApplyError::new(
+                    display_path(b),
+                    format!("the path {} {}", reason, display_path(a)),
+                ));
+            }
+        }
+        Ok(())
     }
 
     fn render_path(
@@ -147,12 +509,113 @@ impl UpdateExpr {
     }
 }
 
+/// The container holding the last segment of `path`, which must already exist.
+fn parent_mut<'a>(
+    item: &'a mut HashMap<String, AttributeValue>,
+    path: &[PathSegment],
+) -> Result<Option<&'a mut AttributeValue>, ApplyError> {
+    let invalid = || ApplyError::new(display_path(path), "the document path doesn't exist");
+    let Some((PathSegment::Attr(name), rest)) = path.split_first() else {
+        return Err(invalid());
+    };
+    if rest.is_empty() {
+        return Ok(None);
+    }
+    let mut value = item.get_mut(name).ok_or_else(invalid)?;
+    for seg in &rest[..rest.len() - 1] {
+        value = match (seg, value) {
+            (PathSegment::Attr(key), AttributeValue::M(map)) => map.get_mut(key),
+            (PathSegment::Index(i), AttributeValue::L(list)) => list.get_mut(*i),
+            _ => None,
+        }
+        .ok_or_else(invalid)?;
+    }
+    Ok(Some(value))
+}
+
+/// Writes `value` at `path`; an index past the end of a list appends to it.
+fn write(
+    item: &mut HashMap<String, AttributeValue>,
+    path: &[PathSegment],
+    value: AttributeValue,
+) -> Result<(), ApplyError> {
+    let invalid = || ApplyError::new(display_path(path), "the document path doesn't exist");
+    match (parent_mut(item, path)?, path.last()) {
+        (None, Some(PathSegment::Attr(name))) => {
+            item.insert(name.clone(), value);
+        }
+        (Some(AttributeValue::M(map)), Some(PathSegment::Attr(key))) => {
+            map.insert(key.clone(), value);
+        }
+        (Some(AttributeValue::L(list)), Some(PathSegment::Index(i))) => match list.get_mut(*i) {
+            Some(element) => *element = value,
+            None => list.push(value),
+        },
+        _ => return Err(invalid()),
+    }
+    Ok(())
+}
+
+/// Removes the value at `path`, doing nothing if it doesn't exist.
+fn remove(
+    item: &mut HashMap<String, AttributeValue>,
+    path: &[PathSegment],
+) -> Result<(), ApplyError> {
+    match (parent_mut(item, path)?, path.last()) {
+        (None, Some(PathSegment::Attr(name))) => {
+            item.remove(name);
+        }
+        (Some(AttributeValue::M(map)), Some(PathSegment::Attr(key))) => {
+            map.remove(key);
+        }
+        (Some(AttributeValue::L(list)), Some(PathSegment::Index(i))) if *i < list.len() => {
+            list.remove(*i);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn union<T: Clone>(a: &[T], b: &[T], contains: impl Fn(&[T], &T) -> bool) -> Vec<T> {
+    let mut union = a.to_vec();
+    for v in b {
+        if !contains(&union, v) {
+            union.push(v.clone());
+        }
+    }
+    union
+}
+
+fn compare_paths(a: &[PathSegment], b: &[PathSegment]) -> std::cmp::Ordering {
+    let key = |seg: &PathSegment| match seg {
+        PathSegment::Attr(name) => (0, name.clone(), 0),
+        PathSegment::Index(i) => (1, String::new(), *i),
+    };
+    a.iter().map(key).cmp(b.iter().map(key))
+}
+
+/// Renders a path for error messages, e.g. `tags[2].name`.
+fn display_path(path: &[PathSegment]) -> String {
+    let mut rendered = String::new();
+    for seg in path {
+        match seg {
+            PathSegment::Attr(name) if rendered.is_empty() => rendered.push_str(name),
+            PathSegment::Attr(name) => {
+                rendered.push('.');
+                rendered.push_str(name);
+            }
+            PathSegment::Index(i) => rendered.push_str(&format!("[{}]", i)),
+        }
+    }
+    rendered
+}
+
 pub trait IntoUpdateValue {
-    fn into_update_value(self) -> AttributeValue;
+    fn into_update_value(self) -> Result<AttributeValue, EncodeError>;
 }
 
 pub trait IntoUpdateNumberValue {
-    fn into_update_number_value(self) -> AttributeValue;
+    fn into_update_number_value(self) -> Result<AttributeValue, EncodeError>;
 }
 
 pub trait IntoUpdateSetValue {
@@ -162,42 +625,44 @@ pub trait IntoUpdateSetValue {
 }
 
 impl IntoUpdateValue for String {
-    fn into_update_value(self) -> AttributeValue {
-        AttributeValue::S(self)
+    fn into_update_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::S(self))
     }
 }
 
 impl IntoUpdateValue for &str {
-    fn into_update_value(self) -> AttributeValue {
-        AttributeValue::S(self.to_string())
+    fn into_update_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::S(self.to_string()))
     }
 }
 
 impl IntoUpdateValue for bool {
-    fn into_update_value(self) -> AttributeValue {
-        AttributeValue::Bool(self)
+    fn into_update_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::Bool(self))
     }
 }
 
 impl IntoUpdateValue for Vec<u8> {
-    fn into_update_value(self) -> AttributeValue {
-        AttributeValue::B(aws_sdk_dynamodb::primitives::Blob::new(self))
+    fn into_update_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::B(aws_sdk_dynamodb::primitives::Blob::new(
+            self,
+        )))
     }
 }
 
 impl IntoUpdateValue for HashSet<String> {
-    fn into_update_value(self) -> AttributeValue {
-        AttributeValue::Ss(self.into_iter().collect())
+    fn into_update_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::Ss(self.into_iter().collect()))
     }
 }
 
 impl IntoUpdateValue for HashSet<Vec<u8>> {
-    fn into_update_value(self) -> AttributeValue {
-        AttributeValue::Bs(
+    fn into_update_value(self) -> Result<AttributeValue, EncodeError> {
+        Ok(AttributeValue::Bs(
             self.into_iter()
                 .map(aws_sdk_dynamodb::primitives::Blob::new)
                 .collect(),
-        )
+        ))
     }
 }
 
@@ -222,14 +687,14 @@ macro_rules! impl_into_update_numeric {
     ($($t:ty),+) => {
         $(
             impl IntoUpdateValue for $t {
-                fn into_update_value(self) -> AttributeValue {
-                    AttributeValue::N(self.to_string())
+                fn into_update_value(self) -> Result<AttributeValue, EncodeError> {
+                    Ok(AttributeValue::N(self.to_string()))
                 }
             }
 
             impl IntoUpdateNumberValue for $t {
-                fn into_update_number_value(self) -> AttributeValue {
-                    AttributeValue::N(self.to_string())
+                fn into_update_number_value(self) -> Result<AttributeValue, EncodeError> {
+                    Ok(AttributeValue::N(self.to_string()))
                 }
             }
         )+
@@ -238,16 +703,90 @@ macro_rules! impl_into_update_numeric {
 
 impl_into_update_numeric!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
 
+macro_rules! impl_into_update_float {
+    ($($t:ty),+) => {
+        $(
+            impl IntoUpdateValue for $t {
+                fn into_update_value(self) -> Result<AttributeValue, EncodeError> {
+                    crate::marshal::encode_float(self)
+                }
+            }
+
+            impl IntoUpdateNumberValue for $t {
+                fn into_update_number_value(self) -> Result<AttributeValue, EncodeError> {
+                    crate::marshal::encode_float(self)
+                }
+            }
+        )+
+    };
+}
+
+impl_into_update_float!(f32, f64);
+
+macro_rules! impl_into_update_set_numeric {
+    ($($t:ty),+) => {
+        $(
+            impl IntoUpdateSetValue for $t {
+                fn into_update_set_value(values: HashSet<Self>) -> AttributeValue {
+                    AttributeValue::Ns(values.into_iter().map(|v| v.to_string()).collect())
+                }
+            }
+        )+
+    };
+}
+
+impl_into_update_set_numeric!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
 pub trait UpdatePathRoot: Sized {
     fn with_prefix(path: Vec<PathSegment>) -> Self;
 }
 
-pub struct ScalarUpdatePath<T: IntoUpdateValue> {
+/// Names the update path of a type the derive doesn't know by name, the counterpart of
+/// [`HasConditionPath`](crate::condition::HasConditionPath). `O` is [`Required`] or
+/// [`Optional`].
+pub trait HasUpdatePath {
+    type Path<O>: UpdatePathRoot;
+}
+
+/// Marks an update path to a required attribute.
+pub struct Required;
+
+/// Marks an update path to an `Option` field, whose `set` also takes `None` to remove the
+/// attribute.
+pub struct Optional;
+
+/// Selects the [`IntoOptionalValue`] implementation for plain values.
+pub struct ByValue;
+
+/// Selects the [`IntoOptionalValue`] implementation for `Option`s.
+pub struct ByOption;
+
+/// A value for the `set` of an optional attribute: anything that converts into the attribute's
+/// type, or an `Option` of it.
+///
+/// `M` only keeps the two implementations apart, so that both `set("x")` and `set(None)` infer.
+pub trait IntoOptionalValue<T, M> {
+    fn into_optional_value(self) -> Option<T>;
+}
+
+impl<T, V: Into<T>> IntoOptionalValue<T, ByValue> for V {
+    fn into_optional_value(self) -> Option<T> {
+        Some(self.into())
+    }
+}
+
+impl<T> IntoOptionalValue<T, ByOption> for Option<T> {
+    fn into_optional_value(self) -> Option<T> {
+        self
+    }
+}
+
+pub struct ScalarUpdatePath<T: IntoUpdateValue, O = Required> {
     path: Vec<PathSegment>,
-    _phantom: PhantomData<T>,
+    _phantom: PhantomData<(T, O)>,
 }
 
-impl<T: IntoUpdateValue> UpdatePathRoot for ScalarUpdatePath<T> {
+impl<T: IntoUpdateValue, O> UpdatePathRoot for ScalarUpdatePath<T, O> {
     fn with_prefix(path: Vec<PathSegment>) -> Self {
         Self {
             path,
@@ -258,17 +797,123 @@ impl<T: IntoUpdateValue> UpdatePathRoot for ScalarUpdatePath<T> {
 
 impl<T: IntoUpdateValue> ScalarUpdatePath<T> {
     pub fn set(self, v: impl Into<T>) -> UpdateExpr {
-        UpdateExpr::set(self.path, v.into().into_update_value())
+        UpdateExpr::with_value(v.into().into_update_value(), |value| {
+            UpdateExpr::set(self.path, value)
+        })
     }
 }
 
-impl<T: IntoUpdateNumberValue + IntoUpdateValue> ScalarUpdatePath<T> {
+impl<T: IntoUpdateValue> ScalarUpdatePath<T, Optional> {
+    /// Sets the attribute, or removes it if `v` is `None`.
+    pub fn set<M>(self, v: impl IntoOptionalValue<T, M>) -> UpdateExpr {
+        let value = v.into_optional_value().map(T::into_update_value);
+        UpdateExpr::set_or_remove_encoded(self.path, value)
+    }
+}
+
+impl<T: IntoUpdateValue, O> ScalarUpdatePath<T, O> {
+    /// Sets the attribute only if it doesn't exist yet.
+    pub fn set_if_not_exists(self, v: impl Into<T>) -> UpdateExpr {
+        UpdateExpr::with_value(v.into().into_update_value(), |value| {
+            UpdateExpr::set_if_not_exists(self.path, value)
+        })
+    }
+
+    /// Sets the attribute to the value of another attribute of the same type.
+    pub fn copy_from<P>(self, other: ScalarUpdatePath<T, P>) -> UpdateExpr {
+        UpdateExpr::copy(self.path, other.path)
+    }
+
+    pub fn remove(self) -> UpdateExpr {
+        UpdateExpr::remove(self.path)
+    }
+}
+
+impl<T: IntoUpdateNumberValue + IntoUpdateValue, O> ScalarUpdatePath<T, O> {
+    /// Adds `v` to the number, initialising it to `v` if it doesn't exist.
     #[allow(clippy::should_implement_trait)]
     pub fn add(self, v: impl Into<T>) -> UpdateExpr {
-        UpdateExpr::add(self.path, v.into().into_update_number_value())
+        UpdateExpr::with_value(v.into().into_update_number_value(), |value| {
+            UpdateExpr::add(self.path, value)
+        })
+    }
+
+    pub fn subtract(self, v: impl Into<T>) -> UpdateExpr {
+        UpdateExpr::with_value(v.into().into_update_number_value(), |value| {
+            UpdateExpr::subtract(self.path, value)
+        })
+    }
+
+    /// Sets the attribute to another attribute plus `v`.
+    pub fn copy_from_plus<P>(self, other: ScalarUpdatePath<T, P>, v: impl Into<T>) -> UpdateExpr {
+        UpdateExpr::with_value(v.into().into_update_number_value(), |value| {
+            UpdateExpr::copy_plus(self.path, other.path, value)
+        })
+    }
+
+    /// Sets the attribute to another attribute minus `v`.
+    pub fn copy_from_minus<P>(self, other: ScalarUpdatePath<T, P>, v: impl Into<T>) -> UpdateExpr {
+        UpdateExpr::with_value(v.into().into_update_number_value(), |value| {
+            UpdateExpr::copy_minus(self.path, other.path, value)
+        })
+    }
+}
+
+impl<T: IntoUpdateValue> UpdateValuePath for ScalarUpdatePath<T> {
+    type Value = T;
+
+    fn to_attribute_value(v: T) -> Result<AttributeValue, EncodeError> {
+        v.into_update_value()
+    }
+}
+
+/// A typed path to an attribute marshalled by a `#[aymond(with = "...")]` converter.
+pub struct ConvertedUpdatePath<T, O = Required> {
+    path: Vec<PathSegment>,
+    convert: fn(&T) -> Result<AttributeValue, EncodeError>,
+    _phantom: PhantomData<O>,
+}
+
+impl<T, O> ConvertedUpdatePath<T, O> {
+    pub fn new(
+        path: Vec<PathSegment>,
+        convert: fn(&T) -> Result<AttributeValue, EncodeError>,
+    ) -> Self {
+        Self {
+            path,
+            convert,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn remove(self) -> UpdateExpr {
+        UpdateExpr::remove(self.path)
     }
 }
 
+impl<T> ConvertedUpdatePath<T> {
+    pub fn set(self, v: impl Into<T>) -> UpdateExpr {
+        UpdateExpr::with_value((self.convert)(&v.into()), |value| {
+            UpdateExpr::set(self.path, value)
+        })
+    }
+}
+
+impl<T> ConvertedUpdatePath<T, Optional> {
+    /// Sets the attribute, or removes it if `v` is `None`.
+    pub fn set<M>(self, v: impl IntoOptionalValue<T, M>) -> UpdateExpr {
+        let value = v.into_optional_value().map(|v| (self.convert)(&v));
+        UpdateExpr::set_or_remove_encoded(self.path, value)
+    }
+}
+
+/// A path to an attribute that can be written as a whole, so that lists of it can be extended.
+pub trait UpdateValuePath: UpdatePathRoot {
+    type Value;
+
+    fn to_attribute_value(v: Self::Value) -> Result<AttributeValue, EncodeError>;
+}
+
 pub struct ListUpdatePath<T: UpdatePathRoot> {
     path_prefix: Vec<PathSegment>,
     _phantom: PhantomData<T>,
@@ -284,19 +929,92 @@ impl<T: UpdatePathRoot> UpdatePathRoot for ListUpdatePath<T> {
 }
 
 impl<T: UpdatePathRoot> ListUpdatePath<T> {
+    /// The element at index `i`; `remove()` on it drops the element and shifts the rest down.
     pub fn index(&self, i: usize) -> T {
         let mut path = self.path_prefix.clone();
         path.push(PathSegment::Index(i));
         T::with_prefix(path)
     }
+
+    pub fn remove(self) -> UpdateExpr {
+        UpdateExpr::remove(self.path_prefix)
+    }
 }
 
-pub struct SetUpdatePath<T: IntoUpdateSetValue + Eq + Hash> {
-    path: Vec<PathSegment>,
+impl<T: UpdateValuePath> ListUpdatePath<T> {
+    /// Adds `values` to the end of the list, creating it if it doesn't exist.
+    pub fn append(self, values: Vec<T::Value>) -> UpdateExpr {
+        UpdateExpr::with_value(Self::to_attribute_value(values), |value| {
+            UpdateExpr::list_append(self.path_prefix, value)
+        })
+    }
+
+    /// Adds `values` to the start of the list, creating it if it doesn't exist.
+    pub fn prepend(self, values: Vec<T::Value>) -> UpdateExpr {
+        UpdateExpr::with_value(Self::to_attribute_value(values), |value| {
+            UpdateExpr::list_prepend(self.path_prefix, value)
+        })
+    }
+}
+
+impl<T: UpdateValuePath> UpdateValuePath for ListUpdatePath<T> {
+    type Value = Vec<T::Value>;
+
+    fn to_attribute_value(v: Self::Value) -> Result<AttributeValue, EncodeError> {
+        v.into_iter()
+            .enumerate()
+            .map(|(i, v)| T::to_attribute_value(v).map_err(|err| err.at_index(i)))
+            .collect::<Result<_, _>>()
+            .map(AttributeValue::L)
+    }
+}
+
+pub struct MapUpdatePath<T: UpdatePathRoot> {
+    path_prefix: Vec<PathSegment>,
     _phantom: PhantomData<T>,
 }
 
-impl<T: IntoUpdateSetValue + Eq + Hash> UpdatePathRoot for SetUpdatePath<T> {
+impl<T: UpdatePathRoot> UpdatePathRoot for MapUpdatePath<T> {
+    fn with_prefix(path: Vec<PathSegment>) -> Self {
+        Self {
+            path_prefix: path,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: UpdatePathRoot> MapUpdatePath<T> {
+    pub fn key(&self, k: impl Into<String>) -> T {
+        let mut path = self.path_prefix.clone();
+        path.push(PathSegment::Attr(k.into()));
+        T::with_prefix(path)
+    }
+
+    pub fn remove(self) -> UpdateExpr {
+        UpdateExpr::remove(self.path_prefix)
+    }
+}
+
+impl<T: UpdateValuePath> UpdateValuePath for MapUpdatePath<T> {
+    type Value = HashMap<String, T::Value>;
+
+    fn to_attribute_value(v: Self::Value) -> Result<AttributeValue, EncodeError> {
+        v.into_iter()
+            .map(|(k, v)| match T::to_attribute_value(v) {
+                Ok(v) => Ok((k, v)),
+                Err(err) => Err(err.at_attr(&k)),
+            })
+            .collect::<Result<_, _>>()
+            .map(AttributeValue::M)
+    }
+}
+
+pub struct SetUpdatePath<T: IntoUpdateSetValue + Eq + Hash, O = Required> {
+    path: Vec<PathSegment>,
+    _phantom: PhantomData<(T, O)>,
+}
+
+impl<T: IntoUpdateSetValue + Eq + Hash, O> UpdatePathRoot for SetUpdatePath<T, O> {
     fn with_prefix(path: Vec<PathSegment>) -> Self {
         Self {
             path,
@@ -309,6 +1027,36 @@ impl<T: IntoUpdateSetValue + Eq + Hash> SetUpdatePath<T> {
     pub fn set(self, values: HashSet<T>) -> UpdateExpr {
         UpdateExpr::set(self.path, T::into_update_set_value(values))
     }
+}
+
+impl<T: IntoUpdateSetValue + Eq + Hash> SetUpdatePath<T, Optional> {
+    /// Sets the attribute, or removes it if `values` is `None`.
+    pub fn set<M>(self, values: impl IntoOptionalValue<HashSet<T>, M>) -> UpdateExpr {
+        let value = values.into_optional_value().map(T::into_update_set_value);
+        UpdateExpr::set_or_remove(self.path, value)
+    }
+}
+
+impl<T: IntoUpdateSetValue + Eq + Hash, O> SetUpdatePath<T, O> {
+    pub fn remove(self) -> UpdateExpr {
+        UpdateExpr::remove(self.path)
+    }
+
+    /// Adds a member to the set, creating the set if it doesn't exist.
+    pub fn insert(self, v: impl Into<T>) -> UpdateExpr {
+        let mut values = HashSet::new();
+        values.insert(v.into());
+        UpdateExpr::add(self.path, T::into_update_set_value(values))
+    }
+
+    /// Adds every member of `values` to the set, creating the set if it doesn't exist.
+    ///
+    /// DynamoDB rejects empty sets, so an empty `values` fails to build.
+    pub fn insert_all(self, values: HashSet<T>) -> UpdateExpr {
+        UpdateExpr::with_value(Self::non_empty(values), |value| {
+            UpdateExpr::add(self.path, value)
+        })
+    }
 
     pub fn delete(self, v: impl Into<T>) -> UpdateExpr {
         let mut values = HashSet::new();
@@ -316,8 +1064,26 @@ impl<T: IntoUpdateSetValue + Eq + Hash> SetUpdatePath<T> {
         UpdateExpr::delete(self.path, T::into_update_set_value(values))
     }
 
+    /// Removes every member of `values` from the set. An empty `values` fails to build.
     pub fn delete_set(self, values: HashSet<T>) -> UpdateExpr {
-        UpdateExpr::delete(self.path, T::into_update_set_value(values))
+        UpdateExpr::with_value(Self::non_empty(values), |value| {
+            UpdateExpr::delete(self.path, value)
+        })
+    }
+
+    fn non_empty(values: HashSet<T>) -> Result<AttributeValue, EncodeError> {
+        if values.is_empty() {
+            return Err(EncodeError::new("DynamoDB sets can't be empty"));
+        }
+        Ok(T::into_update_set_value(values))
+    }
+}
+
+impl<T: IntoUpdateSetValue + Eq + Hash> UpdateValuePath for SetUpdatePath<T> {
+    type Value = HashSet<T>;
+
+    fn to_attribute_value(v: Self::Value) -> Result<AttributeValue, EncodeError> {
+        Ok(T::into_update_set_value(v))
     }
 }
 
@@ -337,13 +1103,65 @@ impl IntoOptionalUpdateExpr for () {
     }
 }
 
+/// Selects what an update returns, and the type the returned attributes decode into.
+///
+/// [`ReturnNew`] and [`ReturnOld`] return the whole item, [`UpdatedNew`] and [`UpdatedOld`]
+/// only the attributes the update touched, decoded into the item's partial type.
+pub trait ReturnSelector<T: Item> {
+    type Output;
+
+    fn return_value(&self) -> ReturnValue;
+
+    fn decode(attrs: &HashMap<String, AttributeValue>) -> Result<Self::Output, DecodeError>;
+}
+
+/// Returns the item as it is after the update.
+pub struct ReturnNew;
+
+/// Returns the item as it was before the update.
+pub struct ReturnOld;
+
+/// Returns the updated attributes as they are after the update.
+pub struct UpdatedNew;
+
+/// Returns the updated attributes as they were before the update.
+pub struct UpdatedOld;
+
+macro_rules! impl_return_selector {
+    ($selector:ty, $value:ident, $output:ty) => {
+        impl<T: Item> ReturnSelector<T> for $selector {
+            type Output = $output;
+
+            fn return_value(&self) -> ReturnValue {
+                ReturnValue::$value
+            }
+
+            fn decode(
+                attrs: &HashMap<String, AttributeValue>,
+            ) -> Result<Self::Output, DecodeError> {
+                <$output>::try_from(attrs)
+            }
+        }
+    };
+}
+
+impl_return_selector!(ReturnNew, AllNew, T);
+impl_return_selector!(ReturnOld, AllOld, T);
+impl_return_selector!(UpdatedNew, UpdatedNew, T::Partial);
+impl_return_selector!(UpdatedOld, UpdatedOld, T::Partial);
+
 #[cfg(test)]
 mod tests {
-    use super::{PathSegment, UpdateExpr};
+    use super::{
+        ConvertedUpdatePath, ListUpdatePath, Optional, PathSegment, ScalarUpdatePath,
+        SetUpdatePath, UpdateExpr, UpdatePathRoot,
+    };
+    use crate::error::EncodeError;
     use aws_sdk_dynamodb::types::AttributeValue;
+    use std::collections::{HashMap, HashSet};
 
     #[test]
-    fn test_update_expr_render_set_remove_and_delete() {
+    fn test_update_expr_render_add_remove_and_delete() {
         let expr = UpdateExpr::add(
             vec![PathSegment::Attr("count".into())],
             AttributeValue::N("10".into()),
@@ -353,7 +1171,243 @@ mod tests {
             vec![PathSegment::Attr("labels".into())],
             AttributeValue::Ss(vec!["rust".into()]),
         ));
-        let (rendered, _, _) = expr.build();
-        assert_eq!(rendered, "SET #u0 = #u0 + :u1 REMOVE #u2 DELETE #u3 :u4");
+        let (rendered, _, _) = expr.build().unwrap();
+        assert_eq!(rendered, "REMOVE #u2 ADD #u0 :u1 DELETE #u3 :u4");
+    }
+
+    #[test]
+    fn test_update_expr_render_functions_and_arithmetic() {
+        let attr = |name: &str| vec![PathSegment::Attr(name.into())];
+        let n = |v: &str| AttributeValue::N(v.into());
+        let expr = UpdateExpr::set_if_not_exists(attr("created"), n("1"))
+            .and(UpdateExpr::subtract(attr("stock"), n("2")))
+            .and(UpdateExpr::list_append(
+                attr("tags"),
+                AttributeValue::L(vec![]),
+            ))
+            .and(UpdateExpr::list_prepend(
+                attr("tags"),
+                AttributeValue::L(vec![]),
+            ))
+            .and(UpdateExpr::copy_plus(
+                attr("total"),
+                attr("subtotal"),
+                n("5"),
+            ));
+        let (rendered, names, values) = expr.build().unwrap();
+        assert_eq!(
+            rendered,
+            "SET #u0 = if_not_exists(#u0, :u1), #u2 = #u2 - :u3, \
+             #u4 = list_append(if_not_exists(#u4, :u5), :u6), \
+             #u7 = list_append(:u8, if_not_exists(#u7, :u9)), #u10 = #u11 + :u12"
+        );
+        assert_eq!(names["#u11"], "subtotal");
+        assert_eq!(values[":u5"], AttributeValue::L(vec![]));
+        assert_eq!(values.len(), 7);
+    }
+
+    #[test]
+    fn test_optional_set_and_index_remove() {
+        let attr = |name: &str| vec![PathSegment::Attr(name.into())];
+        let nickname = || ScalarUpdatePath::<String, Optional>::with_prefix(attr("nickname"));
+        let tags = ListUpdatePath::<ScalarUpdatePath<String>>::with_prefix(attr("tags"));
+        let expr = nickname()
+            .set(None)
+            .and(tags.index(2).remove())
+            .and(nickname().set("Al"));
+        let (rendered, _, values) = expr.build().unwrap();
+        assert_eq!(rendered, "SET #u2 = :u3 REMOVE #u0, #u1[2]");
+        assert_eq!(values[":u3"], AttributeValue::S("Al".into()));
+    }
+
+    fn path<P: UpdatePathRoot>(name: &str) -> P {
+        P::with_prefix(vec![PathSegment::Attr(name.into())])
+    }
+
+    fn s(v: &str) -> AttributeValue {
+        AttributeValue::S(v.into())
+    }
+
+    fn n(v: &str) -> AttributeValue {
+        AttributeValue::N(v.into())
+    }
+
+    #[test]
+    fn test_apply_reads_the_original_item() {
+        let mut item = HashMap::from([
+            ("stock".to_string(), n("10")),
+            ("subtotal".to_string(), n("95")),
+            (
+                "tags".to_string(),
+                AttributeValue::L(vec![s("a"), s("b"), s("c")]),
+            ),
+        ]);
+        let tags = || path::<ListUpdatePath<ScalarUpdatePath<String>>>("tags");
+        let expr = path::<ScalarUpdatePath<i32>>("stock")
+            .subtract(3)
+            .and(
+                path::<ScalarUpdatePath<i32>>("total")
+                    .copy_from_plus(path::<ScalarUpdatePath<i32>>("stock"), 5),
+            )
+            .and(path::<ScalarUpdatePath<i64>>("created").set_if_not_exists(1000))
+            .and(path::<ScalarUpdatePath<i32>>("views").add(1))
+            .and(tags().index(0).remove())
+            .and(tags().index(2).remove());
+        expr.apply(&mut item).unwrap();
+
+        assert_eq!(item["stock"], n("7"));
+        assert_eq!(item["total"], n("15"));
+        assert_eq!(item["created"], n("1000"));
+        assert_eq!(item["views"], n("1"));
+        // Both removes saw the original three elements
+        assert_eq!(item["tags"], AttributeValue::L(vec![s("b")]));
+
+        // DynamoDB rejects updating a list alongside its elements
+        let overlapping = tags()
+            .index(0)
+            .remove()
+            .and(tags().index(2).remove())
+            .and(tags().append(vec!["d".to_string()]));
+        let err = overlapping.apply(&mut item).unwrap_err();
+        assert_eq!(err.path, "tags");
+        assert_eq!(err.reason, "the path overlaps with tags[0]");
+        assert_eq!(item["tags"], AttributeValue::L(vec![s("b")]));
+    }
+
+    #[test]
+    fn test_apply_rejects_conflicting_paths() {
+        let mut item = HashMap::new();
+        let same = path::<ScalarUpdatePath<i32>>("views")
+            .add(1)
+            .and(path::<ScalarUpdatePath<i32>>("views").set(2));
+        assert_eq!(
+            same.apply(&mut item).unwrap_err().reason,
+            "the path overlaps with views"
+        );
+
+        let conflicting = UpdateExpr::set(
+            vec![PathSegment::Attr("doc".into()), PathSegment::Index(0)],
+            n("1"),
+        )
+        .and(UpdateExpr::set(
+            vec![
+                PathSegment::Attr("doc".into()),
+                PathSegment::Attr("key".into()),
+            ],
+            n("2"),
+        ));
+        assert_eq!(
+            conflicting.apply(&mut item).unwrap_err().reason,
+            "the path conflicts with doc[0]"
+        );
+
+        // Siblings don't overlap
+        let siblings = UpdateExpr::set(
+            vec![
+                PathSegment::Attr("doc".into()),
+                PathSegment::Attr("a".into()),
+            ],
+            n("1"),
+        )
+        .and(UpdateExpr::set(
+            vec![
+                PathSegment::Attr("doc".into()),
+                PathSegment::Attr("b".into()),
+            ],
+            n("2"),
+        ));
+        item.insert("doc".to_string(), AttributeValue::M(HashMap::new()));
+        siblings.apply(&mut item).unwrap();
+    }
+
+    #[test]
+    fn test_apply_sets_and_rejections() {
+        let mut item = HashMap::from([
+            ("name".to_string(), s("Al")),
+            (
+                "labels".to_string(),
+                AttributeValue::Ss(vec!["a".into(), "b".into()]),
+            ),
+        ]);
+        let labels = || path::<SetUpdatePath<String>>("labels");
+        labels().insert("c").apply(&mut item).unwrap();
+        labels()
+            .delete_set(HashSet::from(["a".to_string(), "b".to_string()]))
+            .apply(&mut item)
+            .unwrap();
+        assert_eq!(item["labels"], AttributeValue::Ss(vec!["c".into()]));
+        labels().delete("c").apply(&mut item).unwrap();
+        assert!(!item.contains_key("labels"));
+
+        let err = labels().insert_all(HashSet::new()).build().unwrap_err();
+        assert_eq!(err.reason, "DynamoDB sets can't be empty");
+        assert!(labels().delete_set(HashSet::new()).build().is_err());
+        assert!(
+            labels()
+                .insert_all(HashSet::new())
+                .apply(&mut item)
+                .is_err()
+        );
+
+        path::<ScalarUpdatePath<String, Optional>>("name")
+            .set(None)
+            .apply(&mut item)
+            .unwrap();
+        assert!(item.is_empty());
+
+        // Nothing is written when any action is rejected
+        let rejected = path::<ScalarUpdatePath<String>>("name")
+            .set("Bo")
+            .and(path::<ScalarUpdatePath<i32>>("stock").subtract(1));
+        let err = rejected.apply(&mut item).unwrap_err();
+        assert_eq!(err.path, "stock");
+        assert!(item.is_empty());
+
+        let nested = UpdateExpr::set(
+            vec![
+                PathSegment::Attr("address".into()),
+                PathSegment::Attr("city".into()),
+            ],
+            s("Seattle"),
+        );
+        assert_eq!(nested.apply(&mut item).unwrap_err().path, "address.city");
+
+        // ADD only creates numbers and sets
+        let add = UpdateExpr::add(vec![PathSegment::Attr("name".into())], s("Bo"));
+        assert_eq!(
+            add.apply(&mut item).unwrap_err().reason,
+            "ADD takes a number or a set of the same type"
+        );
+        assert!(item.is_empty());
+    }
+
+    #[test]
+    fn test_values_that_fail_to_encode() {
+        let expires = || {
+            ConvertedUpdatePath::<u64>::new(vec![PathSegment::Attr("expires".into())], |_| {
+                Err(EncodeError::new("out of range"))
+            })
+        };
+        let err = expires().set(1u64).build().unwrap_err();
+        assert_eq!(err.to_string(), "failed to encode value: out of range");
+
+        let mut item = HashMap::from([("name".to_string(), s("Al"))]);
+        let expr = path::<ScalarUpdatePath<String>>("name")
+            .set("Bo")
+            .and(expires().set(1u64));
+        assert!(expr.apply(&mut item).is_err());
+        assert_eq!(item["name"], s("Al"));
+
+        let ratio = || path::<ScalarUpdatePath<f64>>("ratio");
+        assert!(ratio().add(0.5).build().is_ok());
+        let err = ratio().set(f64::NAN).build().unwrap_err();
+        assert_eq!(err.reason, "NaN can't be stored as a DynamoDB number");
+        assert!(ratio().add(f64::INFINITY).build().is_err());
+        assert!(
+            path::<ScalarUpdatePath<f32>>("ratio")
+                .subtract(f32::NEG_INFINITY)
+                .build()
+                .is_err()
+        );
     }
 }